@@ -1,6 +1,9 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
+use crate::event::EventOverflow;
+
 #[derive(Parser, Debug)]
 #[command(
     name = "nusalaunchd",
@@ -32,8 +35,25 @@ pub struct CliArgs {
     )]
     pub log_level: LogLevel,
     
+    /// More log output: -v for debug, -vv for trace (overrides --log-level)
+    #[arg(short = 'v', long = "verbose", action = ArgAction::Count, global = true, conflicts_with = "quiet")]
+    pub verbose: u8,
+    
+    /// Only log errors (overrides --log-level)
+    #[arg(short = 'q', long = "quiet", action = ArgAction::Count, global = true)]
+    pub quiet: u8,
+    
+    /// Format of the daemon's own log output
+    #[arg(
+        long = "log-format",
+        value_enum,
+        default_value = "text",
+        global = true
+    )]
+    pub log_format: LogFormat,
+    
     /// Run in foreground (don't daemonize)
-    #[arg(short = 'f', long = "foreground", global = true)]
+    #[arg(short = 'f', long = "foreground", visible_alias = "no-daemonize", global = true)]
     pub foreground: bool,
     
     /// Configuration file to test/validate
@@ -43,6 +63,29 @@ pub struct CliArgs {
     /// Dry run - don't actually start jobs
     #[arg(long = "dry-run", global = true)]
     pub dry_run: bool,
+    
+    /// Control socket of the running daemon
+    #[arg(
+        long = "control-socket",
+        default_value = "/run/nusalaunchd/control.sock",
+        global = true
+    )]
+    pub control_socket: PathBuf,
+    
+    /// Seconds the daemon has to accept the connection and answer each request; also
+    /// how long `job start --wait` waits and `job stop` gives a job before SIGKILL
+    /// (default: 5 to connect, replies unbounded)
+    #[arg(long = "timeout", global = true)]
+    pub timeout: Option<u64>,
+    
+    /// Output format of status, job list, job status, socket status, validate, and export
+    #[arg(
+        long = "format",
+        value_enum,
+        default_value = "table",
+        global = true
+    )]
+    pub format: OutputFormat,
 }
 
 #[derive(Subcommand, Debug)]
@@ -70,6 +113,17 @@ pub enum Commands {
         strict: bool,
     },
     
+    /// Print the jobs of a config file as written, with all defaults filled in (TOML, or
+    /// JSON/YAML with --format)
+    Export {
+        /// Configuration file to export
+        path: PathBuf,
+        
+        /// Output file (default: stdout)
+        #[arg(short = 'o', long = "output")]
+        output: Option<PathBuf>,
+    },
+    
     /// Generate example configuration
     Example {
         /// Type of example to generate
@@ -90,46 +144,144 @@ pub enum Commands {
         /// Watch mode (continuously update)
         #[arg(short = 'w', long = "watch")]
         watch: bool,
-        
-        /// Output format
-        #[arg(short = 'f', long = "format", value_enum, default_value = "table")]
-        format: OutputFormat,
     },
     
-    /// Manage the control socket
+    /// Manage activation sockets
     Socket {
         #[command(subcommand)]
         socket_command: SocketCommands,
     },
+    
+    /// Have the daemon re-read every job config in its config directory
+    Reload,
+    
+    /// Show job counts by state, total restarts and daemon uptime
+    Summary,
+    
+    /// Print job events as they happen, until interrupted
+    Events,
 }
 
 #[derive(Parser, Debug)]
 pub struct DaemonOptions {
     /// PID file location
     #[arg(long = "pid-file", default_value = "/run/nusalaunchd.pid")]
-    pid_file: PathBuf,
+    pub pid_file: PathBuf,
+    
+    /// Log file of the daemon once it has detached from the terminal (unused with --foreground)
+    #[arg(long = "log-file", default_value = "/var/log/nusalaunchd.log")]
+    pub log_file: PathBuf,
     
-    /// State directory
+    /// State directory (created with mode 0700)
     #[arg(long = "state-dir", default_value = "/var/lib/nusalaunchd")]
-    state_dir: PathBuf,
+    pub state_dir: PathBuf,
     
-    /// Runtime directory
+    /// Runtime directory, holding the control socket (created with mode 0755)
     #[arg(long = "runtime-dir", default_value = "/run/nusalaunchd")]
-    runtime_dir: PathBuf,
+    pub runtime_dir: PathBuf,
+    
+    /// Group owning the control socket; its members may run nusaload (name or numeric GID)
+    #[arg(long = "control-group")]
+    pub control_group: Option<String>,
+    
+    /// Boot target; its enabled jobs are started (all keep_alive jobs if it has none set up)
+    #[arg(long = "target", default_value = "multi-user")]
+    pub target: String,
+    
+    /// Also load configs from subdirectories of the config directory; jobs under
+    /// <config-dir>/<name>/ only start automatically when booting into target <name>
+    #[arg(long = "recursive-config")]
+    pub recursive_config: bool,
     
     /// Maximum number of jobs
     #[arg(long = "max-jobs", default_value = "512")]
-    max_jobs: usize,
+    pub max_jobs: usize,
+    
+    /// Daemon settings file (log level, limits), re-read on reload-config
+    #[arg(long = "daemon-config")]
+    pub daemon_config: Option<PathBuf>,
+    
+    /// Serve Prometheus metrics at http://<addr>/metrics (e.g. 127.0.0.1:9464)
+    #[arg(long = "metrics-addr")]
+    pub metrics_addr: Option<SocketAddr>,
+    
+    /// Load, reload or unload jobs as files in the config directory change
+    #[arg(long = "watch-config")]
+    pub watch_config: bool,
+    
+    /// Load and validate all configs, then exit without starting any jobs
+    #[arg(long = "validate-only")]
+    pub validate_only: bool,
+    
+    /// Append all job events to this file as JSON lines
+    #[arg(long = "event-log")]
+    pub event_log_path: Option<PathBuf>,
+    
+    /// Rotate the event log once it exceeds this many bytes (0 = never)
+    #[arg(long = "event-log-max-size", default_value = "10485760")]
+    pub event_log_max_size: u64,
+    
+    /// Number of rotated event logs to keep
+    #[arg(long = "event-log-max-files", default_value = "5")]
+    pub event_log_max_files: usize,
+    
+    /// Seconds every job gets to exit on shutdown before SIGKILL, overriding
+    /// each job's `stop_timeout_sec`
+    #[arg(long = "shutdown-timeout")]
+    pub shutdown_timeout: Option<u64>,
+    
+    /// Job events buffered for the event processor
+    #[arg(long = "event-capacity", default_value = "100")]
+    pub event_capacity: usize,
+    
+    /// Process exits and due restarts buffered for the supervisor
+    #[arg(long = "restart-capacity", default_value = "50")]
+    pub restart_capacity: usize,
+    
+    /// What happens to new events while the event buffer is full
+    #[arg(long = "event-overflow", value_enum, default_value = "block")]
+    pub event_overflow: EventOverflow,
 }
 
 impl Default for DaemonOptions {
     fn default() -> Self {
         Self {
             pid_file: PathBuf::from("/run/nusalaunchd.pid"),
+            log_file: PathBuf::from("/var/log/nusalaunchd.log"),
             state_dir: PathBuf::from("/var/lib/nusalaunchd"),
             runtime_dir: PathBuf::from("/run/nusalaunchd"),
+            control_group: None,
+            target: crate::job::enabled::DEFAULT_TARGET.to_string(),
+            recursive_config: false,
             max_jobs: 512,
+            daemon_config: None,
+            metrics_addr: None,
+            watch_config: false,
+            validate_only: false,
+            event_log_path: None,
+            event_log_max_size: 10 * 1024 * 1024,
+            event_log_max_files: 5,
+            shutdown_timeout: None,
+            event_capacity: 100,
+            restart_capacity: 50,
+            event_overflow: EventOverflow::Block,
+        }
+    }
+}
+
+impl DaemonOptions {
+    /// Resolve relative paths against the current directory, so they still name the
+    /// same files after the daemon changes directory to `/`
+    pub fn make_absolute(&mut self) -> std::io::Result<()> {
+        for path in [&mut self.pid_file, &mut self.log_file, &mut self.state_dir, &mut self.runtime_dir] {
+            *path = std::path::absolute(&*path)?;
+        }
+        
+        for path in [&mut self.daemon_config, &mut self.event_log_path].into_iter().flatten() {
+            *path = std::path::absolute(&*path)?;
         }
+        
+        Ok(())
     }
 }
 
@@ -137,35 +289,28 @@ impl Default for DaemonOptions {
 pub enum JobCommands {
     /// Start a job
     Start {
-        /// Job label(s)
+        /// Job label(s); patterns such as 'web-*' match loaded jobs
         labels: Vec<String>,
         
-        /// Wait for job to fully start
+        /// Wait for job to fully start, for up to `--timeout` seconds (default 30)
         #[arg(short = 'w', long = "wait")]
         wait: bool,
-        
-        /// Timeout in seconds for wait
-        #[arg(long = "timeout", default_value = "30")]
-        timeout: u64,
     },
     
     /// Stop a job
     Stop {
-        /// Job label(s)
+        /// Job label(s); patterns such as 'web-*' match loaded jobs
         labels: Vec<String>,
         
-        /// Force stop (SIGKILL)
-        #[arg(short = 'f', long = "force")]
+        /// Force stop (SIGKILL); without it, `--timeout` is the grace before SIGKILL
+        /// (default: the job's `stop_timeout_sec`)
+        #[arg(long = "force")]
         force: bool,
-        
-        /// Timeout before force stop
-        #[arg(long = "timeout", default_value = "10")]
-        timeout: u64,
     },
     
     /// Restart a job
     Restart {
-        /// Job label(s)
+        /// Job label(s); patterns such as 'web-*' match loaded jobs
         labels: Vec<String>,
         
         /// Skip if not running
@@ -173,17 +318,45 @@ pub enum JobCommands {
         skip_if_stopped: bool,
     },
     
+    /// Send a signal to a running job (e.g. USR1 to reopen its logs)
+    Kill {
+        /// Job label(s); patterns such as 'web-*' match loaded jobs
+        labels: Vec<String>,
+        
+        /// Signal name (USR1, SIGHUP) or number
+        #[arg(short = 's', long = "signal", default_value = "TERM")]
+        signal: String,
+    },
+    
+    /// Stop a job and remove it from the running daemon
+    Unload {
+        /// Job label(s); patterns such as 'web-*' match loaded jobs
+        labels: Vec<String>,
+    },
+    
+    /// Check that a job would start, without starting it
+    Check {
+        /// Job label(s); patterns such as 'web-*' match loaded jobs
+        labels: Vec<String>,
+    },
+    
+    /// Show a job's recent restarts and the exits that caused them
+    History {
+        /// Job label
+        label: String,
+    },
+    
     /// Show job status
     Status {
         /// Job label (optional, shows all if omitted)
         label: Option<String>,
         
-        /// Show full configuration
-        #[arg(short = 'c', long = "config")]
+        /// Show the effective configuration, as `export` prints it
+        #[arg(long = "config")]
         show_config: bool,
         
         /// Show process tree
-        #[arg(short = 't', long = "tree")]
+        #[arg(long = "tree")]
         show_tree: bool,
     },
     
@@ -193,12 +366,16 @@ pub enum JobCommands {
         labels: Vec<String>,
         
         /// Target (default, multi-user, graphical)
-        #[arg(short = 't', long = "target", default_value = "multi-user")]
+        #[arg(long = "target", default_value = "multi-user")]
         target: String,
         
         /// Now (start immediately)
         #[arg(short = 'n', long = "now")]
         now: bool,
+        
+        /// State directory of the daemon
+        #[arg(long = "state-dir", default_value = "/var/lib/nusalaunchd")]
+        state_dir: PathBuf,
     },
     
     /// Disable job at boot
@@ -209,6 +386,10 @@ pub enum JobCommands {
         /// Stop if currently running
         #[arg(short = 's', long = "stop")]
         stop: bool,
+        
+        /// State directory of the daemon
+        #[arg(long = "state-dir", default_value = "/var/lib/nusalaunchd")]
+        state_dir: PathBuf,
     },
     
     /// Reload job configuration
@@ -231,7 +412,7 @@ pub enum JobCommands {
         lines: usize,
         
         /// Follow logs
-        #[arg(short = 'f', long = "follow")]
+        #[arg(long = "follow")]
         follow: bool,
         
         /// Show since timestamp
@@ -261,15 +442,15 @@ pub enum JobCommands {
         #[arg(long = "failed")]
         failed_only: bool,
         
-        /// Output format
-        #[arg(short = 'o', long = "output", value_enum, default_value = "table")]
-        output_format: OutputFormat,
+        /// Show only jobs whose current process started within SECONDS of system boot
+        #[arg(long = "since-boot", value_name = "SECONDS")]
+        since_boot: Option<u64>,
     },
 }
 
 #[derive(Subcommand, Debug)]
 pub enum SocketCommands {
-    /// Show socket status
+    /// Show activation sockets with their activation and pending-connection counts
     Status,
     
     /// Activate socket
@@ -285,7 +466,7 @@ pub enum SocketCommands {
     },
 }
 
-#[derive(ValueEnum, Clone, Debug)]
+#[derive(ValueEnum, Clone, Debug, PartialEq)]
 pub enum LogLevel {
     Error,
     Warn,
@@ -294,6 +475,12 @@ pub enum LogLevel {
     Trace,
 }
 
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
 #[derive(ValueEnum, Clone, Debug)]
 pub enum ExampleType {
     Simple,
@@ -311,6 +498,19 @@ pub enum OutputFormat {
     Plain,
 }
 
+impl CliArgs {
+    /// Log level to run with: `--log-level`, unless `-q` (errors only) or `-v` (one level
+    /// above info per flag, up to trace) is given
+    pub fn effective_log_level(&self) -> LogLevel {
+        match (self.quiet, self.verbose) {
+            (0, 0) => self.log_level.clone(),
+            (_, 0) => LogLevel::Error,
+            (_, 1) => LogLevel::Debug,
+            _ => LogLevel::Trace,
+        }
+    }
+}
+
 impl std::fmt::Display for LogLevel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -0,0 +1,64 @@
+//! Output of `job check`
+
+use crate::cli::args::OutputFormat;
+use crate::cli::output::encode;
+use crate::process::check::SpawnCheck;
+use crate::util::error::Result;
+
+/// Render spawn checks: a block per job, or JSON/YAML if asked
+pub fn render(checks: &[SpawnCheck], format: &OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Json | OutputFormat::Yaml => encode(checks, format),
+        OutputFormat::Table | OutputFormat::Plain => Ok(checks.iter().map(render_text).collect()),
+    }
+}
+
+fn render_text(check: &SpawnCheck) -> String {
+    let mut out = format!(
+        "{} {}: {}\n",
+        if check.ok() { "✓" } else { "✗" },
+        check.label,
+        if check.ok() { "would start" } else { "would not start" }
+    );
+    
+    let mut command = check.program.display().to_string();
+    for arg in &check.arguments {
+        command.push(' ');
+        command.push_str(arg);
+    }
+    
+    let user = match &check.user {
+        Some(name) => format!("{} (uid {}, gid {})", name, check.uid, check.gid),
+        None => format!("uid {}, gid {}", check.uid, check.gid),
+    };
+    
+    let mut environment = if check.environment.is_empty() {
+        "none set".to_string()
+    } else {
+        check.environment.join(", ")
+    };
+    if check.clear_environment {
+        environment.push_str(" (daemon environment cleared)");
+    }
+    
+    let working_dir = check.working_directory.as_ref()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_else(|| "(daemon's)".to_string());
+    
+    for (name, value) in [
+        ("command", command),
+        ("user", user),
+        ("working dir", working_dir),
+        ("environment", environment),
+        ("stdout", check.stdout.clone()),
+        ("stderr", check.stderr.clone()),
+    ] {
+        out.push_str(&format!("  {:<12} {}\n", format!("{}:", name), value));
+    }
+    
+    for problem in &check.problems {
+        out.push_str(&format!("  ✗ {}\n", problem));
+    }
+    
+    out
+}
\ No newline at end of file
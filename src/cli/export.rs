@@ -0,0 +1,42 @@
+//! Canonical form of a job config for the `export` command
+
+use serde::Serialize;
+
+use crate::cli::args::OutputFormat;
+use crate::cli::output::encode;
+use crate::job::config::{JobConfig, JobConfigFile};
+use crate::util::error::{NusaError, Result};
+
+/// Several jobs, in the `[[job]]` form a config file declares them in
+#[derive(Serialize)]
+struct JobList<'a> {
+    job: &'a [JobConfig],
+}
+
+/// Render every job of a config file as `render` does; several jobs stay `[[job]]` tables
+pub fn render_file(file: &JobConfigFile, format: &OutputFormat) -> Result<String> {
+    let jobs = match file {
+        JobConfigFile::Single(config) => return render(config, format),
+        JobConfigFile::Multiple(jobs) => JobList { job: jobs },
+    };
+    
+    match format {
+        OutputFormat::Json | OutputFormat::Yaml => encode(&jobs, format),
+        OutputFormat::Table | OutputFormat::Plain => toml::to_string_pretty(&jobs)
+            .map_err(|e| NusaError::System(format!("Failed to encode config: {}", e))),
+    }
+}
+
+/// Render a loaded config with every defaulted field spelled out: TOML, or JSON/YAML if asked
+pub fn render(config: &JobConfig, format: &OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Json | OutputFormat::Yaml => encode(config, format),
+        OutputFormat::Table | OutputFormat::Plain => to_toml(config),
+    }
+}
+
+/// The config as pretty-printed TOML
+pub fn to_toml(config: &JobConfig) -> Result<String> {
+    toml::to_string_pretty(config)
+        .map_err(|e| NusaError::System(format!("Failed to encode config: {}", e)))
+}
\ No newline at end of file
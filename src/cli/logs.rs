@@ -0,0 +1,234 @@
+//! Tailing of job log files for the `logs` command
+
+use chrono::{DateTime, FixedOffset};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::job::JobConfig;
+use crate::process::output::resolve_log_path;
+use crate::util::error::{NusaError, Result};
+
+/// How often `--follow` checks for appended output
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Bytes read per step when scanning a file backwards for line breaks
+const TAIL_CHUNK: u64 = 8192;
+
+/// Files a job logs to, stdout first, without duplicates
+pub fn log_paths(config: &JobConfig) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    
+    for path in [&config.logging.stdout_path, &config.logging.stderr_path].into_iter().flatten() {
+        let path = resolve_log_path(config, path);
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    
+    paths
+}
+
+/// Parse an RFC3339 `--since` / `--until` value
+pub fn parse_timestamp(value: &str) -> Result<DateTime<FixedOffset>> {
+    DateTime::parse_from_rfc3339(value)
+        .map_err(|e| NusaError::System(format!("Invalid timestamp '{}' (expected RFC3339): {}", value, e)))
+}
+
+/// Timestamp at the start of a line, e.g. `2024-05-01T12:00:00Z started`
+pub fn line_timestamp(line: &str) -> Option<DateTime<FixedOffset>> {
+    let first = line.split_whitespace().next()?;
+    DateTime::parse_from_rfc3339(first.trim_start_matches('[').trim_end_matches(']')).ok()
+}
+
+/// `--since` / `--until` window
+///
+/// Lines without a timestamp prefix (wrapped messages, stack traces) go with the line before them.
+#[derive(Debug, Clone, Default)]
+pub struct TimeFilter {
+    since: Option<DateTime<FixedOffset>>,
+    until: Option<DateTime<FixedOffset>>,
+    last_accepted: bool,
+}
+
+impl TimeFilter {
+    pub fn new(since: Option<DateTime<FixedOffset>>, until: Option<DateTime<FixedOffset>>) -> Self {
+        Self {
+            since,
+            until,
+            last_accepted: false,
+        }
+    }
+    
+    /// Whether any bound is set
+    pub fn is_active(&self) -> bool {
+        self.since.is_some() || self.until.is_some()
+    }
+    
+    /// Whether to show a line
+    pub fn accept(&mut self, line: &str) -> bool {
+        if !self.is_active() {
+            return true;
+        }
+        
+        if let Some(time) = line_timestamp(line) {
+            self.last_accepted = self.since.map_or(true, |since| time >= since)
+                && self.until.map_or(true, |until| time <= until);
+        }
+        
+        self.last_accepted
+    }
+}
+
+/// The last `count` lines of a file, plus the offset to follow from
+pub fn tail_lines(path: &Path, count: usize) -> io::Result<(Vec<String>, u64)> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+    
+    // Read backwards until the buffer holds `count` complete lines
+    let mut buf: Vec<u8> = Vec::new();
+    let mut pos = len;
+    while pos > 0 && buf.iter().filter(|b| **b == b'\n').count() <= count {
+        let step = pos.min(TAIL_CHUNK);
+        pos -= step;
+        
+        let mut chunk = vec![0u8; step as usize];
+        file.seek(SeekFrom::Start(pos))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&buf);
+        buf = chunk;
+    }
+    
+    let text = String::from_utf8_lossy(&buf);
+    let lines: Vec<&str> = text.lines().collect();
+    let skip = lines.len().saturating_sub(count);
+    
+    Ok((lines[skip..].iter().map(|line| line.to_string()).collect(), len))
+}
+
+/// The last `count` lines of a file that pass `filter`, plus the offset to follow from
+pub fn filtered_tail(path: &Path, count: usize, filter: &mut TimeFilter) -> io::Result<(Vec<String>, u64)> {
+    let mut file = File::open(path)?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    
+    let text = String::from_utf8_lossy(&buf);
+    let lines: Vec<&str> = text.lines().filter(|line| filter.accept(line)).collect();
+    let skip = lines.len().saturating_sub(count);
+    
+    Ok((lines[skip..].iter().map(|line| line.to_string()).collect(), buf.len() as u64))
+}
+
+/// A file being followed like `tail -f`
+struct FollowedFile {
+    path: PathBuf,
+    offset: u64,
+    partial: Vec<u8>,
+}
+
+impl FollowedFile {
+    /// Complete lines appended since the last read
+    fn read_new_lines(&mut self) -> io::Result<Vec<String>> {
+        let len = match std::fs::metadata(&self.path) {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        
+        // Truncated or rotated: start over from the beginning
+        if len < self.offset {
+            self.offset = 0;
+            self.partial.clear();
+        }
+        
+        if len == self.offset {
+            return Ok(Vec::new());
+        }
+        
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut appended = Vec::new();
+        file.take(len - self.offset).read_to_end(&mut appended)?;
+        self.offset += appended.len() as u64;
+        self.partial.extend_from_slice(&appended);
+        
+        // Keep an unterminated last line until the rest of it arrives
+        let complete = match self.partial.iter().rposition(|b| *b == b'\n') {
+            Some(index) => self.partial.drain(..=index).collect::<Vec<u8>>(),
+            None => return Ok(Vec::new()),
+        };
+        
+        Ok(String::from_utf8_lossy(&complete).lines().map(|line| line.to_string()).collect())
+    }
+}
+
+/// Print a job's recent log lines and, with `follow`, keep printing new ones until Ctrl-C
+pub async fn show(
+    config: &JobConfig,
+    lines: usize,
+    follow: bool,
+    since: Option<&str>,
+    until: Option<&str>,
+) -> Result<()> {
+    let paths = log_paths(config);
+    if paths.is_empty() {
+        return Err(NusaError::System(format!(
+            "Job '{}' has no stdout_path or stderr_path; its output goes to the daemon log",
+            config.label
+        )));
+    }
+    
+    let since = since.map(parse_timestamp).transpose()?;
+    let until = until.map(parse_timestamp).transpose()?;
+    let show_headers = paths.len() > 1;
+    
+    let mut followed = Vec::new();
+    for path in paths {
+        let mut filter = TimeFilter::new(since, until);
+        let result = if filter.is_active() {
+            filtered_tail(&path, lines, &mut filter)
+        } else {
+            tail_lines(&path, lines)
+        };
+        
+        let (recent, offset) = match result {
+            Ok(result) => result,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (Vec::new(), 0),
+            Err(e) => return Err(NusaError::System(format!("Failed to read {}: {}", path.display(), e))),
+        };
+        
+        if show_headers {
+            println!("==> {} <==", path.display());
+        }
+        for line in recent {
+            println!("{}", line);
+        }
+        
+        followed.push((FollowedFile { path, offset, partial: Vec::new() }, filter));
+    }
+    
+    if !follow {
+        return Ok(());
+    }
+    
+    loop {
+        tokio::select! {
+            _ = tokio::time::sleep(FOLLOW_INTERVAL) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+        
+        for (file, filter) in followed.iter_mut() {
+            let new_lines = file.read_new_lines()
+                .map_err(|e| NusaError::System(format!("Failed to read {}: {}", file.path.display(), e)))?;
+            
+            for line in new_lines.into_iter().filter(|line| filter.accept(line)) {
+                if show_headers {
+                    println!("{}: {}", file.path.display(), line);
+                } else {
+                    println!("{}", line);
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -1,4 +1,11 @@
 pub mod args;
+pub mod check;
+pub mod export;
+pub mod logs;
+pub mod output;
+pub mod pattern;
+pub mod status;
+pub mod validate;
 
 // Re-export
-pub use args::{CliArgs, Commands, LogLevel, OutputFormat, DaemonOptions, JobCommands, SocketCommands, ExampleType};
\ No newline at end of file
+pub use args::{CliArgs, Commands, LogFormat, LogLevel, OutputFormat, DaemonOptions, JobCommands, SocketCommands, ExampleType};
\ No newline at end of file
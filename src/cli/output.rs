@@ -0,0 +1,190 @@
+//! Output formats shared by every command that prints status
+//!
+//! JSON and YAML are the serialized forms of the types themselves, so they only change
+//! when the types do:
+//!
+//! - `status`, `job list`, `job status`: an array of `JobStatus` (`label`, `state`, `pid`,
+//!   `restart_count`, `uptime_sec`, `supervised_sec`, `started_after_boot_sec`, `exit_code`,
+//!   `exit_signal`, `config`, `needs_restart`, `source_path`, `rss_bytes`, `cpu_percent`);
+//!   `job status` leaves out `config` unless given `--config`
+//! - `socket status`: an array of `SocketStatus` (`job`, `listen`, `socket_type`,
+//!   `job_state`, `activations`, `pending`)
+//! - `summary`: a `DaemonSummary` object (`total`, `running`, `stopped`, `failed`,
+//!   `backoff`, `other`, `restarts`, `started_at`, `uptime_sec`)
+//! - `validate`: a `ValidationResult` object, or an array of them for a directory
+//! - `events`: one JSON line per event as the event log writes it (`timestamp`, `message`,
+//!   `event`, `data`), for YAML as well
+//! - `job check`: an array of `SpawnCheck` (`label`, `program`, `arguments`, `user`, `uid`,
+//!   `gid`, `groups`, `working_directory`, `environment`, `clear_environment`, `stdout`,
+//!   `stderr`, `problems`)
+//!
+//! Tables color the job state (green running, red failed, yellow backoff) once `init_color`
+//! found stdout to be a terminal and `NO_COLOR` unset.
+
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::Serialize;
+
+use crate::cli::args::OutputFormat;
+use crate::cli::export;
+use crate::cli::status;
+use crate::job::sockets::SocketStatus;
+use crate::job::{DaemonSummary, JobStatus, RestartRecord};
+use crate::util::error::{NusaError, Result};
+
+/// Off until `init_color`, so rendering is the same everywhere else (tests, libraries)
+static COLOR: AtomicBool = AtomicBool::new(false);
+
+const GREEN: &str = "\x1B[32m";
+const RED: &str = "\x1B[31m";
+const YELLOW: &str = "\x1B[33m";
+const RESET: &str = "\x1B[0m";
+
+/// Color tables if stdout is a terminal and `NO_COLOR` is unset or empty (see no-color.org)
+pub fn init_color() {
+    let no_color = std::env::var_os("NO_COLOR").map_or(false, |value| !value.is_empty());
+    set_color(!no_color && std::io::stdout().is_terminal());
+}
+
+/// Turn table colors on or off regardless of the terminal
+pub fn set_color(enabled: bool) {
+    COLOR.store(enabled, Ordering::Relaxed);
+}
+
+/// Rows of cells padded to their column's widest entry
+///
+/// Widths count characters of the text alone, so colored cells line up with plain ones.
+pub struct Table {
+    rows: Vec<Vec<String>>,
+    state_column: Option<usize>,
+}
+
+impl Table {
+    pub fn new(header: &[&str]) -> Self {
+        Self {
+            rows: vec![header.iter().map(|h| h.to_string()).collect()],
+            state_column: None,
+        }
+    }
+    
+    /// Color this column's cells by the job state they show
+    pub fn with_state_column(mut self, column: usize) -> Self {
+        self.state_column = Some(column);
+        self
+    }
+    
+    pub fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+    
+    pub fn render(&self) -> String {
+        let color = COLOR.load(Ordering::Relaxed);
+        let widths: Vec<usize> = (0..self.rows[0].len())
+            .map(|column| self.rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+            .collect();
+        
+        let mut out = String::new();
+        for (index, row) in self.rows.iter().enumerate() {
+            let line: Vec<String> = row.iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(column, (cell, width))| {
+                    let padding = " ".repeat(width - cell.chars().count());
+                    let colored = color && index > 0 && Some(column) == self.state_column;
+                    match if colored { state_color(cell) } else { None } {
+                        Some(code) => format!("{}{}{}{}", code, cell, RESET, padding),
+                        None => format!("{}{}", cell, padding),
+                    }
+                })
+                .collect();
+            out.push_str(line.join("  ").trim_end());
+            out.push('\n');
+        }
+        
+        out
+    }
+}
+
+/// Color for a state as displayed, e.g. `failed (reason)`
+fn state_color(state: &str) -> Option<&'static str> {
+    match state.split(' ').next() {
+        Some("running") => Some(GREEN),
+        Some("failed") => Some(RED),
+        Some("backoff") => Some(YELLOW),
+        _ => None,
+    }
+}
+
+/// Render job statuses; `detailed` adds columns to the table
+pub fn render_jobs(jobs: &[JobStatus], format: &OutputFormat, detailed: bool) -> Result<String> {
+    match format {
+        OutputFormat::Table => Ok(status::render_table(jobs, detailed)),
+        OutputFormat::Plain => Ok(status::render_plain(jobs)),
+        _ => encode(jobs, format),
+    }
+}
+
+/// Render `job status`: the detailed table, followed with `show_config` by each job's
+/// config in the form `export` prints; JSON and YAML carry the config only with `show_config`
+pub fn render_job_status(jobs: &[JobStatus], format: &OutputFormat, show_config: bool) -> Result<String> {
+    if !show_config && matches!(format, OutputFormat::Json | OutputFormat::Yaml) {
+        let mut value = serde_json::to_value(jobs)
+            .map_err(|e| NusaError::System(format!("Failed to encode output: {}", e)))?;
+        if let serde_json::Value::Array(statuses) = &mut value {
+            for status in statuses {
+                if let serde_json::Value::Object(fields) = status {
+                    fields.remove("config");
+                }
+            }
+        }
+        return encode(&value, format);
+    }
+    
+    let mut output = render_jobs(jobs, format, true)?;
+    
+    if show_config && matches!(format, OutputFormat::Table | OutputFormat::Plain) {
+        for job in jobs {
+            let config = export::to_toml(&job.config)?;
+            output.push_str(&format!("\n# {}\n{}\n", job.label, config.trim_end()));
+        }
+    }
+    
+    Ok(output)
+}
+
+/// Render activation sockets; plain output is the table as well
+pub fn render_sockets(sockets: &[SocketStatus], format: &OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Table | OutputFormat::Plain => Ok(status::render_sockets(sockets)),
+        _ => encode(sockets, format),
+    }
+}
+
+/// Render the daemon summary; plain output is the same lines
+pub fn render_summary(summary: &DaemonSummary, format: &OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Table | OutputFormat::Plain => Ok(status::render_summary(summary)),
+        _ => encode(summary, format),
+    }
+}
+
+/// Render a job's restart history; plain output is the table as well
+pub fn render_history(history: &[RestartRecord], format: &OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Table | OutputFormat::Plain => Ok(status::render_history(history)),
+        _ => encode(history, format),
+    }
+}
+
+/// Encode a value as JSON or YAML; text formats fall back to JSON
+pub fn encode<T: Serialize + ?Sized>(value: &T, format: &OutputFormat) -> Result<String> {
+    let encoded = match format {
+        OutputFormat::Yaml => serde_yaml::to_string(value)
+            .map_err(|e| NusaError::System(format!("Failed to encode output: {}", e)))?,
+        _ => serde_json::to_string_pretty(value)
+            .map_err(|e| NusaError::System(format!("Failed to encode output: {}", e)))?,
+    };
+    
+    Ok(format!("{}\n", encoded.trim_end()))
+}
\ No newline at end of file
@@ -0,0 +1,118 @@
+//! Shell-style label patterns (`web-*`, `db?`, `[ab]-worker`) for commands taking several jobs
+
+use crate::util::error::{NusaError, Result};
+
+/// Whether `arg` has an unescaped `*`, `?` or `[`
+pub fn is_pattern(arg: &str) -> bool {
+    let mut chars = arg.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '*' | '?' | '[' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Drop the backslashes of escaped characters: `web\*` names the job `web*`
+pub fn unescape(arg: &str) -> String {
+    let mut out = String::with_capacity(arg.len());
+    let mut chars = arg.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.extend(chars.next()),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Match `text` against a pattern: `*` is any run, `?` one character, `[a-z]` / `[!a-z]` a
+/// character class, and `\` makes the next character literal
+pub fn matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    match_from(&pattern, &text)
+}
+
+fn match_from(pattern: &[char], text: &[char]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some('*') => (0..=text.len()).any(|skip| match_from(&pattern[1..], &text[skip..])),
+        Some('?') => !text.is_empty() && match_from(&pattern[1..], &text[1..]),
+        Some('[') => match (parse_class(&pattern[1..]), text.first()) {
+            (Some((matched, len)), Some(c)) => matched(*c) && match_from(&pattern[1 + len..], &text[1..]),
+            // An unclosed `[` is literal
+            (None, Some('[')) => match_from(&pattern[1..], &text[1..]),
+            _ => false,
+        },
+        Some('\\') if pattern.len() > 1 => {
+            text.first() == Some(&pattern[1]) && match_from(&pattern[2..], &text[1..])
+        }
+        Some(c) => text.first() == Some(c) && match_from(&pattern[1..], &text[1..]),
+    }
+}
+
+/// Parse a class after its `[`: a matcher and the pattern length it used, including the `]`
+fn parse_class(pattern: &[char]) -> Option<(impl Fn(char) -> bool, usize)> {
+    let negated = matches!(pattern.first(), Some('!') | Some('^'));
+    let start = usize::from(negated);
+    
+    // A `]` right after the opening bracket is part of the class
+    let close = pattern.iter()
+        .skip(start + 1)
+        .position(|c| *c == ']')
+        .map(|index| index + start + 1)?;
+    
+    let mut ranges = Vec::new();
+    let body = &pattern[start..close];
+    let mut index = 0;
+    while index < body.len() {
+        if index + 2 < body.len() && body[index + 1] == '-' {
+            ranges.push((body[index], body[index + 2]));
+            index += 3;
+        } else {
+            ranges.push((body[index], body[index]));
+            index += 1;
+        }
+    }
+    
+    let matched = move |c: char| ranges.iter().any(|(low, high)| (*low..=*high).contains(&c)) != negated;
+    Some((matched, close + 1))
+}
+
+/// Replace patterns among `args` with the `labels` they match, keeping order and dropping repeats
+///
+/// Plain arguments are passed through (unescaped) even if no such job exists, so the daemon
+/// reports them; a pattern that matches nothing is an error.
+pub fn expand_labels(args: &[String], labels: &[String]) -> Result<Vec<String>> {
+    let mut sorted: Vec<&String> = labels.iter().collect();
+    sorted.sort();
+    
+    let mut expanded: Vec<String> = Vec::new();
+    for arg in args {
+        let matched: Vec<String> = if is_pattern(arg) {
+            let matched: Vec<String> = sorted.iter()
+                .filter(|label| matches(arg, label))
+                .map(|label| label.to_string())
+                .collect();
+            if matched.is_empty() {
+                return Err(NusaError::System(format!("No job matches '{}'", arg)));
+            }
+            matched
+        } else {
+            vec![unescape(arg)]
+        };
+        
+        for label in matched {
+            if !expanded.contains(&label) {
+                expanded.push(label);
+            }
+        }
+    }
+    
+    Ok(expanded)
+}
\ No newline at end of file
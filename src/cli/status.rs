@@ -0,0 +1,204 @@
+//! Text rendering of job and socket status; see `cli::output` for format selection and tables
+
+use std::time::Duration;
+
+use crate::cli::output::Table;
+use crate::job::sockets::SocketStatus;
+use crate::job::{DaemonSummary, JobState, JobStatus, RestartRecord};
+use crate::util::error::{describe_signal, NusaError, Result};
+
+/// Which jobs `job list` shows
+#[derive(Debug, Default)]
+pub struct JobFilter {
+    /// Only jobs in this state, named as in the status output (`running`, `failed`, ...)
+    pub state: Option<String>,
+    
+    /// Any of these flags admits a job; none set admits all
+    pub loaded: bool,
+    pub running: bool,
+    pub failed: bool,
+    
+    /// Only jobs whose current process started within this many seconds of system boot
+    pub since_boot: Option<u64>,
+}
+
+impl JobFilter {
+    /// Fail on a `state` no job can be in, rather than silently matching nothing
+    pub fn validate(&self) -> Result<()> {
+        match &self.state {
+            Some(state) if !JobState::NAMES.contains(&state.as_str()) => Err(NusaError::System(format!(
+                "Unknown state '{}' (expected one of: {})",
+                state,
+                JobState::NAMES.join(", ")
+            ))),
+            _ => Ok(()),
+        }
+    }
+    
+    pub fn matches(&self, job: &JobStatus) -> bool {
+        // `failed (reason)` is in state `failed`
+        let state_matches = self.state.as_deref().map_or(true, |state| {
+            let shown = job.state.to_string();
+            shown == state || shown.starts_with(&format!("{} (", state))
+        });
+        
+        // Every job the daemon reports is loaded
+        let any_flag = self.loaded || self.running || self.failed;
+        let flag_matches = !any_flag
+            || self.loaded
+            || (self.running && job.state == JobState::Running)
+            || (self.failed && matches!(job.state, JobState::Failed(_)));
+        
+        let boot_matches = self.since_boot.map_or(true, |limit| {
+            job.started_after_boot_sec.map_or(false, |started| started <= limit)
+        });
+        
+        state_matches && flag_matches && boot_matches
+    }
+    
+    /// The matching jobs, sorted by label
+    pub fn apply(&self, mut jobs: Vec<JobStatus>) -> Result<Vec<JobStatus>> {
+        self.validate()?;
+        jobs.retain(|job| self.matches(job));
+        jobs.sort_by(|a, b| a.label.cmp(&b.label));
+        Ok(jobs)
+    }
+}
+
+/// Aligned columns: label, state, pid, uptime, restarts (plus time supervised, CPU, memory, last exit,
+/// program and source file when detailed)
+pub fn render_table(jobs: &[JobStatus], detailed: bool) -> String {
+    let mut header = vec!["LABEL", "STATE", "PID", "UPTIME", "RESTARTS"];
+    if detailed {
+        header.extend(["SUPERVISED", "CPU", "MEM", "LAST EXIT", "PROGRAM", "SOURCE"]);
+    }
+    
+    let mut table = Table::new(&header).with_state_column(1);
+    
+    for job in jobs {
+        let mut row = vec![
+            job.label.clone(),
+            job.state.to_string(),
+            job.pid.map_or_else(|| "-".to_string(), |pid| pid.to_string()),
+            job.uptime_sec.map_or_else(|| "-".to_string(), |secs| format_uptime(Duration::from_secs(secs))),
+            job.restart_count.to_string(),
+        ];
+        
+        if detailed {
+            row.push(job.supervised_sec.map_or_else(|| "-".to_string(), |secs| format_uptime(Duration::from_secs(secs))));
+            row.push(job.cpu_percent.map_or_else(|| "-".to_string(), |percent| format!("{:.1}%", percent)));
+            row.push(job.rss_bytes.map_or_else(|| "-".to_string(), format_bytes));
+            row.push(format_exit(job));
+            row.push(job.config.program.path.display().to_string());
+            row.push(job.source_path.as_ref().map_or_else(|| "-".to_string(), |path| path.display().to_string()));
+        }
+        
+        table.push(row);
+    }
+    
+    table.render()
+}
+
+/// Aligned columns for `socket status`: job, type, address, job state, activations, pending connections
+pub fn render_sockets(sockets: &[SocketStatus]) -> String {
+    let mut table = Table::new(&["JOB", "TYPE", "LISTEN", "STATE", "ACTIVATIONS", "PENDING"])
+        .with_state_column(3);
+    
+    for socket in sockets {
+        table.push(vec![
+            socket.job.clone(),
+            socket.socket_type.to_string(),
+            socket.listen.clone(),
+            socket.job_state.to_string(),
+            socket.activations.to_string(),
+            socket.pending.map_or_else(|| "-".to_string(), |pending| pending.to_string()),
+        ]);
+    }
+    
+    table.render()
+}
+
+/// Restarts, oldest first, with the exit that caused each
+pub fn render_history(history: &[RestartRecord]) -> String {
+    if history.is_empty() {
+        return "No restarts\n".to_string();
+    }
+    
+    let mut table = Table::new(&["TIME", "EXIT"]);
+    for record in history {
+        let at = chrono::DateTime::<chrono::Local>::from(record.at);
+        let exit = match (record.exit_code, record.signal) {
+            (_, Some(signal)) => describe_signal(signal),
+            (Some(code), None) => format!("code {}", code),
+            (None, None) => "spawn failed".to_string(),
+        };
+        table.push(vec![at.format("%Y-%m-%d %H:%M:%S").to_string(), exit]);
+    }
+    
+    table.render()
+}
+
+/// Job counts, restarts and uptime, one per line
+pub fn render_summary(summary: &DaemonSummary) -> String {
+    let started = chrono::DateTime::<chrono::Local>::from(summary.started_at);
+    format!(
+        "Jobs:      {} ({} running, {} stopped, {} failed, {} backoff, {} other)\n\
+         Restarts:  {}\n\
+         Uptime:    {} (since {})\n",
+        summary.total,
+        summary.running,
+        summary.stopped,
+        summary.failed,
+        summary.backoff,
+        summary.other,
+        summary.restarts,
+        format_uptime(Duration::from_secs(summary.uptime_sec)),
+        started.format("%Y-%m-%d %H:%M:%S"),
+    )
+}
+
+/// One job per line: `label state pid`
+pub fn render_plain(jobs: &[JobStatus]) -> String {
+    jobs.iter()
+        .map(|job| {
+            let pid = job.pid.map_or_else(|| "-".to_string(), |pid| pid.to_string());
+            format!("{} {} {}\n", job.label, job.state, pid)
+        })
+        .collect()
+}
+
+/// Compact uptime, e.g. `45s`, `12m30s`, `3h05m`, `2d04h`
+pub fn format_uptime(uptime: Duration) -> String {
+    let secs = uptime.as_secs();
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        3600..=86399 => format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60),
+        _ => format!("{}d{:02}h", secs / 86400, (secs % 86400) / 3600),
+    }
+}
+
+/// Compact byte count in binary units, e.g. `512B`, `4.0K`, `12.3M`, `1.5G`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["K", "M", "G", "T"];
+    
+    if bytes < 1024 {
+        return format!("{}B", bytes);
+    }
+    
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1}{}", value, UNITS[unit])
+}
+
+fn format_exit(job: &JobStatus) -> String {
+    match (job.exit_code, job.exit_signal) {
+        (_, Some(signal)) => describe_signal(signal),
+        (Some(code), None) => format!("code {}", code),
+        (None, None) => "-".to_string(),
+    }
+}
\ No newline at end of file
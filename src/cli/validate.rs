@@ -0,0 +1,211 @@
+//! Results and rendering for the `validate` command
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::cli::args::OutputFormat;
+use crate::cli::output::encode;
+use crate::job::config::{is_config_file, JobConfig, JobConfigFile};
+use crate::job::dependency::{cycle_error, find_cycle};
+use crate::job::validator::ConfigValidator;
+use crate::util::error::{NusaError, Result};
+
+/// Outcome of validating one config file, or one job in a file that defines several
+#[derive(Debug, Serialize)]
+pub struct ValidationResult {
+    pub path: PathBuf,
+    pub label: Option<String>,
+    pub valid: bool,
+    pub error: Option<String>,
+    
+    #[serde(skip)]
+    pub config: Option<JobConfig>,
+}
+
+impl ValidationResult {
+    /// Parse and validate a single config file
+    ///
+    /// `strict` rejects unset environment variables and programs that are missing or not executable.
+    pub async fn check(path: &Path, strict: bool) -> Self {
+        let loaded = if strict {
+            match JobConfig::from_file_strict(path).await {
+                Ok(config) => ConfigValidator::validate(&config, true).await.map(|()| config),
+                Err(e) => Err(e),
+            }
+        } else {
+            JobConfig::from_file(path).await
+        };
+        
+        match loaded {
+            Ok(config) => Self::valid(path, config),
+            Err(e) => Self::invalid(path, None, e),
+        }
+    }
+    
+    /// Parse and validate a config file that may define several jobs, one result per job
+    ///
+    /// A file that fails to parse gives a single result without a label.
+    pub async fn check_file(path: &Path, strict: bool) -> Vec<Self> {
+        let loaded = if strict {
+            JobConfigFile::from_file_strict(path).await
+        } else {
+            JobConfigFile::from_file(path).await
+        };
+        let configs = match loaded {
+            Ok(file) => file.into_jobs(),
+            Err(e) => return vec![Self::invalid(path, None, e)],
+        };
+        
+        let mut results = Vec::with_capacity(configs.len());
+        for config in configs {
+            if strict {
+                if let Err(e) = ConfigValidator::validate(&config, true).await {
+                    results.push(Self::invalid(path, Some(config.label), e));
+                    continue;
+                }
+            }
+            results.push(Self::valid(path, config));
+        }
+        results
+    }
+    
+    fn valid(path: &Path, config: JobConfig) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            label: Some(config.label.clone()),
+            valid: true,
+            error: None,
+            config: Some(config),
+        }
+    }
+    
+    fn invalid(path: &Path, label: Option<String>, error: NusaError) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            label,
+            valid: false,
+            error: Some(error.to_string()),
+            config: None,
+        }
+    }
+}
+
+/// Validate every config file in a directory, sorted by path
+///
+/// Files that are valid on their own but declare the same label as another
+/// file, or whose jobs depend on each other in a cycle, are marked invalid, since
+/// the daemon would refuse to load them.
+pub async fn check_dir(dir: &Path, strict: bool) -> Result<Vec<ValidationResult>> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map_err(|e| NusaError::System(format!("Failed to read directory {}: {}", dir.display(), e)))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| is_config_file(path))
+        .collect();
+    paths.sort();
+    
+    let mut results = Vec::with_capacity(paths.len());
+    for path in paths {
+        results.extend(ValidationResult::check_file(&path, strict).await);
+    }
+    
+    mark_duplicate_labels(&mut results);
+    mark_dependency_cycles(&mut results);
+    Ok(results)
+}
+
+/// Fail every job on a dependency cycle, naming the cycle; checked until no valid job is on one
+fn mark_dependency_cycles(results: &mut [ValidationResult]) {
+    loop {
+        let configs = results.iter().filter(|result| result.valid).filter_map(|result| result.config.as_ref());
+        let Some(cycle) = find_cycle(configs) else {
+            return;
+        };
+        
+        let error = cycle_error(&cycle).to_string();
+        for result in results.iter_mut() {
+            if result.valid && result.label.as_ref().map_or(false, |label| cycle.contains(label)) {
+                result.valid = false;
+                result.error = Some(error.clone());
+            }
+        }
+    }
+}
+
+/// Fail every result whose label another result also declares, naming the other files
+fn mark_duplicate_labels(results: &mut [ValidationResult]) {
+    let mut paths_by_label: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for result in results.iter() {
+        if let Some(label) = &result.label {
+            paths_by_label.entry(label.clone()).or_default().push(result.path.clone());
+        }
+    }
+    
+    for result in results.iter_mut() {
+        let Some(paths) = result.label.as_ref().and_then(|label| paths_by_label.get(label)) else {
+            continue;
+        };
+        if paths.len() < 2 {
+            continue;
+        }
+        
+        let others: Vec<String> = paths.iter()
+            .filter(|path| **path != result.path)
+            .map(|path| path.display().to_string())
+            .collect();
+        result.valid = false;
+        result.error = Some(format!(
+            "Duplicate label '{}' (also declared in {})",
+            result.label.as_deref().unwrap_or_default(),
+            others.join(", ")
+        ));
+    }
+}
+
+/// Render directory results: one line per file plus a summary, or a JSON/YAML array
+pub fn render_dir(results: &[ValidationResult], format: &OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Table | OutputFormat::Plain => {
+            let mut out = String::new();
+            for result in results {
+                match (&result.label, &result.error) {
+                    (Some(label), None) => out.push_str(&format!("✓ {}: {}\n", result.path.display(), label)),
+                    (_, error) => out.push_str(&format!(
+                        "✗ {}: {}\n",
+                        result.path.display(),
+                        error.as_deref().unwrap_or("invalid")
+                    )),
+                }
+            }
+            
+            let valid = results.iter().filter(|result| result.valid).count();
+            out.push_str(&format!(
+                "\nValidation complete: {} valid, {} invalid\n",
+                valid,
+                results.len() - valid
+            ));
+            Ok(out)
+        }
+        _ => encode(results, format),
+    }
+}
+
+/// Render a single file's result: a short summary, or a JSON/YAML object
+pub fn render_file(result: &ValidationResult, format: &OutputFormat) -> Result<String> {
+    match format {
+        OutputFormat::Table | OutputFormat::Plain => Ok(match (&result.config, &result.error) {
+            (Some(config), _) => format!(
+                "✓ Configuration is valid\n  Label: {}\n  Program: {}\n  Supervision: keep_alive={}\n",
+                config.label,
+                config.program.path.display(),
+                config.supervision.keep_alive
+            ),
+            (None, error) => format!(
+                "✗ Configuration is invalid: {}\n",
+                error.as_deref().unwrap_or("unknown error")
+            ),
+        }),
+        _ => encode(result, format),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,148 @@
+use serde::de::DeserializeOwned;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::UnixStream;
+use tokio::time::{self, Instant};
+
+use crate::control::protocol::{ControlRequest, ControlResponse};
+use crate::util::error::{NusaError, Result};
+
+/// Pause between connection attempts while the daemon is unreachable
+const CONNECT_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Connection to a running daemon's control socket
+pub struct ControlClient {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+    /// How long a request and its response may take (None = no limit)
+    timeout: Option<Duration>,
+}
+
+impl ControlClient {
+    /// Connect to the control socket at `path`, retrying for up to `timeout`
+    ///
+    /// A daemon that is starting up may not have bound its socket yet, so a missing or
+    /// refusing socket is retried until the timeout; the error then says which it was.
+    pub async fn connect(path: &Path, timeout: Duration) -> Result<Self> {
+        let deadline = Instant::now() + timeout;
+        
+        let stream = loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let attempt = time::timeout(remaining.max(CONNECT_RETRY_INTERVAL), UnixStream::connect(path)).await;
+            
+            let error = match attempt {
+                Ok(Ok(stream)) => break stream,
+                Ok(Err(e)) => e,
+                Err(_) => {
+                    return Err(NusaError::System(format!(
+                        "Timed out after {}s connecting to daemon at {}",
+                        timeout.as_secs(),
+                        path.display()
+                    )));
+                }
+            };
+            
+            let retry = matches!(error.kind(), ErrorKind::NotFound | ErrorKind::ConnectionRefused);
+            if !retry || Instant::now() + CONNECT_RETRY_INTERVAL > deadline {
+                return Err(connect_error(path, &error));
+            }
+            
+            time::sleep(CONNECT_RETRY_INTERVAL).await;
+        };
+        
+        let (reader, writer) = stream.into_split();
+        
+        Ok(Self {
+            lines: BufReader::new(reader).lines(),
+            writer,
+            timeout: None,
+        })
+    }
+    
+    /// Limit how long each request may take, from sending it to reading the response
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+    
+    /// Send a request and wait for the raw response
+    pub async fn request(&mut self, request: &ControlRequest) -> Result<ControlResponse> {
+        let mut line = serde_json::to_string(request)
+            .map_err(|e| NusaError::System(format!("Failed to encode request: {}", e)))?;
+        line.push('\n');
+        
+        let exchange = async {
+            self.writer.write_all(line.as_bytes()).await?;
+            Ok::<_, NusaError>(self.lines.next_line().await?)
+        };
+        let response = match self.timeout {
+            Some(timeout) => time::timeout(timeout, exchange).await.map_err(|_| {
+                NusaError::System(format!("Timed out after {}s waiting for the daemon to respond", timeout.as_secs()))
+            })??,
+            None => exchange.await?,
+        };
+        let response = response
+            .ok_or_else(|| NusaError::System("Daemon closed the control connection".into()))?;
+        
+        serde_json::from_str(&response)
+            .map_err(|e| NusaError::System(format!("Invalid response from daemon: {}", e)))
+    }
+    
+    /// Send a request and decode the data of a successful response
+    pub async fn call<T: DeserializeOwned>(&mut self, request: &ControlRequest) -> Result<T> {
+        let response = self.request(request).await?;
+        
+        if !response.ok {
+            return Err(NusaError::Control(
+                response.error.unwrap_or_else(|| "unknown error".to_string())
+            ));
+        }
+        
+        let data = response.data.unwrap_or(serde_json::Value::Null);
+        serde_json::from_value(data)
+            .map_err(|e| NusaError::System(format!("Unexpected response from daemon: {}", e)))
+    }
+    
+    /// Ask the daemon to stream job events; read them with `next_event`
+    ///
+    /// The connection serves no other requests afterwards.
+    pub async fn subscribe_events(&mut self) -> Result<()> {
+        self.call::<serde_json::Value>(&ControlRequest::Events).await?;
+        Ok(())
+    }
+    
+    /// The next streamed event, as the event log records it (`timestamp`, `message`,
+    /// `event`, `data`); `None` once the daemon closes the connection
+    pub async fn next_event(&mut self) -> Result<Option<serde_json::Value>> {
+        match self.lines.next_line().await? {
+            Some(line) => serde_json::from_str(&line)
+                .map(Some)
+                .map_err(|e| NusaError::System(format!("Invalid event from daemon: {}", e))),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Explain a failed connection in terms of what the user should fix
+fn connect_error(path: &Path, error: &std::io::Error) -> NusaError {
+    let reason = match error.kind() {
+        ErrorKind::NotFound => format!(
+            "control socket {} does not exist; is the daemon running?",
+            path.display()
+        ),
+        ErrorKind::ConnectionRefused => format!(
+            "control socket {} refuses connections; it is stale (left by a daemon that exited) \
+             or the daemon is still starting",
+            path.display()
+        ),
+        ErrorKind::PermissionDenied => format!(
+            "permission denied on control socket {}",
+            path.display()
+        ),
+        _ => format!("failed to connect to daemon at {}: {}", path.display(), error),
+    };
+    
+    NusaError::System(format!("Cannot reach daemon: {}", reason))
+}
\ No newline at end of file
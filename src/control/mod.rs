@@ -0,0 +1,13 @@
+//! Control socket for managing a running NusaLaunchd daemon
+
+pub mod client;
+pub mod protocol;
+pub mod server;
+
+// Re-export
+pub use client::ControlClient;
+pub use protocol::{ControlRequest, ControlResponse};
+pub use server::ControlServer;
+
+/// File name of the control socket inside the runtime directory
+pub const SOCKET_NAME: &str = "control.sock";
\ No newline at end of file
@@ -0,0 +1,195 @@
+//! Wire format of the control socket
+//!
+//! Each request and response is a single line of JSON, e.g.
+//! `{"cmd":"start","label":"web"}` answered by `{"ok":true,"data":{...}}`.
+//! Failures carry a stable `code` next to the message, e.g.
+//! `{"ok":false,"error":"Job 'web' not found","code":"job_not_found"}`.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use crate::util::error::NusaError;
+
+/// `code` of a request line that is not valid JSON or names no known command
+pub const INVALID_REQUEST: &str = "invalid_request";
+
+/// A command sent to the daemon
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "cmd", rename_all = "kebab-case")]
+pub enum ControlRequest {
+    /// Status of every loaded job
+    List,
+    
+    /// Status of one job, or of every job when `label` is omitted
+    Status {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+    },
+    
+    /// Start a job
+    Start { label: String },
+    
+    /// Stop a job, sending SIGKILL if it has not exited after `timeout_sec`
+    Stop {
+        label: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timeout_sec: Option<u64>,
+    },
+    
+    /// Start every loaded job that is not scheduled
+    StartAll,
+    
+    /// Stop every running job
+    StopAll,
+    
+    /// Status of every open activation socket
+    Sockets,
+    
+    /// Job counts by state, total restarts and daemon uptime, as a `DaemonSummary`
+    Summary,
+    
+    /// Stop a job immediately with SIGKILL
+    Kill { label: String },
+    
+    /// Send a signal, by name or number, to a running job without changing its state
+    Signal { label: String, signal: String },
+    
+    /// Stop and start a job
+    Restart { label: String },
+    
+    /// Re-read a job's config file, restarting it if `restart` is set and the change needs it;
+    /// without `label`, rescan the whole config directory (restarting jobs as needed) and
+    /// answer with a `RescanSummary`
+    Reload {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        #[serde(default)]
+        restart: bool,
+    },
+    
+    /// Preview what rescanning the daemon's config directory would change
+    ///
+    /// `path`, if given, must name that directory; the daemon reads no other.
+    Diff {
+        #[serde(default)]
+        path: Option<PathBuf>,
+    },
+    
+    /// Re-read the daemon config file
+    ReloadConfig,
+    
+    /// Stop a job and remove it from the daemon, unless other jobs depend on it
+    Unload { label: String },
+    
+    /// Check that a job could be spawned, without starting it
+    Check { label: String },
+    
+    /// Recent restarts of a job, oldest first, as `RestartRecord`s
+    History { label: String },
+    
+    /// Stream job events: answered like any request, then followed by one event per line
+    /// in the event log's form until the client hangs up
+    Events,
+}
+
+/// The daemon's answer to a single request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlResponse {
+    pub ok: bool,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    
+    /// Machine-readable kind of `error`, see `NusaError::code`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+impl ControlResponse {
+    /// Successful response carrying `data`
+    pub fn success<T: Serialize>(data: T) -> Self {
+        match serde_json::to_value(data) {
+            Ok(data) => Self {
+                ok: true,
+                data: Some(data),
+                error: None,
+                code: None,
+            },
+            Err(e) => Self::failure(NusaError::System(format!("Failed to encode response: {}", e))),
+        }
+    }
+    
+    /// Failed response with the error's message and code
+    pub fn failure(error: NusaError) -> Self {
+        Self::failure_with_code(error.code(), error)
+    }
+    
+    /// Failed response for an error that is no `NusaError`
+    pub fn failure_with_code(code: &str, error: impl Display) -> Self {
+        Self {
+            ok: false,
+            data: None,
+            error: Some(error.to_string()),
+            code: Some(code.to_string()),
+        }
+    }
+}
+
+/// Data returned by `start`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartResult {
+    pub label: String,
+    
+    /// PID of the job's process, if one is running
+    pub pid: Option<u32>,
+    
+    /// When that process started
+    #[serde(default)]
+    pub start_time: Option<SystemTime>,
+}
+
+/// Data returned by `signal`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalResult {
+    pub label: String,
+    
+    /// PID the signal was delivered to
+    pub pid: u32,
+}
+
+/// One job's outcome in the data returned by `start-all` and `stop-all`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JobOutcome {
+    pub label: String,
+    pub ok: bool,
+    
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl JobOutcome {
+    /// Convert the per-job results of `start_all` / `stop_all`
+    pub fn from_results(results: Vec<(String, crate::util::error::Result<()>)>) -> Vec<Self> {
+        results.into_iter()
+            .map(|(label, result)| Self {
+                label,
+                ok: result.is_ok(),
+                error: result.err().map(|e| e.to_string()),
+            })
+            .collect()
+    }
+}
+
+/// Data returned by `diff`
+#[derive(Debug, Serialize)]
+pub struct DiffResult {
+    pub plan: crate::job::reconcile::ReconcilePlan,
+    
+    /// The plan rendered for humans
+    pub rendered: String,
+}
\ No newline at end of file
@@ -0,0 +1,348 @@
+use std::io::ErrorKind;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::io::Lines;
+use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, info, warn};
+
+use crate::control::protocol::{ControlRequest, ControlResponse, INVALID_REQUEST, DiffResult, JobOutcome, SignalResult, StartResult};
+use crate::daemon::dirs::CONTROL_SOCKET_MODE;
+use crate::daemon::DaemonConfigHandle;
+use crate::event::dispatcher::event_record;
+use crate::job::config::{JobConfig, JobConfigFile};
+use crate::job::manager::config_files;
+use crate::job::JobManager;
+use crate::process::credentials::resolve_group;
+use crate::util::error::{ConfigError, NusaError, Result};
+
+/// Unix socket server answering control requests against a `JobManager`
+pub struct ControlServer {
+    path: PathBuf,
+    listener: UnixListener,
+    job_manager: JobManager,
+    daemon_config: Option<DaemonConfigHandle>,
+}
+
+impl ControlServer {
+    /// Bind the control socket (mode 0660), replacing a stale socket file left by a previous run
+    pub fn bind(
+        path: PathBuf,
+        job_manager: JobManager,
+        daemon_config: Option<DaemonConfigHandle>,
+    ) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        
+        remove_stale_socket(&path)?;
+        
+        let listener = UnixListener::bind(&path).map_err(|e| {
+            NusaError::System(format!("Failed to bind control socket {}: {}", path.display(), e))
+        })?;
+        
+        // Owner and group only; see `daemon::dirs`
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(CONTROL_SOCKET_MODE))?;
+        
+        info!("Control socket listening on {}", path.display());
+        
+        Ok(Self {
+            path,
+            listener,
+            job_manager,
+            daemon_config,
+        })
+    }
+    
+    /// Hand the socket to `group` (name or numeric GID), so its members can use `nusaload`
+    pub fn set_group(&self, group: &str) -> Result<()> {
+        let gid = resolve_group(group)?;
+        std::os::unix::fs::chown(&self.path, None, Some(gid.as_raw())).map_err(|e| {
+            NusaError::System(format!(
+                "Failed to give control socket {} to group '{}': {}",
+                self.path.display(), group, e
+            ))
+        })
+    }
+    
+    /// Accept connections until the task is dropped
+    pub async fn run(self) {
+        loop {
+            match self.listener.accept().await {
+                Ok((stream, _)) => {
+                    let job_manager = self.job_manager.clone();
+                    let daemon_config = self.daemon_config.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(stream, job_manager, daemon_config).await {
+                            debug!("Control connection closed with error: {}", e);
+                        }
+                    });
+                }
+                Err(e) => {
+                    warn!("Failed to accept control connection: {}", e);
+                }
+            }
+        }
+    }
+}
+
+impl Drop for ControlServer {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Unlink a leftover socket file, refusing if another daemon is still listening on it
+fn remove_stale_socket(path: &Path) -> Result<()> {
+    if !path.exists() {
+        return Ok(());
+    }
+    
+    if std::os::unix::net::UnixStream::connect(path).is_ok() {
+        return Err(NusaError::System(format!(
+            "Control socket {} is in use by another daemon",
+            path.display()
+        )));
+    }
+    
+    warn!("Removing stale control socket {}", path.display());
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Serve newline-delimited requests on one connection until the client hangs up
+async fn handle_connection(
+    stream: UnixStream,
+    job_manager: JobManager,
+    daemon_config: Option<DaemonConfigHandle>,
+) -> Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        
+        let response = match serde_json::from_str::<ControlRequest>(&line) {
+            Ok(ControlRequest::Events) => return stream_events(lines, writer, &job_manager).await,
+            Ok(request) => {
+                debug!("Control request: {:?}", request);
+                dispatch(request, &job_manager, daemon_config.as_ref()).await
+            }
+            Err(e) => ControlResponse::failure_with_code(INVALID_REQUEST, format!("Invalid request: {}", e)),
+        };
+        
+        write_response(&mut writer, &response).await?;
+    }
+    
+    Ok(())
+}
+
+async fn write_response(writer: &mut OwnedWriteHalf, response: &ControlResponse) -> Result<()> {
+    let mut encoded = serde_json::to_string(response)
+        .map_err(|e| NusaError::System(format!("Failed to encode response: {}", e)))?;
+    encoded.push('\n');
+    
+    writer.write_all(encoded.as_bytes()).await?;
+    Ok(())
+}
+
+/// Answer `events`, then write every job event until the client hangs up
+///
+/// A viewer that disconnects, or cannot keep up and misses events, never affects the
+/// daemon; lines the client sends meanwhile are ignored.
+async fn stream_events(
+    mut lines: Lines<BufReader<OwnedReadHalf>>,
+    mut writer: OwnedWriteHalf,
+    job_manager: &JobManager,
+) -> Result<()> {
+    let mut events = job_manager.subscribe();
+    write_response(&mut writer, &ControlResponse::success(())).await?;
+    debug!("Streaming events to a control client");
+    
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => {
+                    let mut line = event_record(&event)
+                        .map_err(|e| NusaError::System(format!("Failed to encode event: {}", e)))?;
+                    line.push('\n');
+                    if let Err(e) = writer.write_all(line.as_bytes()).await {
+                        debug!("Event viewer disconnected: {}", e);
+                        return Ok(());
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Event viewer fell behind; {} events were not sent", skipped);
+                }
+                Err(RecvError::Closed) => return Ok(()),
+            },
+            line = lines.next_line() => {
+                if !matches!(line, Ok(Some(_))) {
+                    debug!("Event viewer disconnected");
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// Run a single request and build its response
+async fn dispatch(
+    request: ControlRequest,
+    job_manager: &JobManager,
+    daemon_config: Option<&DaemonConfigHandle>,
+) -> ControlResponse {
+    match request {
+        ControlRequest::List => ControlResponse::success(job_manager.list_jobs().await),
+        
+        ControlRequest::Status { label: None } => {
+            let mut jobs = job_manager.list_jobs().await;
+            jobs.sort_by(|a, b| a.label.cmp(&b.label));
+            ControlResponse::success(jobs)
+        }
+        
+        ControlRequest::Status { label: Some(label) } => match job_manager.get_job_status(&label).await {
+            Some(status) => ControlResponse::success(vec![status]),
+            None => ControlResponse::failure(NusaError::JobNotFound(label)),
+        },
+        
+        ControlRequest::Start { label } => match job_manager.start_job_detailed(&label).await {
+            Ok(started) => ControlResponse::success(StartResult {
+                label,
+                pid: started.map(|started| started.pid),
+                start_time: started.map(|started| started.started_at),
+            }),
+            Err(e) => ControlResponse::failure(e),
+        },
+        
+        ControlRequest::Stop { label, timeout_sec } => {
+            let result = match timeout_sec {
+                Some(secs) => job_manager.stop_job_with_timeout(&label, Duration::from_secs(secs)).await,
+                None => job_manager.stop_job(&label).await,
+            };
+            match result {
+                Ok(()) => ControlResponse::success(job_manager.get_job_status(&label).await),
+                Err(e) => ControlResponse::failure(e),
+            }
+        }
+        
+        ControlRequest::StartAll => ControlResponse::success(JobOutcome::from_results(job_manager.start_all().await)),
+        
+        ControlRequest::StopAll => ControlResponse::success(JobOutcome::from_results(job_manager.stop_all().await)),
+        
+        ControlRequest::Sockets => ControlResponse::success(job_manager.list_sockets().await),
+        
+        ControlRequest::Summary => ControlResponse::success(job_manager.summary().await),
+        
+        ControlRequest::Kill { label } => match job_manager.kill_job(&label).await {
+            Ok(()) => ControlResponse::success(job_manager.get_job_status(&label).await),
+            Err(e) => ControlResponse::failure(e),
+        },
+        
+        ControlRequest::Signal { label, signal } => match job_manager.send_signal(&label, &signal).await {
+            Ok(pid) => ControlResponse::success(SignalResult { label, pid }),
+            Err(e) => ControlResponse::failure(e),
+        },
+        
+        ControlRequest::Restart { label } => match job_manager.restart_job(&label).await {
+            Ok(()) => ControlResponse::success(job_manager.get_job_status(&label).await),
+            Err(e) => ControlResponse::failure(e),
+        },
+        
+        ControlRequest::Unload { label } => match job_manager.remove_job(&label).await {
+            Ok(()) => ControlResponse::success(()),
+            Err(e) => ControlResponse::failure(e),
+        },
+        
+        ControlRequest::Check { label } => match job_manager.check_job(&label).await {
+            Ok(report) => ControlResponse::success(report),
+            Err(e) => ControlResponse::failure(e),
+        },
+        
+        ControlRequest::History { label } => match job_manager.get_job_history(&label).await {
+            Some(history) => ControlResponse::success(history),
+            None => ControlResponse::failure(NusaError::JobNotFound(label)),
+        },
+        
+        ControlRequest::Reload { label: Some(label), restart } => match job_manager.reload_job(&label, restart).await {
+            Ok(outcome) => ControlResponse::success(outcome),
+            Err(e) => ControlResponse::failure(e),
+        },
+        
+        ControlRequest::Reload { label: None, .. } => match job_manager.reload_all().await {
+            Ok(summary) => ControlResponse::success(summary),
+            Err(e) => ControlResponse::failure(e),
+        },
+        
+        ControlRequest::Diff { path } => match diff_config_dir(job_manager, path).await {
+            Ok(diff) => ControlResponse::success(diff),
+            Err(e) => ControlResponse::failure(e),
+        },
+        
+        // Served by `stream_events`, which takes over the connection
+        ControlRequest::Events => ControlResponse::failure_with_code(
+            INVALID_REQUEST,
+            "events cannot be dispatched as a single request",
+        ),
+        
+        ControlRequest::ReloadConfig => match daemon_config {
+            Some(handle) => match handle.reload().await {
+                Ok(report) => ControlResponse::success(report),
+                Err(e) => ControlResponse::failure(e),
+            },
+            None => ControlResponse::failure(NusaError::System(
+                "Daemon was started without --daemon-config".into()
+            )),
+        },
+    }
+}
+
+/// Plan a rescan of the daemon's config directory
+///
+/// Any other directory is refused: the daemon runs as root, and its parse errors would
+/// show clients the contents of files they may not be able to read.
+async fn diff_config_dir(job_manager: &JobManager, path: Option<PathBuf>) -> Result<DiffResult> {
+    let config_dir = job_manager.config_dir().ok_or_else(|| {
+        NusaError::System("The daemon has no config directory to compare against".into())
+    })?;
+    
+    // Compared without resolving the client's path, so it learns nothing about other files
+    if let Some(path) = path {
+        if path != config_dir && config_dir.canonicalize().ok() != Some(path) {
+            return Err(NusaError::System(format!(
+                "Only the daemon's config directory ({}) can be compared",
+                config_dir.display()
+            )));
+        }
+    }
+    
+    let on_disk = read_config_dir(&config_dir, job_manager.recursive_config()).await?;
+    let plan = job_manager.plan_reconcile(&config_dir, &on_disk).await;
+    let rendered = plan.render();
+    Ok(DiffResult { plan, rendered })
+}
+
+/// Parse every job config in a directory, and with `recursive` in all directories below it,
+/// failing on the first invalid file
+async fn read_config_dir(dir: &Path, recursive: bool) -> Result<Vec<(PathBuf, JobConfig)>> {
+    let paths = config_files(dir, recursive)
+        .map_err(|e| ConfigError::Parse(format!("Failed to read {}: {}", dir.display(), e)))?;
+    
+    let mut configs = Vec::new();
+    
+    for path in paths {
+        let jobs = JobConfigFile::from_file(&path).await
+            .map_err(|e| ConfigError::Parse(format!("{}: {}", path.display(), e)))?;
+        configs.extend(jobs.into_jobs().into_iter().map(|job| (path.clone(), job)));
+    }
+    
+    Ok(configs)
+}
\ No newline at end of file
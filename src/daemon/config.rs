@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::{reload, Registry};
+
+use crate::job::JobManager;
+use crate::util::error::{ConfigError, NusaError, Result};
+
+/// Handle for changing the daemon's log level at runtime
+pub type LogHandle = reload::Handle<LevelFilter, Registry>;
+
+/// Settings for the daemon itself, as opposed to its jobs
+#[derive(Debug, Default, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct DaemonConfig {
+    /// Log level (hot-reloadable); unset keeps `--log-level`, `-v` or `-q`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<String>,
+    
+    /// Maximum number of loaded jobs (hot-reloadable); unset keeps `--max-jobs`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_jobs: Option<usize>,
+    
+    /// Control socket path, relative to the file's directory (requires restart); unset
+    /// keeps it in `--runtime-dir`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub control_socket: Option<PathBuf>,
+}
+
+/// Outcome of reloading the daemon config
+#[derive(Debug, Default, Serialize)]
+pub struct ReloadReport {
+    /// Settings that changed and were applied live
+    pub applied: Vec<String>,
+    
+    /// Settings that changed but only take effect after a restart
+    pub requires_restart: Vec<String>,
+}
+
+impl DaemonConfig {
+    /// Load daemon configuration from a TOML file
+    pub async fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        
+        if !path.exists() {
+            return Err(ConfigError::FileNotFound(path.to_path_buf()).into());
+        }
+        
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError::Parse(format!("Failed to read file: {}", e)))?;
+        
+        let mut config: Self = toml::from_str(&content)
+            .map_err(|e| ConfigError::Parse(format!("Invalid TOML: {}", e)))?;
+        
+        // The daemon has changed directory to / by now, so the working directory means nothing
+        if let Some(control_socket) = config.control_socket.as_mut().filter(|socket| socket.is_relative()) {
+            let dir = std::path::absolute(path)?.parent().map(Path::to_path_buf).unwrap_or_default();
+            *control_socket = dir.join(&*control_socket);
+        }
+        
+        config.validate()?;
+        
+        Ok(config)
+    }
+    
+    /// Validate configuration
+    pub fn validate(&self) -> Result<()> {
+        if let Some(level) = self.log_level.as_deref().filter(|level| parse_log_level(level).is_none()) {
+            return Err(ConfigError::Validation(
+                format!("Unknown log level: {}", level)
+            ).into());
+        }
+        
+        if self.max_jobs == Some(0) {
+            return Err(ConfigError::Validation("max_jobs must be at least 1".into()).into());
+        }
+        
+        Ok(())
+    }
+    
+    /// Compare against a newly loaded config, sorting changes by whether they can be applied live
+    pub fn changes(&self, new: &DaemonConfig) -> ReloadReport {
+        let mut report = ReloadReport::default();
+        
+        if self.log_level != new.log_level {
+            report.applied.push(format!("log_level: {} -> {}", describe(self.log_level.as_ref()), describe(new.log_level.as_ref())));
+        }
+        
+        if self.max_jobs != new.max_jobs {
+            report.applied.push(format!("max_jobs: {} -> {}", describe(self.max_jobs), describe(new.max_jobs)));
+        }
+        
+        if self.control_socket != new.control_socket {
+            report.requires_restart.push(format!(
+                "control_socket: {} -> {}",
+                describe(self.control_socket.as_ref().map(|path| path.display())),
+                describe(new.control_socket.as_ref().map(|path| path.display()))
+            ));
+        }
+        
+        report
+    }
+}
+
+fn describe<T: std::fmt::Display>(value: Option<T>) -> String {
+    value.map_or_else(|| "unset".to_string(), |value| value.to_string())
+}
+
+/// Parse a log level name
+pub fn parse_log_level(level: &str) -> Option<LevelFilter> {
+    match level.to_lowercase().as_str() {
+        "trace" => Some(LevelFilter::TRACE),
+        "debug" => Some(LevelFilter::DEBUG),
+        "info" => Some(LevelFilter::INFO),
+        "warn" => Some(LevelFilter::WARN),
+        "error" => Some(LevelFilter::ERROR),
+        _ => None,
+    }
+}
+
+/// Shared access to the active daemon config, for applying reloads at runtime
+#[derive(Clone)]
+pub struct DaemonConfigHandle {
+    path: PathBuf,
+    current: Arc<RwLock<DaemonConfig>>,
+    log_handle: LogHandle,
+    job_manager: JobManager,
+    /// The log level when the handle was made (`--log-level`, `-v`, `-q`), in force while
+    /// the file leaves `log_level` unset
+    default_log_level: LevelFilter,
+    /// The manager's job limit when the handle was made (`--max-jobs`), in force while
+    /// the file leaves `max_jobs` unset
+    default_max_jobs: usize,
+}
+
+impl DaemonConfigHandle {
+    pub fn new(path: PathBuf, config: DaemonConfig, log_handle: LogHandle, job_manager: JobManager) -> Self {
+        Self {
+            path,
+            current: Arc::new(RwLock::new(config)),
+            default_log_level: log_handle.clone_current().unwrap_or(LevelFilter::INFO),
+            log_handle,
+            default_max_jobs: job_manager.max_jobs(),
+            job_manager,
+        }
+    }
+    
+    /// Apply the hot-reloadable settings of the current config
+    pub async fn apply(&self) -> Result<()> {
+        let config = self.current.read().await.clone();
+        self.apply_config(&config)
+    }
+    
+    /// Snapshot of the active config
+    pub async fn current(&self) -> DaemonConfig {
+        self.current.read().await.clone()
+    }
+    
+    /// Re-read the config file and apply whatever can change without a restart
+    pub async fn reload(&self) -> Result<ReloadReport> {
+        let new = DaemonConfig::from_file(&self.path).await?;
+        
+        let mut current = self.current.write().await;
+        let report = current.changes(&new);
+        
+        self.apply_config(&new)?;
+        
+        // Restart-only settings keep their running values
+        let control_socket = current.control_socket.clone();
+        *current = DaemonConfig { control_socket, ..new };
+        
+        info!(
+            "Daemon config reloaded from {} ({} applied, {} require restart)",
+            self.path.display(),
+            report.applied.len(),
+            report.requires_restart.len()
+        );
+        
+        Ok(report)
+    }
+    
+    fn apply_config(&self, config: &DaemonConfig) -> Result<()> {
+        let level = config.log_level.as_deref()
+            .and_then(parse_log_level)
+            .unwrap_or(self.default_log_level);
+        self.log_handle.modify(|filter| *filter = level)
+            .map_err(|e| NusaError::System(format!("Failed to update log level: {}", e)))?;
+        
+        self.job_manager.set_max_jobs(config.max_jobs.unwrap_or(self.default_max_jobs));
+        
+        Ok(())
+    }
+}
\ No newline at end of file
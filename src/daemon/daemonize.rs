@@ -0,0 +1,65 @@
+//! Detaching the daemon from its terminal
+//!
+//! Runs before the tokio runtime exists: `fork` only carries the calling
+//! thread into the child, so forking a running runtime would lose its workers.
+
+use std::fs::{File, OpenOptions};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+use nix::unistd::{fork, setsid, ForkResult};
+
+use crate::util::error::{NusaError, Result};
+
+/// Open (appending) the file the daemon logs to once stderr is gone
+pub fn open_log_file(path: &Path) -> Result<File> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .mode(0o640)
+        .open(path)
+        .map_err(|e| NusaError::System(format!("Failed to open log file {}: {}", path.display(), e)))
+}
+
+/// Double-fork into a session-less background process
+///
+/// Returns in the daemon only; the original process and the intermediate
+/// session leader exit with status 0. Afterwards the working directory is `/`
+/// and stdin, stdout and stderr point at `/dev/null`.
+pub fn daemonize() -> Result<()> {
+    // The shell gets its prompt back, and the child is no process group leader
+    fork_and_exit_parent()?;
+    
+    setsid().map_err(|e| NusaError::System(format!("setsid failed: {}", e)))?;
+    
+    // The session leader exits, so the daemon can never reacquire a controlling terminal
+    fork_and_exit_parent()?;
+    
+    std::env::set_current_dir("/")?;
+    redirect_std_fds()
+}
+
+fn fork_and_exit_parent() -> Result<()> {
+    match unsafe { fork() } {
+        Ok(ForkResult::Parent { .. }) => unsafe { libc::_exit(0) },
+        Ok(ForkResult::Child) => Ok(()),
+        Err(e) => Err(NusaError::System(format!("fork failed: {}", e))),
+    }
+}
+
+fn redirect_std_fds() -> Result<()> {
+    let null = OpenOptions::new().read(true).write(true).open("/dev/null")?;
+    
+    for fd in [libc::STDIN_FILENO, libc::STDOUT_FILENO, libc::STDERR_FILENO] {
+        if unsafe { libc::dup2(null.as_raw_fd(), fd) } < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+    }
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,59 @@
+//! The daemon's own directories, created on startup
+//!
+//! Permission model:
+//!
+//! - `--runtime-dir` (mode 0755) holds the control socket. Anyone may traverse it;
+//!   the socket itself is mode 0660, so only its owner and members of its group
+//!   (`--control-group`) can connect and run `nusaload`.
+//! - `--state-dir` (mode 0700) holds enablement markers and schedule stamps; only
+//!   the daemon's user may read or change them.
+//!
+//! A directory that already exists keeps its mode, so an administrator can loosen
+//! or tighten it, but must belong to the daemon's user: one owned by someone else
+//! could have its contents swapped under the daemon, so startup fails instead.
+
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+use std::path::Path;
+
+use crate::util::error::{NusaError, Result};
+
+/// Mode of a newly created runtime directory
+pub const RUNTIME_DIR_MODE: u32 = 0o755;
+
+/// Mode of a newly created state directory
+pub const STATE_DIR_MODE: u32 = 0o700;
+
+/// Mode of the control socket
+pub const CONTROL_SOCKET_MODE: u32 = 0o660;
+
+/// Create `path` and any missing parents, giving the directory itself `mode`
+///
+/// Fails if `path` exists but is not a directory or is owned by another user.
+pub fn ensure_dir(path: &Path, mode: u32) -> Result<()> {
+    match std::fs::metadata(path) {
+        Ok(metadata) => {
+            if !metadata.is_dir() {
+                return Err(NusaError::System(format!("{} exists but is not a directory", path.display())));
+            }
+            
+            let euid = nix::unistd::geteuid().as_raw();
+            if metadata.uid() != euid {
+                return Err(NusaError::System(format!(
+                    "{} is owned by uid {}, not the daemon's uid {}; fix its ownership or choose another directory",
+                    path.display(), metadata.uid(), euid
+                )));
+            }
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            std::fs::DirBuilder::new().recursive(true).mode(mode).create(path).map_err(|e| {
+                NusaError::System(format!("Failed to create {}: {}", path.display(), e))
+            })?;
+            
+            // The umask may have masked bits off
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))?;
+            Ok(())
+        }
+        Err(e) => Err(NusaError::System(format!("Failed to inspect {}: {}", path.display(), e))),
+    }
+}
\ No newline at end of file
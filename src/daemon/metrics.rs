@@ -0,0 +1,235 @@
+//! Prometheus metrics over HTTP (`--metrics-addr`)
+//!
+//! Gauges are read from `JobManager::list_jobs` on every scrape; counters are
+//! accumulated from the job event stream. Only `GET /metrics` is served, so a
+//! few lines of HTTP/1.1 replace a web framework.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, Mutex};
+use tracing::{debug, info, warn};
+
+use crate::job::{JobEvent, JobManager, JobState};
+use crate::util::error::{NusaError, Result};
+
+/// Per-job counters driven by job events
+#[derive(Debug, Default, Clone)]
+pub struct JobCounters {
+    pub starts: u64,
+    pub exits: u64,
+    pub restarts: u64,
+    pub failures: u64,
+}
+
+/// Counters for every job seen on the event stream, keyed by label
+#[derive(Debug, Default, Clone)]
+pub struct Counters {
+    jobs: BTreeMap<String, JobCounters>,
+}
+
+impl Counters {
+    /// Count one event
+    pub fn record(&mut self, event: &JobEvent) {
+        match event {
+            JobEvent::JobStarted(label, ..) => self.job(label).starts += 1,
+            JobEvent::JobExited(label, ..) => self.job(label).exits += 1,
+            JobEvent::JobRestartScheduled(label, ..) => self.job(label).restarts += 1,
+            JobEvent::JobFailed(label, _) => self.job(label).failures += 1,
+            _ => {}
+        }
+    }
+    
+    /// Counters of one job, if it has had any events
+    pub fn get(&self, label: &str) -> Option<&JobCounters> {
+        self.jobs.get(label)
+    }
+    
+    fn job(&mut self, label: &str) -> &mut JobCounters {
+        self.jobs.entry(label.to_string()).or_default()
+    }
+}
+
+/// Escape a label value for the text exposition format
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Render all metrics in the Prometheus text exposition format
+pub async fn render(job_manager: &JobManager, counters: &Counters) -> String {
+    let mut jobs = job_manager.list_jobs().await;
+    jobs.sort_by(|a, b| a.label.cmp(&b.label));
+    
+    let mut out = String::new();
+    
+    let _ = writeln!(out, "# HELP nusalaunchd_jobs Number of loaded jobs");
+    let _ = writeln!(out, "# TYPE nusalaunchd_jobs gauge");
+    let _ = writeln!(out, "nusalaunchd_jobs {}", jobs.len());
+    
+    let _ = writeln!(out, "# HELP nusalaunchd_job_state Current state of each job (1 for the active state)");
+    let _ = writeln!(out, "# TYPE nusalaunchd_job_state gauge");
+    for job in &jobs {
+        let current = job.state.name();
+        for state in JobState::NAMES {
+            let _ = writeln!(
+                out,
+                "nusalaunchd_job_state{{job=\"{}\",state=\"{}\"}} {}",
+                escape(&job.label), state, u8::from(state == current)
+            );
+        }
+    }
+    
+    let _ = writeln!(out, "# HELP nusalaunchd_job_restart_count Restarts since the job was last started by hand");
+    let _ = writeln!(out, "# TYPE nusalaunchd_job_restart_count gauge");
+    for job in &jobs {
+        let _ = writeln!(out, "nusalaunchd_job_restart_count{{job=\"{}\"}} {}", escape(&job.label), job.restart_count);
+    }
+    
+    let _ = writeln!(out, "# HELP nusalaunchd_job_uptime_seconds Seconds the job's current process has been running");
+    let _ = writeln!(out, "# TYPE nusalaunchd_job_uptime_seconds gauge");
+    for job in &jobs {
+        let uptime = job.uptime_sec.unwrap_or(0) as f64;
+        let _ = writeln!(out, "nusalaunchd_job_uptime_seconds{{job=\"{}\"}} {:.3}", escape(&job.label), uptime);
+    }
+    
+    let _ = writeln!(out, "# HELP nusalaunchd_job_last_exit_code Exit code of the job's last process");
+    let _ = writeln!(out, "# TYPE nusalaunchd_job_last_exit_code gauge");
+    for job in &jobs {
+        if let Some(code) = job.exit_code {
+            let _ = writeln!(out, "nusalaunchd_job_last_exit_code{{job=\"{}\"}} {}", escape(&job.label), code);
+        }
+    }
+    
+    let counter_metrics: [(&str, &str, fn(&JobCounters) -> u64); 4] = [
+        ("nusalaunchd_job_starts_total", "Processes started", |c| c.starts),
+        ("nusalaunchd_job_exits_total", "Processes that exited on their own", |c| c.exits),
+        ("nusalaunchd_job_restarts_total", "Restarts scheduled by the supervisor", |c| c.restarts),
+        ("nusalaunchd_job_failures_total", "Times the job entered the failed state", |c| c.failures),
+    ];
+    for (name, help, value) in counter_metrics {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} counter", name);
+        for job in &jobs {
+            let count = counters.get(&job.label).map_or(0, value);
+            let _ = writeln!(out, "{}{{job=\"{}\"}} {}", name, escape(&job.label), count);
+        }
+    }
+    
+    let _ = writeln!(out, "# HELP nusalaunchd_events_dropped_total Job events dropped because the event channel was full");
+    let _ = writeln!(out, "# TYPE nusalaunchd_events_dropped_total counter");
+    let _ = writeln!(out, "nusalaunchd_events_dropped_total {}", job_manager.dropped_events());
+    
+    out
+}
+
+/// HTTP server exposing `/metrics`
+pub struct MetricsServer {
+    listener: TcpListener,
+    job_manager: JobManager,
+    counters: Arc<Mutex<Counters>>,
+}
+
+impl MetricsServer {
+    /// Bind the metrics endpoint and start counting job events
+    pub fn bind(addr: SocketAddr, job_manager: JobManager) -> Result<Self> {
+        let listener = std::net::TcpListener::bind(addr)
+            .and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                TcpListener::from_std(listener)
+            })
+            .map_err(|e| NusaError::System(format!("Failed to bind metrics endpoint {}: {}", addr, e)))?;
+        
+        let counters = Arc::new(Mutex::new(Counters::default()));
+        tokio::spawn(count_events(job_manager.subscribe(), Arc::clone(&counters)));
+        
+        info!("Serving metrics on http://{}/metrics", addr);
+        
+        Ok(Self {
+            listener,
+            job_manager,
+            counters,
+        })
+    }
+    
+    /// Address the endpoint is listening on
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+    
+    /// Accept connections until the task is dropped
+    pub async fn run(self) {
+        loop {
+            match self.listener.accept().await {
+                Ok((stream, _)) => {
+                    let job_manager = self.job_manager.clone();
+                    let counters = Arc::clone(&self.counters);
+                    tokio::spawn(async move {
+                        if let Err(e) = handle_connection(stream, job_manager, counters).await {
+                            debug!("Metrics connection closed with error: {}", e);
+                        }
+                    });
+                }
+                Err(e) => warn!("Failed to accept metrics connection: {}", e),
+            }
+        }
+    }
+}
+
+async fn count_events(mut events: broadcast::Receiver<JobEvent>, counters: Arc<Mutex<Counters>>) {
+    loop {
+        match events.recv().await {
+            Ok(event) => counters.lock().await.record(&event),
+            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                warn!("Metrics missed {} job events; counters may be low", missed);
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+/// Answer one request and close the connection
+async fn handle_connection(
+    stream: TcpStream,
+    job_manager: JobManager,
+    counters: Arc<Mutex<Counters>>,
+) -> Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    
+    let request_line = lines.next_line().await?.unwrap_or_default();
+    
+    // Skip the headers; requests have no body we care about
+    while let Some(header) = lines.next_line().await? {
+        if header.is_empty() {
+            break;
+        }
+    }
+    
+    let mut parts = request_line.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let path = target.split('?').next().unwrap_or("");
+    
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/metrics") => {
+            let counters = counters.lock().await.clone();
+            ("200 OK", "text/plain; version=0.0.4; charset=utf-8", render(&job_manager, &counters).await)
+        }
+        ("GET", _) => ("404 Not Found", "text/plain; charset=utf-8", "Not found; try /metrics\n".to_string()),
+        _ => ("405 Method Not Allowed", "text/plain; charset=utf-8", "Only GET is supported\n".to_string()),
+    };
+    
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    writer.write_all(response.as_bytes()).await?;
+    writer.shutdown().await?;
+    
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+//! Daemon-level runtime configuration for NusaLaunchd
+
+pub mod config;
+pub mod daemonize;
+pub mod dirs;
+pub mod metrics;
+pub mod pidfile;
+pub mod watch;
+
+// Re-export
+pub use config::{DaemonConfig, DaemonConfigHandle, LogHandle, ReloadReport};
+pub use pidfile::PidFile;
\ No newline at end of file
@@ -0,0 +1,118 @@
+//! The daemon's PID file (`--pid-file`)
+//!
+//! The file holds the daemon's PID and stays under an exclusive `flock` for as
+//! long as the daemon runs, so a second instance fails to lock it and refuses
+//! to start. The kernel drops the lock when its holder dies, which makes a file
+//! left behind by a crashed daemon reclaimable without any cleanup.
+
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+use crate::process::ProcessMonitor;
+use crate::util::error::{NusaError, Result};
+
+/// A written and locked PID file, removed again when dropped
+#[derive(Debug)]
+pub struct PidFile {
+    path: PathBuf,
+    file: File,
+}
+
+impl PidFile {
+    /// Lock `path` and write the current PID to it
+    ///
+    /// Fails if anyone holds the lock, even without a live PID in the file: a
+    /// daemon that just took the lock may not have written its PID yet.
+    pub fn acquire(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        
+        let mut file = match try_lock(path)? {
+            Some(file) => file,
+            None => {
+                return Err(match read_pid(path) {
+                    Some(pid) if ProcessMonitor::is_process_running(pid) => NusaError::System(format!(
+                        "NusaLaunchd is already running (pid {}, PID file {})",
+                        pid,
+                        path.display()
+                    )),
+                    _ => NusaError::System(format!(
+                        "PID file {} is locked by another process",
+                        path.display()
+                    )),
+                });
+            }
+        };
+        
+        let mut previous = String::new();
+        file.read_to_string(&mut previous)?;
+        if let Ok(pid) = previous.trim().parse::<u32>() {
+            info!("Reclaiming PID file {} left by pid {}", path.display(), pid);
+        }
+        
+        let mut pid_file = Self {
+            path: path.to_path_buf(),
+            file,
+        };
+        pid_file.update()?;
+        
+        Ok(pid_file)
+    }
+    
+    /// Rewrite the file with the current PID, e.g. after the daemon forked
+    ///
+    /// The lock belongs to the open file, so a forked child keeps holding it.
+    pub fn update(&mut self) -> Result<()> {
+        self.file.set_len(0)?;
+        self.file.seek(SeekFrom::Start(0))?;
+        writeln!(self.file, "{}", std::process::id())?;
+        self.file.sync_all()?;
+        Ok(())
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        // Removed while still locked, so a starting daemon never sees it half-gone
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            if e.kind() != ErrorKind::NotFound {
+                warn!("Failed to remove PID file {}: {}", self.path.display(), e);
+            }
+        }
+    }
+}
+
+/// Open `path` and take the lock without blocking; `None` if someone holds it
+fn try_lock(path: &Path) -> Result<Option<File>> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .mode(0o644)
+        .open(path)
+        .map_err(|e| {
+            NusaError::System(format!("Failed to open PID file {}: {}", path.display(), e))
+        })?;
+    
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+        return Ok(Some(file));
+    }
+    
+    let e = std::io::Error::last_os_error();
+    if e.kind() == ErrorKind::WouldBlock {
+        Ok(None)
+    } else {
+        Err(NusaError::System(format!("Failed to lock PID file {}: {}", path.display(), e)))
+    }
+}
+
+/// The PID recorded in `path`, if it holds one
+pub fn read_pid(path: &Path) -> Option<u32> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
\ No newline at end of file
@@ -0,0 +1,100 @@
+//! `--watch-config`: load, reload and unload jobs as their config files change
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+use crate::job::config::is_config_file;
+use crate::job::JobManager;
+use crate::util::error::{NusaError, Result};
+
+/// Quiet period after the last event for a file before the change is applied,
+/// so an editor's write-rename-chmod sequence counts as one save
+pub const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Watches a config directory until dropped
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+    task: JoinHandle<()>,
+}
+
+impl ConfigWatcher {
+    /// Start watching `dir`, applying changes to `job_manager`; subdirectories are watched
+    /// too if the manager reads them (`set_recursive_config`)
+    pub fn start(dir: &Path, job_manager: JobManager) -> Result<Self> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        
+        let mut watcher = notify::recommended_watcher(move |result: notify::Result<Event>| match result {
+            Ok(event) => {
+                if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+                    for path in event.paths.into_iter().filter(|path| is_config_file(path)) {
+                        let _ = tx.send(path);
+                    }
+                }
+            }
+            Err(e) => warn!("Config watch error: {}", e),
+        }).map_err(|e| NusaError::System(format!("Failed to create config watcher: {}", e)))?;
+        
+        let mode = if job_manager.recursive_config() {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        watcher.watch(dir, mode)
+            .map_err(|e| NusaError::System(format!("Failed to watch {}: {}", dir.display(), e)))?;
+        
+        info!("Watching {} for config changes", dir.display());
+        
+        Ok(Self {
+            _watcher: watcher,
+            task: tokio::spawn(debounce(rx, job_manager)),
+        })
+    }
+}
+
+impl Drop for ConfigWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Apply each changed file once it has been quiet for `DEBOUNCE`
+async fn debounce(mut rx: mpsc::UnboundedReceiver<PathBuf>, job_manager: JobManager) {
+    let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+    
+    loop {
+        let next_due = pending.values().min().copied();
+        
+        tokio::select! {
+            path = rx.recv() => match path {
+                Some(path) => {
+                    pending.insert(path, Instant::now() + DEBOUNCE);
+                    continue;
+                }
+                None => return,
+            },
+            _ = tokio::time::sleep_until(next_due.unwrap_or_else(Instant::now)), if next_due.is_some() => {}
+        }
+        
+        let now = Instant::now();
+        let due: Vec<PathBuf> = pending.iter()
+            .filter(|(_, due)| **due <= now)
+            .map(|(path, _)| path.clone())
+            .collect();
+        
+        for path in due {
+            pending.remove(&path);
+            apply_change(&job_manager, &path).await;
+        }
+    }
+}
+
+/// Bring the loaded jobs in line with one config file (see `JobManager::apply_config_file`)
+pub async fn apply_change(job_manager: &JobManager, path: &Path) {
+    job_manager.apply_config_file(path).await;
+}
\ No newline at end of file
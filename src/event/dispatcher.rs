@@ -1,17 +1,108 @@
-use tokio::sync::mpsc;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{broadcast, mpsc, Notify};
 use tracing::{info, warn, debug, instrument};
 
 use crate::job::manager::JobEvent;
 use crate::util::error::{NusaError, Result};
+use crate::util::logwriter::RotatingWriter;
+
+/// One line of the event log
+#[derive(Serialize)]
+struct EventRecord<'a> {
+    timestamp: String,
+    message: String,
+    #[serde(flatten)]
+    event: &'a JobEvent,
+}
+
+/// An event as the event log records it: `timestamp`, `message`, `event` and `data`,
+/// as one line of JSON without the newline
+pub fn event_record(event: &JobEvent) -> serde_json::Result<String> {
+    serde_json::to_string(&EventRecord {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        message: event.to_string(),
+        event,
+    })
+}
+
+/// Events buffered per subscriber before the slowest one starts missing events
+const SUBSCRIBER_CAPACITY: usize = 256;
+
+/// What `send` does while the event channel is full
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventOverflow {
+    /// Wait until the consumer catches up
+    Block,
+    /// Never wait: queue the event and drop the oldest queued one past the capacity
+    DropOldest,
+}
+
+impl Default for EventOverflow {
+    fn default() -> Self {
+        EventOverflow::Block
+    }
+}
+
+/// Events waiting for room in the channel under `EventOverflow::DropOldest`
+struct OverflowQueue {
+    events: Mutex<VecDeque<JobEvent>>,
+    capacity: usize,
+    ready: Notify,
+}
 
 #[derive(Clone)]
 pub struct EventDispatcher {
     tx: mpsc::Sender<JobEvent>,
+    subscribers: broadcast::Sender<JobEvent>,
+    overflow: Option<Arc<OverflowQueue>>,
+    dropped: Arc<AtomicU64>,
 }
 
 impl EventDispatcher {
     pub fn new(tx: mpsc::Sender<JobEvent>) -> Self {
-        Self { tx }
+        let (subscribers, _) = broadcast::channel(SUBSCRIBER_CAPACITY);
+        Self {
+            tx,
+            subscribers,
+            overflow: None,
+            dropped: Arc::new(AtomicU64::new(0)),
+        }
+    }
+    
+    /// A dispatcher that handles a full channel according to `policy`
+    ///
+    /// With `DropOldest`, up to `capacity` events queue up in front of the channel and a
+    /// background task forwards them in order, so `send` returns without waiting.
+    pub fn with_overflow(tx: mpsc::Sender<JobEvent>, capacity: usize, policy: EventOverflow) -> Self {
+        let mut dispatcher = Self::new(tx);
+        if policy == EventOverflow::DropOldest {
+            let queue = Arc::new(OverflowQueue {
+                events: Mutex::new(VecDeque::new()),
+                capacity: capacity.max(1),
+                ready: Notify::new(),
+            });
+            tokio::spawn(forward_events(Arc::clone(&queue), dispatcher.tx.clone()));
+            dispatcher.overflow = Some(queue);
+        }
+        dispatcher
+    }
+    
+    /// Events dropped so far because the channel was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+    
+    /// Receive a copy of every event sent from now on
+    ///
+    /// Subscribers never slow down the daemon: a receiver that falls more than
+    /// `SUBSCRIBER_CAPACITY` events behind loses the oldest ones, and its next `recv()`
+    /// returns `RecvError::Lagged(n)` with the number skipped before resuming with the
+    /// oldest event still buffered.
+    pub fn subscribe(&self) -> broadcast::Receiver<JobEvent> {
+        self.subscribers.subscribe()
     }
     
     /// Send a job event
@@ -19,51 +110,80 @@ impl EventDispatcher {
     pub async fn send(&self, event: JobEvent) -> Result<()> {
         debug!("Dispatching event");
         
+        // Having no subscribers is not an error
+        let _ = self.subscribers.send(event.clone());
+        
+        if let Some(queue) = &self.overflow {
+            let full = {
+                let mut events = queue.events.lock().unwrap_or_else(|e| e.into_inner());
+                events.push_back(event);
+                events.len() > queue.capacity && events.pop_front().is_some()
+            };
+            queue.ready.notify_one();
+            
+            if full {
+                let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                if dropped == 1 || dropped % 100 == 0 {
+                    warn!("Event channel is full, dropping the oldest events ({} dropped so far)", dropped);
+                }
+            }
+            return Ok(());
+        }
+        
         self.tx.send(event).await
             .map_err(|e| NusaError::System(format!("Failed to send event: {}", e)))?;
         
         Ok(())
     }
     
-    /// Process events from a receiver
-    pub async fn process_events(mut rx: mpsc::Receiver<JobEvent>) {
+    /// Process events from a receiver, optionally appending them to a rotating event log
+    ///
+    /// Each event is written as a JSON line and flushed right away so the log can be tailed.
+    pub async fn process_events(mut rx: mpsc::Receiver<JobEvent>, mut event_log: Option<RotatingWriter>) {
         info!("Starting event processor");
         
         while let Some(event) = rx.recv().await {
             match &event {
-                JobEvent::JobLoaded(label) => {
-                    info!("[EVENT] Job loaded: {}", label);
-                }
-                JobEvent::JobStarted(label, pid, _) => {
-                    info!("[EVENT] Job started: {} [PID: {}]", label, pid);
-                }
-                JobEvent::JobStopped(label, previous_state) => {
-                    info!("[EVENT] Job stopped: {} (was: {:?})", label, previous_state);
-                }
-                JobEvent::JobExited(label, code, signal, restart_count) => {
-                    let signal_info = signal.map(|s| format!("signal {}", s))
-                        .unwrap_or_else(|| "normally".to_string());
-                    info!(
-                        "[EVENT] Job exited: {} with code {}, {} (restarts: {})",
-                        label, code, signal_info, restart_count
-                    );
-                }
-                JobEvent::JobFailed(label, state) => {
-                    warn!("[EVENT] Job failed: {} with state: {:?}", label, state);
-                }
-                JobEvent::JobRestartScheduled(label, delay, attempt) => {
-                    info!(
-                        "[EVENT] Job restart scheduled: {} in {:?} (attempt {})",
-                        label, delay, attempt
-                    );
+                JobEvent::JobFailed(..)
+                | JobEvent::RapidCleanExits(..)
+                | JobEvent::JobBlocked(..)
+                | JobEvent::HealthCheckFailed(..)
+                | JobEvent::JobStartTimedOut(..)
+                | JobEvent::SpawnFailed(..) => warn!("[EVENT] {}", event),
+                _ => info!("[EVENT] {}", event),
+            }
+            
+            if let Some(log) = event_log.as_mut() {
+                let written = event_record(&event)
+                    .map_err(std::io::Error::from)
+                    .and_then(|line| log.write_line(&line))
+                    .and_then(|()| log.flush());
+                if let Err(e) = written {
+                    warn!("Failed to write event log {}: {}", log.path().display(), e);
                 }
-                JobEvent::JobReadyForRestart(label) => {
-                    info!("[EVENT] Job ready for restart: {}", label);
+            }
+        }
+        
+        if let Some(log) = event_log.as_mut() {
+            let _ = log.flush();
+        }
+    }
+}
+
+/// Move queued events into the channel until it closes
+async fn forward_events(queue: Arc<OverflowQueue>, tx: mpsc::Sender<JobEvent>) {
+    loop {
+        let next = queue.events.lock().unwrap_or_else(|e| e.into_inner()).pop_front();
+        match next {
+            Some(event) => {
+                if tx.send(event).await.is_err() {
+                    return;
                 }
             }
-            
-            // TODO: Add hooks for external event listeners
-            // TODO: Persist events to log file/database
+            None => tokio::select! {
+                _ = queue.ready.notified() => {}
+                _ = tx.closed() => return,
+            },
         }
     }
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
 pub mod dispatcher;
 
 // Re-export
-pub use dispatcher::EventDispatcher;
\ No newline at end of file
+pub use dispatcher::{EventDispatcher, EventOverflow};
\ No newline at end of file
@@ -0,0 +1,55 @@
+//! Start conditions (`[conditions]` in a job config)
+//!
+//! Conditions are checked every time the job is about to be spawned. A job
+//! whose conditions do not hold is `Skipped` rather than failed, and checked
+//! again on its next start (schedule, socket activation, restart or by hand).
+
+use std::time::Duration;
+
+use crate::job::config::ConditionsConfig;
+use crate::job::hooks::run_hook;
+use crate::util::error::{ConfigError, Result};
+
+/// How long an `exec` condition may run before it counts as failed
+pub const CONDITION_EXEC_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Check that condition paths are absolute and the command is not empty
+pub fn validate_conditions(conditions: &ConditionsConfig) -> Result<()> {
+    for path in conditions.path_exists.iter().chain(&conditions.path_absent) {
+        if !path.is_absolute() {
+            return Err(ConfigError::Validation(format!(
+                "conditions: path must be absolute: {}",
+                path.display()
+            )).into());
+        }
+    }
+    
+    if conditions.exec.as_deref().map_or(false, |command| command.trim().is_empty()) {
+        return Err(ConfigError::Validation(
+            "conditions: exec must not be empty".into()
+        ).into());
+    }
+    
+    Ok(())
+}
+
+/// Why the job should not start now, or `None` if every condition holds
+///
+/// Paths are checked first, so the command only runs when they pass.
+pub async fn unmet_condition(label: &str, conditions: &ConditionsConfig) -> Option<String> {
+    if let Some(path) = conditions.path_exists.iter().find(|path| !path.exists()) {
+        return Some(format!("{} does not exist", path.display()));
+    }
+    
+    if let Some(path) = conditions.path_absent.iter().find(|path| path.exists()) {
+        return Some(format!("{} exists", path.display()));
+    }
+    
+    if let Some(command) = &conditions.exec {
+        if let Err(e) = run_hook(command, label, None, CONDITION_EXEC_TIMEOUT).await {
+            return Some(format!("exec condition {}", e));
+        }
+    }
+    
+    None
+}
\ No newline at end of file
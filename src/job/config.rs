@@ -1,5 +1,7 @@
+use nix::sys::signal::Signal;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::time::Duration;
 use crate::util::error::{ConfigError, Result};
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -23,12 +25,67 @@ pub struct JobConfig {
     #[serde(default)]
     pub environment: Vec<EnvironmentVar>,
     
+    /// File of KEY=VALUE lines to add to the environment (relative to working_directory);
+    /// inline `environment` entries win on conflicts
+    #[serde(default)]
+    pub env_file: Option<PathBuf>,
+    
+    /// Expand `$VAR` / `${VAR}` from the daemon's environment at load time; off by
+    /// default, so a `$` meant for the job's shell reaches it untouched
+    #[serde(default)]
+    pub expand_env: bool,
+    
     /// Working directory
     #[serde(default)]
     pub working_directory: Option<PathBuf>,
+    
+    /// Create a missing working directory, owned by the job's user/group, before spawning
+    #[serde(default)]
+    pub create_working_directory: bool,
+    
+    /// Resource limits
+    #[serde(default)]
+    pub limits: LimitsConfig,
+    
+    /// CPU priority and OOM killer preference
+    #[serde(default)]
+    pub process: ProcessConfig,
+    
+    /// Output logging
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    
+    /// Time-based triggering (instead of keep_alive)
+    #[serde(default)]
+    pub schedule: Option<ScheduleConfig>,
+    
+    /// Jobs to start before this one, if they are loaded
+    #[serde(default)]
+    pub after: Vec<String>,
+    
+    /// Jobs that must be started first (socket-activated ones only listening); this job
+    /// is blocked if one of them fails
+    #[serde(default)]
+    pub requires: Vec<String>,
+    
+    /// Periodic health probe while running
+    #[serde(default)]
+    pub health: Option<HealthConfig>,
+    
+    /// Sockets the daemon listens on for the job; it is started on the first connection
+    #[serde(default)]
+    pub sockets: Vec<SocketConfig>,
+    
+    /// Commands run when the job starts, exits, or fails
+    #[serde(default)]
+    pub hooks: HooksConfig,
+    
+    /// Preconditions checked before every start; the job is skipped while they fail
+    #[serde(default)]
+    pub conditions: ConditionsConfig,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct ProgramConfig {
     /// Path to executable
     pub path: PathBuf,
@@ -36,9 +93,89 @@ pub struct ProgramConfig {
     /// Command line arguments
     #[serde(default)]
     pub arguments: Vec<String>,
+    
+    /// Process title shown by `ps` (replaces argv[0])
+    ///
+    /// Only argv[0] can be set from outside the process: the kernel resets the
+    /// comm name (`/proc/<pid>/comm`, `ps -o comm`) to the executable's name on
+    /// exec, so `PR_SET_NAME` has to be called by the program itself. Interpreters
+    /// also see the new argv[0] as `$0`.
+    #[serde(default)]
+    pub proc_title: Option<String>,
+    
+    /// Use the job label as the process title when `proc_title` is unset
+    #[serde(default)]
+    pub label_as_proc_title: bool,
+    
+    /// User to run as (name or numeric UID)
+    #[serde(default)]
+    pub user: Option<String>,
+    
+    /// Group to run as (name or numeric GID, defaults to the user's primary group)
+    #[serde(default)]
+    pub group: Option<String>,
+    
+    /// File mode creation mask as an octal string such as "0027"; unset inherits the daemon's
+    #[serde(default)]
+    pub umask: Option<String>,
+    
+    /// Start from an empty environment instead of inheriting the daemon's
+    ///
+    /// The daemon's environment can carry secrets, proxy settings or a PATH that
+    /// points at directories the job should not trust; with this set the job sees
+    /// only its own `environment`, `env_file` and, if enabled, `default_path`.
+    #[serde(default)]
+    pub clear_environment: bool,
+    
+    /// With `clear_environment`, set a minimal PATH (`DEFAULT_PATH`) unless the job sets its own
+    #[serde(default)]
+    pub default_path: bool,
+    
+    /// Where stdin comes from: `"null"`, `"inherit"` (the daemon's), or a file or FIFO to read
+    /// (relative to working_directory)
+    #[serde(default)]
+    pub stdin: StdinSource,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+/// Source of a job's stdin, written as `"null"`, `"inherit"` or a path
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(from = "String", into = "String")]
+pub enum StdinSource {
+    Null,
+    Inherit,
+    File(PathBuf),
+}
+
+impl Default for StdinSource {
+    fn default() -> Self {
+        Self::Null
+    }
+}
+
+impl From<String> for StdinSource {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "null" => Self::Null,
+            "inherit" => Self::Inherit,
+            _ => Self::File(PathBuf::from(value)),
+        }
+    }
+}
+
+impl From<StdinSource> for String {
+    fn from(source: StdinSource) -> Self {
+        match source {
+            StdinSource::Null => "null".to_string(),
+            StdinSource::Inherit => "inherit".to_string(),
+            StdinSource::File(path) => path.display().to_string(),
+        }
+    }
+}
+
+/// PATH given to jobs with `clear_environment` and `default_path`
+pub const DEFAULT_PATH: &str = "/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin";
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct SupervisionConfig {
     /// Whether to keep the process alive
     #[serde(default = "default_true")]
@@ -48,13 +185,235 @@ pub struct SupervisionConfig {
     #[serde(default)]
     pub restart_policy: RestartPolicy,
     
+    /// Exit codes that always restart a kept-alive job, whatever the restart policy
+    #[serde(default)]
+    pub restart_on_exit_codes: Vec<i32>,
+    
+    /// Exit codes that never restart the job, e.g. a configuration error; these win
+    /// over `restart_on_exit_codes` and the restart policy
+    #[serde(default)]
+    pub no_restart_exit_codes: Vec<i32>,
+    
     /// Seconds to wait before restarting
     #[serde(default = "default_restart_delay")]
     pub restart_delay_sec: u64,
     
+    /// How the restart delay grows with repeated restarts
+    #[serde(default)]
+    pub backoff_strategy: BackoffStrategy,
+    
+    /// Seconds to wait before a job started at load or with the other jobs is
+    /// spawned, e.g. to let the network settle (0 = start at once)
+    #[serde(default)]
+    pub start_delay_sec: u64,
+    
     /// Maximum restart attempts (0 = unlimited)
     #[serde(default = "default_max_restarts")]
     pub max_restarts: u32,
+    
+    /// Window in seconds for `restart_limit_burst` (0 = no rate limit)
+    #[serde(default)]
+    pub restart_limit_interval_sec: u64,
+    
+    /// Restarts allowed within `restart_limit_interval_sec` before the job fails
+    #[serde(default = "default_restart_limit_burst")]
+    pub restart_limit_burst: u32,
+    
+    /// Uptime in seconds after which earlier restarts are forgotten (0 = never)
+    #[serde(default = "default_success_reset")]
+    pub success_reset_sec: u64,
+    
+    /// Retries for transient spawn failures (EAGAIN, ENOMEM) before giving up
+    #[serde(default = "default_spawn_retries")]
+    pub spawn_retries: u32,
+    
+    /// With keep_alive, a program that cannot be spawned at all (e.g. not deployed yet) is
+    /// retried with backoff like a crashed one, up to max_restarts, instead of failing at once
+    #[serde(default)]
+    pub retry_on_spawn_failure: bool,
+    
+    /// Seconds to wait after the stop signal before sending SIGKILL (default 10)
+    #[serde(default)]
+    pub stop_timeout_sec: Option<u64>,
+    
+    /// Signal for a graceful stop, e.g. "SIGINT" or "QUIT" (default SIGTERM)
+    #[serde(default)]
+    pub kill_signal: Option<String>,
+    
+    /// Run the job in its own process group and stop the whole group, so
+    /// children it forked do not outlive it
+    #[serde(default = "default_true")]
+    pub kill_process_group: bool,
+    
+    /// Seconds a job has to become ready before it is killed and marked failed (0 = no limit)
+    #[serde(default)]
+    pub start_timeout_sec: u64,
+    
+    /// Seconds a process may run before it is killed and the job marked failed,
+    /// a safety net for one-shot jobs (0 = no limit)
+    #[serde(default)]
+    pub max_runtime_sec: u64,
+    
+    /// The job reports readiness by sending `READY=1` to `NOTIFY_SOCKET`; it stays
+    /// Starting until then
+    #[serde(default)]
+    pub notify_ready: bool,
+}
+
+// By hand, so a job without a [supervision] table gets the same defaults as an empty one
+impl Default for SupervisionConfig {
+    fn default() -> Self {
+        Self {
+            keep_alive: default_true(),
+            restart_policy: RestartPolicy::default(),
+            restart_on_exit_codes: Vec::new(),
+            no_restart_exit_codes: Vec::new(),
+            restart_delay_sec: default_restart_delay(),
+            backoff_strategy: BackoffStrategy::default(),
+            start_delay_sec: 0,
+            max_restarts: default_max_restarts(),
+            restart_limit_interval_sec: 0,
+            restart_limit_burst: default_restart_limit_burst(),
+            success_reset_sec: default_success_reset(),
+            spawn_retries: default_spawn_retries(),
+            retry_on_spawn_failure: false,
+            stop_timeout_sec: None,
+            kill_signal: None,
+            kill_process_group: default_true(),
+            start_timeout_sec: 0,
+            max_runtime_sec: 0,
+            notify_ready: false,
+        }
+    }
+}
+
+impl SupervisionConfig {
+    /// Delay before restart attempt `attempt` (0 for the first restart)
+    ///
+    /// Growth stops at 2^6 times `restart_delay_sec` for exponential backoff and at
+    /// `MAX_RESTART_BACKOFF_SEC` overall, but never goes below `restart_delay_sec` itself.
+    pub fn restart_backoff(&self, attempt: u32) -> Duration {
+        let base_secs = self.restart_delay_sec;
+        
+        let multiplier = match self.backoff_strategy {
+            BackoffStrategy::Fixed => 1,
+            BackoffStrategy::Exponential => 2u64.pow(attempt.min(6)),
+            BackoffStrategy::Linear => attempt as u64 + 1,
+        };
+        
+        let backoff_secs = base_secs.saturating_mul(multiplier)
+            .min(MAX_RESTART_BACKOFF_SEC)
+            .max(base_secs);
+        
+        Duration::from_secs(backoff_secs)
+    }
+    
+    /// Whether the restart exit codes or, failing those, the restart policy ask for a
+    /// restart after this exit
+    pub fn wants_restart(&self, exit_code: i32, signal: Option<i32>) -> bool {
+        if !self.keep_alive {
+            return false;
+        }
+        
+        // A process killed by a signal has no exit code to match
+        if signal.is_none() {
+            if self.no_restart_exit_codes.contains(&exit_code) {
+                return false;
+            }
+            if self.restart_on_exit_codes.contains(&exit_code) {
+                return true;
+            }
+        }
+        
+        match self.restart_policy {
+            RestartPolicy::Always => true,
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure => exit_code != 0,
+            RestartPolicy::OnCrash => signal.is_some(),
+        }
+    }
+    
+    /// Whether a failed spawn schedules a restart instead of failing the job
+    pub fn retries_spawn_failure(&self) -> bool {
+        self.keep_alive && self.retry_on_spawn_failure
+    }
+    
+    /// Whether a process that ran for `uptime` has earned a fresh restart count
+    pub fn resets_restart_count(&self, uptime: Duration) -> bool {
+        self.success_reset_sec > 0 && uptime >= Duration::from_secs(self.success_reset_sec)
+    }
+    
+    /// How long a graceful stop may take before the job is killed
+    pub fn stop_timeout(&self) -> Duration {
+        Duration::from_secs(self.stop_timeout_sec.unwrap_or(DEFAULT_STOP_TIMEOUT_SEC))
+    }
+    
+    /// How long an automatic start waits before spawning
+    pub fn start_delay(&self) -> Duration {
+        Duration::from_secs(self.start_delay_sec)
+    }
+    
+    /// The window restarts are rate limited in, if any
+    pub fn restart_limit_interval(&self) -> Option<Duration> {
+        (self.restart_limit_interval_sec > 0).then(|| Duration::from_secs(self.restart_limit_interval_sec))
+    }
+    
+    /// How long a job may take to become ready, if limited
+    pub fn start_timeout(&self) -> Option<Duration> {
+        (self.start_timeout_sec > 0).then(|| Duration::from_secs(self.start_timeout_sec))
+    }
+    
+    /// How long a process may run, if limited
+    pub fn max_runtime(&self) -> Option<Duration> {
+        (self.max_runtime_sec > 0).then(|| Duration::from_secs(self.max_runtime_sec))
+    }
+    
+    /// Signal sent to stop the job gracefully
+    pub fn stop_signal(&self) -> Signal {
+        self.kill_signal.as_deref()
+            .and_then(parse_signal)
+            .unwrap_or(Signal::SIGTERM)
+    }
+}
+
+/// Parse a signal name such as `SIGINT` or `int`, or a number such as `2`
+pub fn parse_signal(name: &str) -> Option<Signal> {
+    let name = name.trim().to_ascii_uppercase();
+    if let Ok(number) = name.parse::<i32>() {
+        return Signal::try_from(number).ok();
+    }
+    if name.starts_with("SIG") {
+        name.parse().ok()
+    } else {
+        format!("SIG{}", name).parse().ok()
+    }
+}
+
+/// Parse an octal umask such as `0027` or `027`
+pub fn parse_umask(value: &str) -> Option<u32> {
+    let value = value.trim();
+    if value.is_empty() || value.len() > 4 {
+        return None;
+    }
+    u32::from_str_radix(value, 8).ok().filter(|mask| *mask <= 0o777)
+}
+
+/// Parse a cpuset-style list of cores such as `0-3,6`
+pub fn parse_cpu_list(value: &str) -> Option<Vec<usize>> {
+    let mut cores = Vec::new();
+    for part in value.split(',').map(str::trim) {
+        match part.split_once('-') {
+            Some((first, last)) => {
+                let (first, last): (usize, usize) = (first.trim().parse().ok()?, last.trim().parse().ok()?);
+                if first > last {
+                    return None;
+                }
+                cores.extend(first..=last);
+            }
+            None => cores.push(part.parse().ok()?),
+        }
+    }
+    Some(cores)
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -63,7 +422,7 @@ pub enum RestartPolicy {
     Never,      // Never restart
     Always,     // Always restart
     OnFailure,  // Restart on non-zero exit
-    OnCrash,    // Restart on signal termination
+    OnCrash,    // Restart on signal termination the daemon did not cause
 }
 
 impl Default for RestartPolicy {
@@ -72,39 +431,607 @@ impl Default for RestartPolicy {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackoffStrategy {
+    Fixed,        // Always restart_delay_sec
+    Exponential,  // restart_delay_sec * 2^attempt
+    Linear,       // restart_delay_sec * attempt count
+}
+
+impl Default for BackoffStrategy {
+    fn default() -> Self {
+        Self::Exponential
+    }
+}
+
+/// Where a job's stdout/stderr go
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct LoggingConfig {
+    /// File to append stdout to (relative to working_directory); unset forwards to the daemon log
+    #[serde(default)]
+    pub stdout_path: Option<PathBuf>,
+    
+    /// File to append stderr to (relative to working_directory); unset forwards to the daemon log
+    #[serde(default)]
+    pub stderr_path: Option<PathBuf>,
+    
+    /// Where captured output goes: `daemon` (the daemon log), `file`, `syslog`, `journald`, or `stderr`
+    ///
+    /// With `syslog`, `journald`, or `stderr`, streams that have a path are written to
+    /// the file as well.
+    #[serde(default)]
+    pub log_target: LogTarget,
+    
+    /// Rotate a log file once it would grow past this many bytes (0 never rotates)
+    #[serde(default)]
+    pub log_max_bytes: u64,
+    
+    /// Rotated files kept as `<name>.1` .. `<name>.<log_keep>`
+    #[serde(default = "default_log_keep")]
+    pub log_keep: usize,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            stdout_path: None,
+            stderr_path: None,
+            log_target: LogTarget::default(),
+            log_max_bytes: 0,
+            log_keep: default_log_keep(),
+        }
+    }
+}
+
+/// Sink for the output of a job
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogTarget {
+    /// Forward lines to the daemon's own log; streams with a path go to their file
+    Daemon,
+    /// Only the files in `stdout_path` and `stderr_path`
+    File,
+    /// The local syslog socket (`/dev/log`), tagged with the job label
+    Syslog,
+    /// The systemd journal, with `SYSLOG_IDENTIFIER` set to the job label
+    Journald,
+    /// The daemon's stderr, each line prefixed with the job label
+    Stderr,
+}
+
+impl Default for LogTarget {
+    fn default() -> Self {
+        Self::Daemon
+    }
+}
+
+impl LogTarget {
+    /// Whether output is piped through the daemon even for streams that have a file
+    pub fn captures(&self) -> bool {
+        matches!(self, LogTarget::Syslog | LogTarget::Journald | LogTarget::Stderr)
+    }
+}
+
+/// Health probe for a running job; set exactly one of `command`, `tcp_port`, `http_url`
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HealthConfig {
+    /// Shell command that exits 0 when healthy
+    #[serde(default)]
+    pub command: Option<String>,
+    
+    /// Local TCP port that accepts connections when healthy
+    #[serde(default)]
+    pub tcp_port: Option<u16>,
+    
+    /// `http://` URL that answers with a 2xx or 3xx status when healthy
+    #[serde(default)]
+    pub http_url: Option<String>,
+    
+    /// Seconds between probes
+    #[serde(default = "default_health_interval")]
+    pub interval_sec: u64,
+    
+    /// Seconds before a single probe counts as failed
+    #[serde(default = "default_health_timeout")]
+    pub timeout_sec: u64,
+    
+    /// Consecutive failed probes before the job is restarted
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+}
+
+/// Shell commands run on job events with `NUSA_LABEL` and `NUSA_EXIT_CODE` set
+///
+/// Hooks are fire-and-forget: they are killed after `timeout_sec` and never restarted.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct HooksConfig {
+    /// Run when the job's process starts
+    #[serde(default)]
+    pub on_start: Option<String>,
+    
+    /// Run when the job exits and is not restarted
+    #[serde(default)]
+    pub on_exit: Option<String>,
+    
+    /// Run when the job ends up failed
+    #[serde(default)]
+    pub on_failure: Option<String>,
+    
+    /// Seconds a hook may run before it is killed
+    #[serde(default = "default_hook_timeout")]
+    pub timeout_sec: u64,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            on_start: None,
+            on_exit: None,
+            on_failure: None,
+            timeout_sec: default_hook_timeout(),
+        }
+    }
+}
+
+/// Preconditions a job needs to start; all must hold
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ConditionsConfig {
+    /// Paths that must exist
+    #[serde(default)]
+    pub path_exists: Vec<PathBuf>,
+    
+    /// Paths that must not exist
+    #[serde(default)]
+    pub path_absent: Vec<PathBuf>,
+    
+    /// Shell command that must exit 0, run with `NUSA_LABEL` set
+    #[serde(default)]
+    pub exec: Option<String>,
+}
+
+/// A socket held open by the daemon and passed to the job (`LISTEN_FDS`)
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SocketConfig {
+    /// `host:port`, a bare port (all IPv4 addresses), or an absolute unix socket path
+    pub listen: String,
+    
+    /// `stream` (TCP or unix stream) or `dgram` (UDP or unix datagram)
+    #[serde(rename = "type", default)]
+    pub socket_type: SocketType,
+    
+    /// Name passed in `LISTEN_FDNAMES` (defaults to the job label)
+    #[serde(default)]
+    pub name: Option<String>,
+    
+    /// Stop the job (without failing it) once no new connection or datagram arrived on
+    /// this socket for this many seconds; it is started again on the next one. With
+    /// several sockets, all of them must be idle. Connections the job already
+    /// accepted do not count as activity.
+    #[serde(default)]
+    pub idle_timeout_sec: Option<u64>,
+}
+
+impl SocketConfig {
+    /// Name the job sees for this socket in `LISTEN_FDNAMES`
+    pub fn fd_name<'a>(&'a self, label: &'a str) -> &'a str {
+        self.name.as_deref().unwrap_or(label)
+    }
+    
+    /// How long the socket may go without activity before its job is stopped
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout_sec.map(Duration::from_secs)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SocketType {
+    Stream,
+    Dgram,
+}
+
+impl Default for SocketType {
+    fn default() -> Self {
+        Self::Stream
+    }
+}
+
+impl std::fmt::Display for SocketType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SocketType::Stream => write!(f, "stream"),
+            SocketType::Dgram => write!(f, "dgram"),
+        }
+    }
+}
+
+/// When to run a periodic job, like launchd's StartInterval / StartCalendarInterval
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduleConfig {
+    /// Run every N seconds
+    #[serde(default)]
+    pub interval_sec: Option<u64>,
+    
+    /// Run whenever the local time matches all set fields
+    #[serde(default)]
+    pub calendar: Option<CalendarInterval>,
+    
+    /// Run once at startup if a scheduled run was missed while the daemon was down
+    #[serde(default)]
+    pub run_missed: bool,
+}
+
+/// Calendar match; unset fields match any value
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CalendarInterval {
+    /// Minute (0-59)
+    #[serde(default)]
+    pub minute: Option<u32>,
+    
+    /// Hour (0-23)
+    #[serde(default)]
+    pub hour: Option<u32>,
+    
+    /// Day of month (1-31)
+    #[serde(default)]
+    pub day: Option<u32>,
+    
+    /// Day of week (0-7, both 0 and 7 are Sunday)
+    #[serde(default)]
+    pub weekday: Option<u32>,
+}
+
+/// Scheduling and OOM settings applied to the process before exec; unset fields inherit the daemon's
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessConfig {
+    /// Nice value (-20 highest priority to 19 lowest); lowering it needs root
+    #[serde(default)]
+    pub nice: Option<i32>,
+    
+    /// OOM killer score adjustment (-1000 never kill to 1000 kill first); negative values need root
+    #[serde(default)]
+    pub oom_score_adj: Option<i32>,
+    
+    /// CPU cores the process may run on, such as `[0, 1]` or `"0-3,6"`; unset means no pinning (Linux only)
+    #[serde(default)]
+    pub cpu_affinity: Option<CpuAffinity>,
+}
+
+/// A set of CPU core indices, as a list of numbers or a cpuset-style string
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum CpuAffinity {
+    Cores(Vec<usize>),
+    List(String),
+}
+
+impl CpuAffinity {
+    /// The core indices, sorted and without duplicates; `None` if the list is malformed
+    pub fn cores(&self) -> Option<Vec<usize>> {
+        let mut cores = match self {
+            Self::Cores(cores) => cores.clone(),
+            Self::List(list) => parse_cpu_list(list)?,
+        };
+        cores.sort_unstable();
+        cores.dedup();
+        Some(cores)
+    }
+}
+
+/// Per-job resource limits; unset fields inherit the daemon's limits
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct LimitsConfig {
+    /// Maximum open file descriptors (RLIMIT_NOFILE)
+    #[serde(default)]
+    pub nofile: Option<ResourceLimit>,
+    
+    /// Maximum number of processes (RLIMIT_NPROC)
+    #[serde(default)]
+    pub nproc: Option<ResourceLimit>,
+    
+    /// Maximum address space in bytes (RLIMIT_AS)
+    #[serde(default)]
+    pub as_bytes: Option<ResourceLimit>,
+    
+    /// Maximum core dump size in bytes (RLIMIT_CORE)
+    #[serde(default)]
+    pub core: Option<ResourceLimit>,
+    
+    /// Maximum CPU time in seconds (RLIMIT_CPU)
+    #[serde(default)]
+    pub cpu_seconds: Option<ResourceLimit>,
+}
+
+/// A soft/hard limit pair
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct ResourceLimit {
+    pub soft: u64,
+    pub hard: u64,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct EnvironmentVar {
     pub key: String,
     pub value: String,
 }
 
+/// Substitute `${VAR}` and `$VAR` with values from the environment; `$$` is a literal `$`
+///
+/// Unset variables expand to nothing, or are an error when `strict`.
+pub fn expand_vars(value: &str, strict: bool) -> Result<String> {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            out.push(c);
+            continue;
+        }
+        
+        let name = match chars.peek() {
+            Some('$') => {
+                chars.next();
+                out.push('$');
+                continue;
+            }
+            Some('{') => {
+                chars.next();
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(ConfigError::Validation(
+                            format!("Unterminated '${{' in '{}'", value)
+                        ).into()),
+                    }
+                }
+                if !is_var_name(&name) {
+                    return Err(ConfigError::Validation(
+                        format!("Invalid variable name '${{{}}}' in '{}'", name, value)
+                    ).into());
+                }
+                name
+            }
+            Some(c) if *c == '_' || c.is_ascii_alphabetic() => {
+                let mut name = String::new();
+                while let Some(c) = chars.peek().copied().filter(|c| *c == '_' || c.is_ascii_alphanumeric()) {
+                    name.push(c);
+                    chars.next();
+                }
+                name
+            }
+            // A lone `$` stays as it is
+            _ => {
+                out.push('$');
+                continue;
+            }
+        };
+        
+        match std::env::var(&name) {
+            Ok(expanded) => out.push_str(&expanded),
+            Err(_) if strict => return Err(ConfigError::Validation(
+                format!("Environment variable '{}' is not set", name)
+            ).into()),
+            Err(_) => tracing::warn!("Environment variable '{}' is not set; expanding to nothing", name),
+        }
+    }
+    
+    Ok(out)
+}
+
+fn is_var_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+/// Ceiling on how far restart backoff grows (5 minutes)
+pub const MAX_RESTART_BACKOFF_SEC: u64 = 300;
+
+/// Default grace period for `stop_timeout_sec`
+pub const DEFAULT_STOP_TIMEOUT_SEC: u64 = 10;
+
+/// Separates a template's name from an instance name: template `worker@`, instance `worker@1`
+pub const INSTANCE_SEPARATOR: char = '@';
+
+/// Replaced by the instance name in a template's arguments and environment values
+pub const INSTANCE_TOKEN: &str = "%i";
+
+/// Split an instance label into its template label and instance name: `worker@1`
+/// becomes `("worker@", "1")`; `None` for a template or a label without `@`
+pub fn split_instance(label: &str) -> Option<(&str, &str)> {
+    let at = label.find(INSTANCE_SEPARATOR)?;
+    let (template, instance) = label.split_at(at + 1);
+    (at > 0 && !instance.is_empty()).then_some((template, instance))
+}
+
+/// File extensions recognized as job configs
+pub const CONFIG_EXTENSIONS: &[&str] = &["toml", "json", "yaml", "yml"];
+
+/// Whether a path looks like a job config file
+pub fn is_config_file(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .map_or(false, |ext| CONFIG_EXTENSIONS.contains(&ext))
+}
+
 // Default value helpers
 fn default_true() -> bool { true }
 fn default_restart_delay() -> u64 { 1 }
 fn default_max_restarts() -> u32 { 5 }
+fn default_restart_limit_burst() -> u32 { 5 }
+fn default_success_reset() -> u64 { 300 }
+fn default_spawn_retries() -> u32 { 3 }
+fn default_health_interval() -> u64 { 10 }
+fn default_health_timeout() -> u64 { 5 }
+fn default_failure_threshold() -> u32 { 3 }
+fn default_hook_timeout() -> u64 { 30 }
+fn default_log_keep() -> usize { 5 }
 
-impl JobConfig {
-    /// Load job configuration from a TOML file
+/// The contents of a config file: one job, or several declared as `[[job]]` tables
+#[derive(Debug, Clone)]
+pub enum JobConfigFile {
+    Single(Box<JobConfig>),
+    Multiple(Vec<JobConfig>),
+}
+
+/// Just enough of a config file to tell which shape it has
+#[derive(Deserialize)]
+struct FileShape {
+    #[serde(default, alias = "jobs")]
+    job: Option<serde::de::IgnoredAny>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JobList {
+    #[serde(alias = "jobs")]
+    job: Vec<JobConfig>,
+}
+
+impl JobConfigFile {
+    /// Load every job in a TOML, JSON, or YAML file
     pub async fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        Self::load(path.as_ref(), false).await
+    }
+    
+    /// Load from file, treating references to unset environment variables as errors
+    pub async fn from_file_strict<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        Self::load(path.as_ref(), true).await
+    }
+    
+    /// Parse every job in a file as written, without expanding variables or validating
+    pub async fn read<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
         let path = path.as_ref();
         
-        // Check if file exists
-        if !path.exists() {
-            return Err(ConfigError::FileNotFound(path.to_path_buf()).into());
-        }
+        // Read file content without blocking the runtime
+        let content = tokio::fs::read_to_string(path).await.map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => ConfigError::FileNotFound(path.to_path_buf()),
+            _ => ConfigError::Parse(format!("Failed to read file: {}", e)),
+        })?;
         
-        // Read file content
-        let content = std::fs::read_to_string(path)
-            .map_err(|e| ConfigError::Parse(format!("Failed to read file: {}", e)))?;
+        let shape: FileShape = parse(path, &content)?;
+        let mut file = match shape.job {
+            Some(_) => {
+                let list: JobList = parse(path, &content)?;
+                if list.job.is_empty() {
+                    return Err(ConfigError::Parse(format!("{} defines no jobs", path.display())).into());
+                }
+                Self::Multiple(list.job)
+            }
+            None => Self::Single(Box::new(parse(path, &content)?)),
+        };
         
-        // Parse TOML
-        let config: Self = toml::from_str(&content)
-            .map_err(|e| ConfigError::Parse(format!("Invalid TOML: {}", e)))?;
+        let mut labels = std::collections::HashSet::new();
+        for config in file.jobs_mut() {
+            if !labels.insert(config.label.clone()) {
+                return Err(ConfigError::Parse(format!(
+                    "Duplicate label '{}' in {}",
+                    config.label,
+                    path.display()
+                )).into());
+            }
+        }
         
-        // Validate the configuration
-        config.validate().await?;
+        Ok(file)
+    }
+    
+    async fn load(path: &std::path::Path, strict: bool) -> Result<Self> {
+        let mut file = Self::read(path).await?;
         
-        Ok(config)
+        for config in file.jobs_mut() {
+            if config.expand_env {
+                config.expand_env(strict)?;
+            }
+            
+            // Validate the configuration
+            config.validate().await?;
+        }
+        
+        Ok(file)
+    }
+    
+    fn jobs_mut(&mut self) -> &mut [JobConfig] {
+        match self {
+            Self::Single(config) => std::slice::from_mut(config.as_mut()),
+            Self::Multiple(jobs) => jobs,
+        }
+    }
+    
+    /// The file's jobs in declaration order
+    pub fn into_jobs(self) -> Vec<JobConfig> {
+        match self {
+            Self::Single(config) => vec![*config],
+            Self::Multiple(jobs) => jobs,
+        }
+    }
+}
+
+/// Parse according to the file extension (TOML when there is none)
+fn parse<T: serde::de::DeserializeOwned>(path: &std::path::Path, content: &str) -> Result<T> {
+    Ok(match path.extension().and_then(|s| s.to_str()) {
+        None | Some("toml") => toml::from_str(content)
+            .map_err(|e| ConfigError::Parse(format!("Invalid TOML: {}", e)))?,
+        Some("json") => serde_json::from_str(content)
+            .map_err(|e| ConfigError::Parse(format!("Invalid JSON: {}", e)))?,
+        Some("yaml") | Some("yml") => serde_yaml::from_str(content)
+            .map_err(|e| ConfigError::Parse(format!("Invalid YAML: {}", e)))?,
+        Some(_) => return Err(ConfigError::UnsupportedFormat.into()),
+    })
+}
+
+impl JobConfig {
+    /// Load the one job defined in a TOML, JSON, or YAML file
+    pub async fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        Self::load(path.as_ref(), false).await
+    }
+    
+    /// Load from file, treating references to unset environment variables as errors
+    pub async fn from_file_strict<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+        Self::load(path.as_ref(), true).await
+    }
+    
+    async fn load(path: &std::path::Path, strict: bool) -> Result<Self> {
+        match JobConfigFile::load(path, strict).await? {
+            JobConfigFile::Single(config) => Ok(*config),
+            JobConfigFile::Multiple(mut jobs) if jobs.len() == 1 => Ok(jobs.remove(0)),
+            JobConfigFile::Multiple(jobs) => Err(ConfigError::Parse(format!(
+                "{} defines {} jobs, expected one",
+                path.display(),
+                jobs.len()
+            )).into()),
+        }
+    }
+    
+    /// Whether this is a template such as `worker@`: never started itself, only
+    /// instantiated as `worker@1`, `worker@2`, ...
+    pub fn is_template(&self) -> bool {
+        self.label.len() > 1 && self.label.ends_with(INSTANCE_SEPARATOR)
+    }
+    
+    /// The config of this template's instance `instance`, with `%i` in arguments and
+    /// environment values replaced by the instance name
+    pub fn instantiate(&self, instance: &str) -> JobConfig {
+        let mut config = self.clone();
+        config.label = format!("{}{}", self.label, instance);
+        for argument in &mut config.program.arguments {
+            *argument = argument.replace(INSTANCE_TOKEN, instance);
+        }
+        for var in &mut config.environment {
+            var.value = var.value.replace(INSTANCE_TOKEN, instance);
+        }
+        config
     }
     
     /// Validate configuration
@@ -126,6 +1053,56 @@ impl JobConfig {
             ).into());
         }
         
+        if self.label.starts_with(INSTANCE_SEPARATOR) || self.label.matches(INSTANCE_SEPARATOR).count() > 1 {
+            return Err(ConfigError::Validation(format!(
+                "Label '{}' must be a name, a template 'name@' or an instance 'name@instance'",
+                self.label
+            )).into());
+        }
+        
+        if let Some(health) = &self.health {
+            crate::job::health::validate_health(health)?;
+        }
+        
+        if let Some(umask) = &self.program.umask {
+            crate::job::validator::ConfigValidator::validate_umask(umask)?;
+        }
+        
+        crate::job::validator::ConfigValidator::validate_environment(&self.environment)?;
+        
+        crate::job::validator::ConfigValidator::validate_process(&self.process)?;
+        
+        crate::job::validator::ConfigValidator::validate_restart_limit(&self.supervision)?;
+        
+        crate::job::validator::ConfigValidator::validate_exit_codes(&self.supervision)?;
+        
+        crate::job::sockets::validate_sockets(&self.sockets)?;
+        
+        crate::job::hooks::validate_hooks(&self.hooks)?;
+        
+        crate::job::conditions::validate_conditions(&self.conditions)?;
+        
+        crate::process::output::validate_logging(&self.logging)?;
+        
+        if crate::job::dependency::dependencies(self).any(|dep| *dep == self.label) {
+            return Err(ConfigError::Validation(
+                format!("Job '{}' cannot depend on itself", self.label)
+            ).into());
+        }
+        
+        // Resolve user/group now so unknown names are caught at load time
+        crate::process::credentials::Credentials::resolve(&self.program)?;
+        
+        if let Some(schedule) = &self.schedule {
+            crate::job::scheduler::validate_schedule(schedule)?;
+            
+            if self.supervision.keep_alive {
+                return Err(ConfigError::Validation(
+                    "A scheduled job cannot also set keep_alive=true".into()
+                ).into());
+            }
+        }
+        
         // Validate restart policy logic
         if !self.supervision.keep_alive && self.supervision.restart_policy != RestartPolicy::Never {
             tracing::warn!(
@@ -134,6 +1111,20 @@ impl JobConfig {
             );
         }
         
+        if !self.supervision.keep_alive && self.supervision.retry_on_spawn_failure {
+            tracing::warn!(
+                "Job '{}': retry_on_spawn_failure is ignored when keep_alive=false",
+                self.label
+            );
+        }
+        
+        if !self.supervision.keep_alive && !self.supervision.restart_on_exit_codes.is_empty() {
+            tracing::warn!(
+                "Job '{}': restart_on_exit_codes is ignored when keep_alive=false",
+                self.label
+            );
+        }
+        
         // Check max_restarts logic
         if self.supervision.max_restarts == 0 {
             tracing::info!(
@@ -145,6 +1136,35 @@ impl JobConfig {
         Ok(())
     }
     
+    /// Expand `$VAR` / `${VAR}` from the daemon's environment in arguments,
+    /// environment values and the working directory
+    pub fn expand_env(&mut self, strict: bool) -> Result<()> {
+        for argument in self.program.arguments.iter_mut() {
+            *argument = expand_vars(argument, strict)?;
+        }
+        
+        for env in self.environment.iter_mut() {
+            env.value = expand_vars(&env.value, strict)?;
+        }
+        
+        if let Some(dir) = self.working_directory.as_mut() {
+            if let Some(raw) = dir.to_str() {
+                *dir = PathBuf::from(expand_vars(raw, strict)?);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Process title to use for argv[0], if any
+    pub fn process_title(&self) -> Option<&str> {
+        match &self.program.proc_title {
+            Some(title) => Some(title),
+            None if self.program.label_as_proc_title => Some(&self.label),
+            None => None,
+        }
+    }
+    
     /// Convert to environment variables format for std::process
     pub fn get_env_vars(&self) -> Vec<(String, String)> {
         self.environment
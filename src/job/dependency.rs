@@ -0,0 +1,126 @@
+//! Dependency ordering between jobs (`after` / `requires`)
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::job::config::JobConfig;
+use crate::util::error::{ConfigError, Result};
+
+/// Labels a job must start after: its `requires` plus its `after` lists
+pub fn dependencies(config: &JobConfig) -> impl Iterator<Item = &String> {
+    config.requires.iter().chain(config.after.iter())
+}
+
+/// Labels of the jobs in `configs` that depend on `label`, sorted
+pub fn dependents<'a>(label: &str, configs: impl IntoIterator<Item = &'a JobConfig>) -> Vec<String> {
+    let mut labels: Vec<String> = configs.into_iter()
+        .filter(|config| config.label != label && dependencies(config).any(|dep| dep == label))
+        .map(|config| config.label.clone())
+        .collect();
+    labels.sort();
+    labels
+}
+
+/// Find a dependency cycle, returned as the labels along it (first label repeated at the end)
+///
+/// Dependencies on labels that are not in `configs` are ignored.
+pub fn find_cycle<'a>(configs: impl IntoIterator<Item = &'a JobConfig>) -> Option<Vec<String>> {
+    cycle_in(&build_graph(configs))
+}
+
+fn visit<'a>(
+    label: &'a str,
+    graph: &BTreeMap<&'a str, Vec<&'a str>>,
+    marks: &mut HashMap<&'a str, u8>,
+    path: &mut Vec<&'a str>,
+) -> Option<Vec<String>> {
+    match marks.get(label).copied().unwrap_or(0) {
+        2 => return None,
+        1 => {
+            let start = path.iter().position(|l| *l == label).unwrap_or(0);
+            let mut cycle: Vec<String> = path[start..].iter().map(|l| l.to_string()).collect();
+            cycle.push(label.to_string());
+            return Some(cycle);
+        }
+        _ => {}
+    }
+    
+    marks.insert(label, 1);
+    path.push(label);
+    
+    for dep in &graph[label] {
+        if let Some(cycle) = visit(dep, graph, marks, path) {
+            return Some(cycle);
+        }
+    }
+    
+    path.pop();
+    marks.insert(label, 2);
+    None
+}
+
+/// Order labels so every job comes after its dependencies (ties broken alphabetically)
+pub fn start_order<'a>(configs: impl IntoIterator<Item = &'a JobConfig>) -> Result<Vec<String>> {
+    let graph = build_graph(configs);
+    
+    if let Some(cycle) = cycle_in(&graph) {
+        return Err(cycle_error(&cycle).into());
+    }
+    
+    let mut remaining: BTreeMap<&str, usize> = graph.iter()
+        .map(|(label, deps)| (*label, deps.len()))
+        .collect();
+    let mut ready: BTreeSet<&str> = remaining.iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(label, _)| *label)
+        .collect();
+    let mut order = Vec::with_capacity(graph.len());
+    
+    while let Some(label) = ready.iter().next().copied() {
+        ready.remove(label);
+        remaining.remove(label);
+        order.push(label.to_string());
+        
+        for (dependent, deps) in &graph {
+            if deps.contains(&label) {
+                if let Some(count) = remaining.get_mut(dependent) {
+                    *count -= 1;
+                    if *count == 0 {
+                        ready.insert(dependent);
+                    }
+                }
+            }
+        }
+    }
+    
+    Ok(order)
+}
+
+/// Validation error naming a cycle, e.g. `a -> b -> a`
+pub fn cycle_error(cycle: &[String]) -> ConfigError {
+    ConfigError::Validation(format!("Dependency cycle: {}", cycle.join(" -> ")))
+}
+
+fn cycle_in(graph: &BTreeMap<&str, Vec<&str>>) -> Option<Vec<String>> {
+    // 0 = unvisited, 1 = on the current path, 2 = done
+    let mut marks = HashMap::new();
+    let mut path = Vec::new();
+    graph.keys().find_map(|start| visit(start, graph, &mut marks, &mut path))
+}
+
+/// Label -> loaded dependencies, deduplicated
+fn build_graph<'a>(configs: impl IntoIterator<Item = &'a JobConfig>) -> BTreeMap<&'a str, Vec<&'a str>> {
+    let configs: Vec<&JobConfig> = configs.into_iter().collect();
+    let labels: BTreeSet<&str> = configs.iter().map(|c| c.label.as_str()).collect();
+    
+    configs.iter()
+        .map(|config| {
+            let mut deps: Vec<&str> = dependencies(config)
+                .map(String::as_str)
+                .filter(|dep| labels.contains(dep))
+                .collect();
+            deps.sort();
+            deps.dedup();
+            (config.label.as_str(), deps)
+        })
+        .collect()
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+//! Boot-time enablement of jobs, kept as marker files under `state_dir/enabled/<target>/`
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::util::error::{NusaError, Result};
+
+/// Target the daemon boots into unless told otherwise
+pub const DEFAULT_TARGET: &str = "multi-user";
+
+/// Directory holding the markers for one target
+pub fn target_dir(state_dir: &Path, target: &str) -> PathBuf {
+    state_dir.join("enabled").join(target)
+}
+
+/// Reject names that would escape the enabled directory
+fn check_name(kind: &str, name: &str) -> Result<()> {
+    if name.is_empty() || name == "." || name == ".." || name.contains('/') {
+        return Err(NusaError::System(format!("Invalid {} name: '{}'", kind, name)));
+    }
+    Ok(())
+}
+
+/// Enable `label` for `target`; returns false if it already was
+pub fn enable(state_dir: &Path, target: &str, label: &str) -> Result<bool> {
+    check_name("target", target)?;
+    check_name("job", label)?;
+    
+    let dir = target_dir(state_dir, target);
+    let marker = dir.join(label);
+    if marker.exists() {
+        return Ok(false);
+    }
+    
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(&marker, "")?;
+    Ok(true)
+}
+
+/// Disable `label` for every target; returns the targets it was removed from
+pub fn disable(state_dir: &Path, label: &str) -> Result<Vec<String>> {
+    check_name("job", label)?;
+    
+    let mut removed = Vec::new();
+    for target in targets(state_dir)? {
+        match std::fs::remove_file(target_dir(state_dir, &target).join(label)) {
+            Ok(()) => removed.push(target),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    
+    Ok(removed)
+}
+
+/// Targets that have an enabled directory, sorted
+pub fn targets(state_dir: &Path) -> Result<Vec<String>> {
+    list_dir(&state_dir.join("enabled"))
+        .map(|names| names.unwrap_or_default())
+}
+
+/// Labels enabled for `target`, sorted, or `None` if the target was never set up
+pub fn enabled_labels(state_dir: &Path, target: &str) -> Result<Option<Vec<String>>> {
+    check_name("target", target)?;
+    list_dir(&target_dir(state_dir, target))
+}
+
+fn list_dir(dir: &Path) -> Result<Option<Vec<String>>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    names.sort();
+    
+    Ok(Some(names))
+}
\ No newline at end of file
@@ -0,0 +1,185 @@
+//! Health probes for running jobs
+
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::process::Command;
+use tokio::time;
+use tracing::{debug, warn};
+
+use crate::job::config::HealthConfig;
+use crate::job::manager::{JobManager, JobState};
+use crate::util::error::{ConfigError, Result};
+
+/// Check that exactly one probe is set and the timings are usable
+pub fn validate_health(health: &HealthConfig) -> Result<()> {
+    let probes = [
+        health.command.is_some(),
+        health.tcp_port.is_some(),
+        health.http_url.is_some(),
+    ];
+    
+    if probes.iter().filter(|set| **set).count() != 1 {
+        return Err(ConfigError::Validation(
+            "health: set exactly one of command, tcp_port, or http_url".into()
+        ).into());
+    }
+    
+    if let Some(url) = &health.http_url {
+        parse_http_url(url).map_err(|e| ConfigError::Validation(format!("health.http_url: {}", e)))?;
+    }
+    
+    if health.interval_sec == 0 || health.timeout_sec == 0 {
+        return Err(ConfigError::Validation(
+            "health: interval_sec and timeout_sec must be at least 1".into()
+        ).into());
+    }
+    
+    if health.failure_threshold == 0 {
+        return Err(ConfigError::Validation(
+            "health: failure_threshold must be at least 1".into()
+        ).into());
+    }
+    
+    Ok(())
+}
+
+/// Run the configured probe once
+pub async fn probe(health: &HealthConfig) -> std::result::Result<(), String> {
+    let timeout = Duration::from_secs(health.timeout_sec);
+    
+    let result = if let Some(command) = &health.command {
+        time::timeout(timeout, probe_command(command)).await
+    } else if let Some(port) = health.tcp_port {
+        time::timeout(timeout, probe_tcp(port)).await
+    } else if let Some(url) = &health.http_url {
+        time::timeout(timeout, probe_http(url)).await
+    } else {
+        return Err("no probe configured".to_string());
+    };
+    
+    result.unwrap_or_else(|_| Err(format!("timed out after {}s", health.timeout_sec)))
+}
+
+async fn probe_command(command: &str) -> std::result::Result<(), String> {
+    let status = Command::new("/bin/sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .status()
+        .await
+        .map_err(|e| format!("failed to run '{}': {}", command, e))?;
+    
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("'{}' exited with {}", command, status))
+    }
+}
+
+async fn probe_tcp(port: u16) -> std::result::Result<(), String> {
+    TcpStream::connect(("127.0.0.1", port)).await
+        .map(|_| ())
+        .map_err(|e| format!("port {}: {}", port, e))
+}
+
+async fn probe_http(url: &str) -> std::result::Result<(), String> {
+    let (host, port, path) = parse_http_url(url)?;
+    
+    let mut stream = TcpStream::connect((host.as_str(), port)).await
+        .map_err(|e| format!("{}: {}", url, e))?;
+    
+    let request = format!("GET {} HTTP/1.0\r\nHost: {}\r\nConnection: close\r\n\r\n", path, host);
+    stream.write_all(request.as_bytes()).await
+        .map_err(|e| format!("{}: {}", url, e))?;
+    
+    // Only the status line matters
+    let mut buf = [0u8; 64];
+    let len = stream.read(&mut buf).await
+        .map_err(|e| format!("{}: {}", url, e))?;
+    let status_line = String::from_utf8_lossy(&buf[..len]);
+    
+    let status: u16 = status_line.split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| format!("{}: invalid HTTP response", url))?;
+    
+    if (200..400).contains(&status) {
+        Ok(())
+    } else {
+        Err(format!("{} returned HTTP {}", url, status))
+    }
+}
+
+/// Split an `http://host[:port][/path]` URL
+fn parse_http_url(url: &str) -> std::result::Result<(String, u16, String), String> {
+    let rest = url.strip_prefix("http://")
+        .ok_or_else(|| format!("only http:// URLs are supported: {}", url))?;
+    
+    let (authority, path) = match rest.find('/') {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => (rest, "/"),
+    };
+    
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => {
+            let port = port.parse().map_err(|_| format!("invalid port in {}", url))?;
+            (host, port)
+        }
+        None => (authority, 80),
+    };
+    
+    if host.is_empty() {
+        return Err(format!("missing host in {}", url));
+    }
+    
+    Ok((host.to_string(), port, path.to_string()))
+}
+
+/// Probe a job for as long as the same process keeps running; the first passing probe marks it ready
+pub async fn run_health_checks(manager: JobManager, label: String, pid: u32, health: HealthConfig) {
+    let mut failures = 0;
+    let mut ready = false;
+    
+    loop {
+        time::sleep(Duration::from_secs(health.interval_sec)).await;
+        
+        match manager.get_job_status(&label).await {
+            Some(status) if status.state == JobState::Running && status.pid == Some(pid) => {}
+            _ => {
+                debug!("Job '{}' [PID: {}] no longer running, stopping health checks", label, pid);
+                return;
+            }
+        }
+        
+        match probe(&health).await {
+            Ok(()) => {
+                if !ready {
+                    manager.mark_ready(&label, pid).await;
+                    ready = true;
+                }
+                if failures > 0 {
+                    debug!("Job '{}' is healthy again", label);
+                }
+                failures = 0;
+            }
+            Err(reason) => {
+                failures += 1;
+                warn!(
+                    "Health check failed for job '{}' ({}/{}): {}",
+                    label, failures, health.failure_threshold, reason
+                );
+                
+                if failures >= health.failure_threshold {
+                    if let Err(e) = manager.handle_unhealthy(&label, failures, reason).await {
+                        warn!("Failed to handle unhealthy job '{}': {}", label, e);
+                    }
+                    return;
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,101 @@
+//! Hook commands run on job events (`[hooks]` in a job config)
+
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::sync::broadcast;
+use tokio::time;
+use tracing::{debug, warn};
+
+use crate::job::config::HooksConfig;
+use crate::job::manager::{JobEvent, JobManager};
+use crate::util::error::{ConfigError, Result};
+
+/// Check that hooks get time to run
+pub fn validate_hooks(hooks: &HooksConfig) -> Result<()> {
+    if hooks.timeout_sec == 0 {
+        return Err(ConfigError::Validation(
+            "hooks: timeout_sec must be at least 1".into()
+        ).into());
+    }
+    
+    Ok(())
+}
+
+/// Run the hooks of every job event received until the daemon shuts down
+///
+/// Each hook runs in its own task, so a slow hook never holds up the next event.
+pub async fn run_hooks(manager: JobManager, mut events: broadcast::Receiver<JobEvent>) {
+    loop {
+        match events.recv().await {
+            Ok(event) => dispatch(&manager, event).await,
+            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                warn!("Missed {} job events; their hooks did not run", missed);
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+    }
+}
+
+/// Start the hook matching an event, if the job has one
+async fn dispatch(manager: &JobManager, event: JobEvent) {
+    let label = match &event {
+        JobEvent::JobStarted(label, ..) | JobEvent::JobExited(label, ..) | JobEvent::JobFailed(label, _) => label,
+        _ => return,
+    };
+    
+    let status = match manager.get_job_status(label).await {
+        Some(status) => status,
+        None => return,
+    };
+    let hooks = status.config.hooks;
+    
+    let (name, command, exit_code) = match &event {
+        JobEvent::JobStarted(..) => ("on_start", hooks.on_start, None),
+        JobEvent::JobExited(_, code, ..) => ("on_exit", hooks.on_exit, Some(*code)),
+        JobEvent::JobFailed(..) => ("on_failure", hooks.on_failure, status.exit_code),
+        _ => return,
+    };
+    
+    if let Some(command) = command {
+        let label = label.clone();
+        let timeout = Duration::from_secs(hooks.timeout_sec);
+        tokio::spawn(async move {
+            if let Err(e) = run_hook(&command, &label, exit_code, timeout).await {
+                warn!("{} hook of job '{}' failed: {}", name, label, e);
+            }
+        });
+    }
+}
+
+/// Run one hook command through `/bin/sh -c`, killing it after `timeout`
+pub async fn run_hook(
+    command: &str,
+    label: &str,
+    exit_code: Option<i32>,
+    timeout: Duration,
+) -> std::result::Result<(), String> {
+    debug!("Running hook of job '{}': {}", label, command);
+    
+    let mut cmd = Command::new("/bin/sh");
+    cmd.arg("-c")
+        .arg(command)
+        .env("NUSA_LABEL", label)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true);
+    match exit_code {
+        Some(code) => cmd.env("NUSA_EXIT_CODE", code.to_string()),
+        None => cmd.env_remove("NUSA_EXIT_CODE"),
+    };
+    
+    let status = time::timeout(timeout, cmd.status()).await
+        .map_err(|_| format!("timed out after {}s", timeout.as_secs()))?
+        .map_err(|e| format!("failed to run '{}': {}", command, e))?;
+    
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("'{}' exited with {}", command, status))
+    }
+}
\ No newline at end of file
@@ -1,24 +1,56 @@
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::os::fd::AsRawFd;
+use std::pin::Pin;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+use nix::sys::signal::Signal;
 use tokio::sync::{Mutex, RwLock, mpsc};
 use tokio::time;
 use tracing::{info, warn, error, debug, instrument};
 
-use crate::job::config::{JobConfig, RestartPolicy};
-use crate::process::spawner::ProcessSpawner;
-use crate::event::dispatcher::EventDispatcher;
-use crate::util::error::{NusaError, Result};
+use crate::job::config::{self, EnvironmentVar, HealthConfig, JobConfig, JobConfigFile, RestartPolicy, SupervisionConfig};
+use crate::job::reconcile::{self, diff_configs, ReconcilePlan};
+use crate::job::{conditions, dependency, health, hooks, scheduler};
+use crate::job::notify::{self, NotifySocket};
+use crate::job::sockets::{self, ActivationSocket, SocketStatus};
+use crate::job::supervisor::JobSupervisor;
+use crate::process::activation::ListenFd;
+use crate::process::check::{self, SpawnCheck};
+use crate::process::credentials::Credentials;
+use crate::process::spawner::{ProcessExit, ProcessSpawner, Spawner};
+use crate::process::usage::{self, CpuSample};
+use crate::event::dispatcher::{EventDispatcher, EventOverflow};
+use crate::util::error::{describe_signal, ConfigError, NusaError, ProcessError, Result};
 
-#[derive(Debug, Clone, PartialEq)]
+/// Default limit on the number of loaded jobs
+const DEFAULT_MAX_JOBS: usize = 512;
+
+/// A clean exit sooner than this after start counts as a "rapid" exit
+const RAPID_EXIT_WINDOW: Duration = Duration::from_secs(10);
+
+/// Consecutive rapid clean exits under `RestartPolicy::Always` before warning
+const RAPID_CLEAN_EXIT_THRESHOLD: u32 = 3;
+
+/// Restarts remembered per job for `get_job_history`
+const RESTART_HISTORY_LEN: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum JobState {
     Stopped,
+    Completed,  // Ran to a clean exit and is not restarted
     Starting,
     Running,
     Stopping,
     Restarting,
     Failed(String),
     Backoff,  // Waiting before restart
+    Waiting,  // Waiting out start_delay_sec before an automatic start
+    Blocked(String),  // A required job is not running
+    Skipped(String),  // A start condition did not hold
 }
 
 #[derive(Debug)]
@@ -27,61 +59,471 @@ pub struct JobInstance {
     pub state: JobState,
     pub pid: Option<u32>,
     pub start_time: Option<Instant>,
+    /// When the current process started, in clock ticks after boot (`/proc/<pid>/stat`)
+    pub process_start_ticks: Option<u64>,
     pub restart_count: u32,
+    /// Supervised restarts scheduled since the job was loaded; unlike `restart_count`, never reset
+    pub total_restarts: u64,
+    /// When recent supervised restarts were scheduled, pruned to `restart_limit_interval_sec`
+    pub restart_times: VecDeque<Instant>,
+    /// The last `RESTART_HISTORY_LEN` supervised restarts, oldest first
+    pub restart_history: VecDeque<RestartRecord>,
     pub last_exit_code: Option<i32>,
     pub last_exit_signal: Option<i32>,
     pub backoff_until: Option<Instant>,
     pub process_handle: Option<tokio::task::JoinHandle<()>>,
+    /// The process is ready: it sent `READY=1` or passed its first health check, or has neither to pass
+    pub ready: bool,
+    pub rapid_clean_exits: u32,
+    /// Last signal the daemon sent the current process; dying of it is expected, not a crash
+    pub sent_signal: Option<i32>,
+    /// Config file the job was loaded from, if any
+    pub source_path: Option<PathBuf>,
+    /// The config was reloaded with changes the running process doesn't have yet
+    pub needs_restart: bool,
+    /// Bound activation sockets, passed to every process of the job
+    pub sockets: Vec<Arc<ActivationSocket>>,
+    pub activation_handle: Option<tokio::task::JoinHandle<()>>,
+    /// Pending delayed start, while the job is Waiting
+    pub start_delay_handle: Option<tokio::task::JoinHandle<()>>,
+    /// CPU time at the last status query, to report CPU use since then
+    pub cpu_sample: std::sync::Mutex<Option<CpuSample>>,
+}
+
+impl JobInstance {
+    /// Whether a job requiring this one may start: it is running, or it is socket-activated
+    /// and listening, so dependents can connect and start it on demand
+    fn satisfies_requires(&self) -> bool {
+        match self.state {
+            JobState::Running => true,
+            JobState::Failed(_) => false,
+            _ => !self.sockets.is_empty(),
+        }
+    }
+    
+    /// The rate limit window if `restart_limit_burst` restarts already happened within it
+    ///
+    /// Restarts older than the window are forgotten first.
+    fn restart_limit_hit(&mut self) -> Option<Duration> {
+        let interval = self.config.supervision.restart_limit_interval()?;
+        let now = Instant::now();
+        while self.restart_times.front().map_or(false, |at| now.duration_since(*at) >= interval) {
+            self.restart_times.pop_front();
+        }
+        
+        (self.restart_times.len() >= self.config.supervision.restart_limit_burst as usize).then_some(interval)
+    }
+    
+    /// Snapshot for status queries, with memory and CPU use while a process runs
+    fn status(&self, label: &str) -> JobStatus {
+        let supervised_sec = self.start_time.map(|t| t.elapsed().as_secs());
+        let start_ticks = self.pid.and_then(|pid| self.current_start_ticks(pid));
+        let uptime_sec = start_ticks
+            .and_then(|ticks| usage::running_for(Path::new("/proc"), ticks))
+            .map(|uptime| uptime.as_secs())
+            .or(supervised_sec);
+        let (rss_bytes, cpu_percent) = match self.pid {
+            Some(pid) => (usage::read_rss_bytes(Path::new("/proc"), pid), self.cpu_percent(pid)),
+            None => (None, None),
+        };
+        
+        JobStatus {
+            label: label.to_string(),
+            state: self.state.clone(),
+            pid: self.pid,
+            restart_count: self.restart_count,
+            uptime_sec,
+            supervised_sec,
+            started_after_boot_sec: start_ticks.map(|ticks| ticks / usage::ticks_per_sec()),
+            exit_code: self.last_exit_code,
+            exit_signal: self.last_exit_signal,
+            config: self.config.clone(),
+            needs_restart: self.needs_restart,
+            source_path: self.source_path.clone(),
+            rss_bytes,
+            cpu_percent,
+        }
+    }
+    
+    /// The start time in `/proc` of the process running as `pid`, if it is still the one
+    /// that was spawned (and not another process that reused the PID)
+    fn current_start_ticks(&self, pid: u32) -> Option<u64> {
+        let started = self.process_start_ticks?;
+        (usage::read_start_ticks(Path::new("/proc"), pid)? == started).then_some(started)
+    }
+    
+    /// CPU use since the previous query, or since the process started on the first one
+    fn cpu_percent(&self, pid: u32) -> Option<f64> {
+        let current = usage::read_cpu_sample(Path::new("/proc"), pid)?;
+        let mut last = self.cpu_sample.lock().unwrap_or_else(|e| e.into_inner());
+        let previous = match *last {
+            Some(sample) if sample.pid == pid => sample,
+            _ => CpuSample { pid, ticks: 0, taken_at: self.start_time? },
+        };
+        *last = Some(current);
+        
+        current.percent_since(&previous, usage::ticks_per_sec())
+    }
+}
+
+/// What `reload_job` did with a job's config file
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReloadOutcome {
+    /// The file matches the loaded config
+    Unchanged,
+    /// The new config is in effect
+    Applied,
+    /// The job was restarted with the new config
+    Restarted,
+    /// The new config is stored but the running process still uses the old one
+    NeedsRestart,
+}
+
+impl std::fmt::Display for ReloadOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReloadOutcome::Unchanged => write!(f, "unchanged"),
+            ReloadOutcome::Applied => write!(f, "reloaded"),
+            ReloadOutcome::Restarted => write!(f, "reloaded and restarted"),
+            ReloadOutcome::NeedsRestart => write!(f, "reloaded, restart needed to apply"),
+        }
+    }
+}
+
+/// What a rescan of the config directory did, counted per job
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RescanSummary {
+    pub loaded: usize,
+    pub reloaded: usize,
+    pub unchanged: usize,
+    pub removed: usize,
+    /// Files that failed to parse, and jobs that failed to load, reload or unload
+    pub failed: usize,
+}
+
+impl std::fmt::Display for RescanSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} loaded, {} reloaded, {} unchanged, {} removed, {} failed",
+            self.loaded, self.reloaded, self.unchanged, self.removed, self.failed
+        )
+    }
+}
+
+/// Daemon-wide counts for dashboards, without the status of every job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DaemonSummary {
+    pub total: usize,
+    pub running: usize,
+    pub stopped: usize,
+    pub failed: usize,
+    pub backoff: usize,
+    /// Jobs starting, stopping, restarting, waiting, blocked, skipped or completed
+    pub other: usize,
+    /// Supervised restarts of all loaded jobs since they were loaded, counted across resets of `restart_count`
+    pub restarts: u64,
+    /// When the daemon started
+    pub started_at: SystemTime,
+    /// Whole seconds the daemon has been running
+    pub uptime_sec: u64,
+}
+
+/// A supervised restart and the exit that caused it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RestartRecord {
+    /// When the restart was scheduled
+    pub at: SystemTime,
+    /// Exit code of the process, `None` if it never spawned or was killed by a signal
+    pub exit_code: Option<i32>,
+    pub signal: Option<i32>,
 }
 
 pub struct JobManager {
     jobs: Arc<RwLock<HashMap<String, JobInstance>>>,
     event_dispatcher: EventDispatcher,
-    spawner: ProcessSpawner,
-    restart_tx: mpsc::Sender<RestartRequest>,
+    spawner: Arc<dyn Spawner>,
+    supervisor: Arc<JobSupervisor>,
+    max_jobs: Arc<AtomicUsize>,
+    /// Shared by all clones, so the background tasks started with the manager see settings
+    /// made after it was created
+    settings: Arc<std::sync::RwLock<ManagerSettings>>,
+    /// Held while config files are applied, so rescans don't interleave
+    reload_lock: Arc<Mutex<()>>,
+    started_at: SystemTime,
+    started: Instant,
+}
+
+/// What the daemon configures after creating the manager
+#[derive(Debug)]
+struct ManagerSettings {
+    auto_start: bool,
+    state_dir: Option<PathBuf>,
+    /// Where notify sockets go; the system temp directory if unset
+    runtime_dir: Option<PathBuf>,
+    config_dir: Option<PathBuf>,
+    /// Whether config files are also read from subdirectories of `config_dir`
+    recursive_config: bool,
+    /// Boot target; with `recursive_config`, jobs in another target's subdirectory never
+    /// start automatically
+    target: Option<String>,
+}
+
+impl Default for ManagerSettings {
+    fn default() -> Self {
+        Self {
+            auto_start: true,
+            state_dir: None,
+            runtime_dir: None,
+            config_dir: None,
+            recursive_config: false,
+            target: None,
+        }
+    }
+}
+
+/// Capacities of the manager's internal channels
+#[derive(Debug, Clone, Copy)]
+pub struct ChannelOptions {
+    /// Job events waiting for the receiver returned by `JobManager::new`
+    pub event_capacity: usize,
+    /// Process exits and due restarts waiting to be handled
+    pub restart_capacity: usize,
+    /// What sending an event does while `event_capacity` events are waiting
+    pub event_overflow: EventOverflow,
+}
+
+impl Default for ChannelOptions {
+    fn default() -> Self {
+        Self {
+            event_capacity: 100,
+            restart_capacity: 50,
+            event_overflow: EventOverflow::Block,
+        }
+    }
 }
 
 impl JobManager {
     /// Create a new JobManager
     pub async fn new() -> Result<(Self, mpsc::Receiver<JobEvent>)> {
-        let (event_tx, event_rx) = mpsc::channel(100);
-        let (restart_tx, restart_rx) = mpsc::channel(50);
+        Self::with_channels(ChannelOptions::default()).await
+    }
+    
+    /// Create a new JobManager with the given channel capacities
+    pub async fn with_channels(options: ChannelOptions) -> Result<(Self, mpsc::Receiver<JobEvent>)> {
+        Self::with_spawner(options, |event_dispatcher, exit_tx| {
+            Arc::new(ProcessSpawner::new(event_dispatcher, exit_tx))
+        }).await
+    }
+    
+    /// Create a new JobManager whose processes are started by the spawner `make_spawner`
+    /// builds, e.g. a `MockSpawner` in tests
+    ///
+    /// The spawner must report exits on the sender it is given.
+    pub async fn with_spawner<F>(options: ChannelOptions, make_spawner: F) -> Result<(Self, mpsc::Receiver<JobEvent>)>
+    where
+        F: FnOnce(EventDispatcher, mpsc::Sender<ProcessExit>) -> Arc<dyn Spawner>,
+    {
+        let (event_tx, event_rx) = mpsc::channel(options.event_capacity.max(1));
+        let (exit_tx, exit_rx) = mpsc::channel(options.restart_capacity.max(1));
         
-        let event_dispatcher = EventDispatcher::new(event_tx);
-        let spawner = ProcessSpawner::new(event_dispatcher.clone());
+        let event_dispatcher = EventDispatcher::with_overflow(event_tx, options.event_capacity, options.event_overflow);
+        let spawner = make_spawner(event_dispatcher.clone(), exit_tx);
         
         let manager = Self {
             jobs: Arc::new(RwLock::new(HashMap::new())),
             event_dispatcher: event_dispatcher.clone(),
             spawner,
-            restart_tx,
+            supervisor: Arc::new(JobSupervisor::new()),
+            max_jobs: Arc::new(AtomicUsize::new(DEFAULT_MAX_JOBS)),
+            settings: Arc::default(),
+            reload_lock: Arc::new(Mutex::new(())),
+            started_at: SystemTime::now(),
+            started: Instant::now(),
         };
         
         // Start background tasks
-        manager.start_background_tasks(restart_rx).await;
+        manager.start_background_tasks(exit_rx, options.restart_capacity.max(1)).await;
         
         Ok((manager, event_rx))
     }
     
-    /// Start background tasks for restart handling
-    async fn start_background_tasks(&self, mut restart_rx: mpsc::Receiver<RestartRequest>) {
-        let jobs = Arc::clone(&self.jobs);
-        let event_dispatcher = self.event_dispatcher.clone();
+    /// Start background tasks for process exits, restart handling, and hooks
+    async fn start_background_tasks(&self, mut exit_rx: mpsc::Receiver<ProcessExit>, restart_capacity: usize) {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            while let Some(exit) = exit_rx.recv().await {
+                let label = exit.label.clone();
+                if let Err(e) = manager.handle_process_exit(
+                    exit.label,
+                    exit.pid,
+                    exit.exit_code,
+                    exit.signal,
+                ).await {
+                    error!("Failed to handle exit of job '{}': {}", label, e);
+                }
+            }
+        });
         
+        // The supervisor hands back jobs whose backoff has elapsed
+        let (ready_tx, mut ready_rx) = mpsc::channel(restart_capacity);
+        Arc::clone(&self.supervisor).start_restart_processor(ready_tx);
+        
+        let manager = self.clone();
         tokio::spawn(async move {
-            while let Some(request) = restart_rx.recv().await {
-                handle_restart_request(
-                    request,
-                    Arc::clone(&jobs),
-                    event_dispatcher.clone()
-                ).await;
+            while let Some(label) = ready_rx.recv().await {
+                manager.restart_after_backoff(&label).await;
             }
         });
+        
+        tokio::spawn(hooks::run_hooks(self.clone(), self.subscribe()));
+    }
+    
+    /// Start a job whose restart backoff has elapsed, unless it was stopped in the meantime
+    async fn restart_after_backoff(&self, label: &str) {
+        {
+            let mut jobs = self.jobs.write().await;
+            match jobs.get_mut(label) {
+                Some(instance) if instance.state == JobState::Backoff => {
+                    instance.backoff_until = None;
+                }
+                _ => {
+                    debug!("Job '{}' left backoff, skipping restart", label);
+                    return;
+                }
+            }
+        }
+        
+        let _ = self.event_dispatcher.send(JobEvent::JobReadyForRestart(label.to_string())).await;
+        
+        if let Err(e) = self.start_job(label).await {
+            error!("Failed to restart job '{}': {}", label, e);
+        }
+    }
+    
+    /// Enable or disable auto-starting keep_alive jobs when they are loaded
+    pub fn set_auto_start(&mut self, enabled: bool) {
+        self.settings_mut().auto_start = enabled;
+    }
+    
+    /// Directory for persistent state such as the last run of scheduled jobs
+    pub fn set_state_dir(&mut self, dir: PathBuf) {
+        self.settings_mut().state_dir = Some(dir);
+    }
+    
+    /// Bind notify sockets under this directory (the daemon's `--runtime-dir`)
+    pub fn set_runtime_dir(&mut self, dir: PathBuf) {
+        self.settings_mut().runtime_dir = Some(dir);
+    }
+    
+    /// Directory `reload_all` rescans
+    pub fn set_config_dir(&mut self, dir: PathBuf) {
+        self.settings_mut().config_dir = Some(dir);
+    }
+    
+    pub fn config_dir(&self) -> Option<PathBuf> {
+        self.settings().config_dir.clone()
+    }
+    
+    /// Also read config files from subdirectories of the config directory
+    ///
+    /// The first subdirectory below the config directory names the target its jobs belong
+    /// to; jobs directly in the config directory belong to every target.
+    pub fn set_recursive_config(&mut self, recursive: bool) {
+        self.settings_mut().recursive_config = recursive;
+    }
+    
+    pub fn recursive_config(&self) -> bool {
+        self.settings().recursive_config
+    }
+    
+    /// Boot target, whose subdirectory's jobs are the only ones of a recursive config
+    /// directory that start automatically
+    pub fn set_target(&mut self, target: String) {
+        self.settings_mut().target = Some(target);
+    }
+    
+    fn settings(&self) -> std::sync::RwLockReadGuard<'_, ManagerSettings> {
+        self.settings.read().unwrap()
+    }
+    
+    fn settings_mut(&self) -> std::sync::RwLockWriteGuard<'_, ManagerSettings> {
+        self.settings.write().unwrap()
+    }
+    
+    /// Whether a job loaded from `source_path` may start automatically under the boot target
+    fn in_target(&self, source_path: Option<&Path>) -> bool {
+        let settings = self.settings();
+        if !settings.recursive_config {
+            return true;
+        }
+        
+        match (settings.config_dir.as_deref(), source_path, settings.target.as_deref()) {
+            (Some(config_dir), Some(path), Some(target)) => {
+                directory_target(config_dir, path).map_or(true, |name| name == target)
+            }
+            _ => true,
+        }
+    }
+    
+    /// Set the maximum number of loaded jobs (shared by all clones)
+    pub fn set_max_jobs(&self, max_jobs: usize) {
+        self.max_jobs.store(max_jobs, Ordering::Relaxed);
+    }
+    
+    /// Maximum number of loaded jobs
+    pub fn max_jobs(&self) -> usize {
+        self.max_jobs.load(Ordering::Relaxed)
+    }
+    
+    /// Receive a copy of every job event (see `EventDispatcher::subscribe`)
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<JobEvent> {
+        self.event_dispatcher.subscribe()
+    }
+    
+    /// Jobs counted by state, total restarts and the daemon's uptime
+    pub async fn summary(&self) -> DaemonSummary {
+        let jobs = self.jobs.read().await;
+        let mut summary = DaemonSummary {
+            total: jobs.len(),
+            running: 0,
+            stopped: 0,
+            failed: 0,
+            backoff: 0,
+            other: 0,
+            restarts: 0,
+            started_at: self.started_at,
+            uptime_sec: self.started.elapsed().as_secs(),
+        };
+        
+        for instance in jobs.values() {
+            match instance.state {
+                JobState::Running => summary.running += 1,
+                JobState::Stopped => summary.stopped += 1,
+                JobState::Failed(_) => summary.failed += 1,
+                JobState::Backoff => summary.backoff += 1,
+                _ => summary.other += 1,
+            }
+            summary.restarts += instance.total_restarts;
+        }
+        
+        summary
+    }
+    
+    /// Job events dropped because the event channel was full (`EventOverflow::DropOldest`)
+    pub fn dropped_events(&self) -> u64 {
+        self.event_dispatcher.dropped()
     }
     
     /// Load a job configuration
-    #[instrument(skip(self), fields(job = %config.label))]
     pub async fn load_job(&self, config: JobConfig) -> Result<()> {
+        self.load_job_from(config, None).await
+    }
+    
+    /// Load a job configuration read from `source_path`, so it can be reloaded later
+    #[instrument(skip(self), fields(job = %config.label))]
+    pub async fn load_job_from(&self, config: JobConfig, source_path: Option<PathBuf>) -> Result<()> {
         let label = config.label.clone();
         
         debug!("Loading job configuration");
@@ -93,17 +535,45 @@ impl JobManager {
             return Err(NusaError::JobExists(label));
         }
         
+        let max_jobs = self.max_jobs();
+        if jobs.len() >= max_jobs {
+            return Err(NusaError::JobLimitExceeded(max_jobs));
+        }
+        
+        // Reject the job if it would close a dependency cycle
+        let configs = jobs.values().map(|instance| &instance.config).chain(std::iter::once(&config));
+        if let Some(cycle) = dependency::find_cycle(configs) {
+            return Err(dependency::cycle_error(&cycle).into());
+        }
+        
+        // Jobs of another target's subdirectory only start when asked to, templates never
+        let auto_start = self.settings().auto_start;
+        let auto_start = auto_start && !config.is_template() && self.in_target(source_path.as_deref());
+        
         // Create job instance
         let instance = JobInstance {
             config: config.clone(),
             state: JobState::Stopped,
             pid: None,
             start_time: None,
+            process_start_ticks: None,
             restart_count: 0,
+            total_restarts: 0,
+            restart_times: VecDeque::new(),
+            restart_history: VecDeque::new(),
             last_exit_code: None,
             last_exit_signal: None,
             backoff_until: None,
             process_handle: None,
+            ready: false,
+            rapid_clean_exits: 0,
+            sent_signal: None,
+            source_path,
+            needs_restart: false,
+            sockets: Vec::new(),
+            activation_handle: None,
+            start_delay_handle: None,
+            cpu_sample: std::sync::Mutex::new(None),
         };
         
         jobs.insert(label.clone(), instance);
@@ -113,44 +583,487 @@ impl JobManager {
         
         info!("Job loaded successfully: {}", label);
         
-        // Start job if keep_alive is true (similar to RunAtLoad)
-        if config.supervision.keep_alive {
+        // Socket-activated jobs wait for their first connection instead
+        if auto_start && !config.sockets.is_empty() {
+            drop(jobs);
+            if let Err(e) = self.listen_sockets(&label).await {
+                error!("Failed to listen on sockets of job '{}': {}", label, e);
+            }
+        } else if auto_start && config.supervision.keep_alive && config.supervision.start_delay_sec > 0 {
+            self.delay_start(&mut jobs, &label, config.supervision.start_delay());
+        } else if auto_start && config.supervision.keep_alive {
+            // Start job if keep_alive is true (similar to RunAtLoad). The job is
+            // claimed under this lock so a concurrent start_job sees it Starting.
             debug!("Auto-starting job due to keep_alive=true");
-            // We'll start it asynchronously to avoid holding the lock
-            let self_clone = self.clone();
-            let label_clone = label.clone();
-            tokio::spawn(async move {
-                if let Err(e) = self_clone.start_job(&label_clone).await {
-                    error!("Failed to auto-start job '{}': {}", label_clone, e);
+            match self.claim_start(&mut jobs, &label).await {
+                Ok(StartClaim::Claimed { restarting }) => {
+                    // Spawn asynchronously to avoid holding the lock
+                    let self_clone = self.clone();
+                    let label_clone = label.clone();
+                    tokio::spawn(async move {
+                        // Skip if the job was stopped or unloaded in the meantime
+                        let still_claimed = self_clone.jobs.read().await
+                            .get(&label_clone)
+                            .map_or(false, |instance| instance.state == JobState::Starting && instance.pid.is_none());
+                        if !still_claimed {
+                            return;
+                        }
+                        
+                        if let Err(e) = self_clone.launch_job(&label_clone, restarting).await {
+                            error!("Failed to auto-start job '{}': {}", label_clone, e);
+                        }
+                    });
                 }
-            });
+                Ok(StartClaim::Active(_)) => {}
+                Err(e) => error!("Failed to auto-start job '{}': {}", label, e),
+            }
+        }
+        
+        if let Some(schedule) = config.schedule.clone() {
+            if auto_start {
+                self.spawn_scheduler(label, schedule);
+            }
         }
         
         Ok(())
     }
     
+    /// Start every loaded job that is not scheduled, dependencies first
+    ///
+    /// Jobs that are already running count as started. Returns each job's outcome.
+    pub async fn start_all(&self) -> Vec<(String, Result<()>)> {
+        let (order, configs) = self.job_order().await;
+        
+        info!("Starting all jobs");
+        
+        let mut results = Vec::new();
+        for label in order {
+            if configs[&label].schedule.is_some() || configs[&label].is_template() {
+                continue;
+            }
+            
+            let result = self.start_job_after_delay(&label).await;
+            if let Err(e) = &result {
+                error!("Failed to start job '{}': {}", label, e);
+            }
+            results.push((label, result));
+        }
+        
+        results
+    }
+    
+    /// Stop every running job, dependents before their dependencies
+    ///
+    /// Each job gets its own stop_timeout. Returns each stopped job's outcome.
+    pub async fn stop_all(&self) -> Vec<(String, Result<()>)> {
+        let (order, _) = self.job_order().await;
+        
+        info!("Stopping all jobs");
+        
+        let mut results = Vec::new();
+        for label in order.into_iter().rev() {
+            let running = self.get_job_status(&label).await
+                .map_or(false, |status| status.pid.is_some() || status.state == JobState::Waiting);
+            if !running {
+                continue;
+            }
+            
+            let result = self.stop_job(&label).await;
+            if let Err(e) = &result {
+                error!("Failed to stop job '{}': {}", label, e);
+            }
+            results.push((label, result));
+        }
+        
+        results
+    }
+    
+    /// Start every keep_alive job, schedule every scheduled job and open the sockets
+    /// of socket-activated jobs, dependencies first
+    pub async fn start_keep_alive(&self) -> Result<()> {
+        let (order, configs) = self.start_plan().await?;
+        
+        info!("Starting jobs in dependency order");
+        
+        for label in order {
+            let config = &configs[&label];
+            
+            if !config.sockets.is_empty() {
+                if let Err(e) = self.listen_sockets(&label).await {
+                    error!("Failed to listen on sockets of job '{}': {}", label, e);
+                }
+            } else if config.supervision.keep_alive {
+                if let Err(e) = self.start_job_after_delay(&label).await {
+                    error!("Failed to start job '{}': {}", label, e);
+                }
+            }
+            
+            if let Some(schedule) = config.schedule.clone() {
+                self.spawn_scheduler(label, schedule);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Start the enabled jobs and everything they require, in dependency order
+    ///
+    /// Enabled scheduled jobs get their scheduler and socket-activated jobs their
+    /// sockets instead of being started directly.
+    pub async fn start_enabled(&self, enabled: &[String]) -> Result<()> {
+        // Enabled instances of a template, like `worker@1`, are created first
+        for label in enabled {
+            if let Err(e) = self.ensure_instance(label).await {
+                error!("Failed to instantiate enabled job '{}': {}", label, e);
+            }
+        }
+        
+        let (order, configs) = self.start_plan().await?;
+        
+        // Pull in the transitive `requires` of every enabled job
+        let mut wanted: HashSet<String> = HashSet::new();
+        let mut pending: Vec<String> = enabled.to_vec();
+        while let Some(label) = pending.pop() {
+            let Some(config) = configs.get(&label) else {
+                warn!("Enabled job '{}' is not loaded", label);
+                continue;
+            };
+            if wanted.insert(label) {
+                pending.extend(config.requires.iter().cloned());
+            }
+        }
+        
+        info!("Starting {} enabled job(s) in dependency order", wanted.len());
+        
+        for label in order {
+            if !wanted.contains(&label) {
+                continue;
+            }
+            
+            let config = &configs[&label];
+            match config.schedule.clone() {
+                Some(schedule) => self.spawn_scheduler(label, schedule),
+                None if !config.sockets.is_empty() => {
+                    if let Err(e) = self.listen_sockets(&label).await {
+                        error!("Failed to listen on sockets of job '{}': {}", label, e);
+                    }
+                }
+                None => {
+                    if let Err(e) = self.start_job_after_delay(&label).await {
+                        error!("Failed to start job '{}': {}", label, e);
+                    }
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Dependency order of the loaded jobs of the boot target, with a snapshot of all configs
+    async fn start_plan(&self) -> Result<(Vec<String>, HashMap<String, JobConfig>)> {
+        let jobs = self.jobs.read().await;
+        let mut order = dependency::start_order(jobs.values().map(|instance| &instance.config))?;
+        order.retain(|label| {
+            let in_target = self.in_target(jobs[label].source_path.as_deref());
+            if !in_target {
+                debug!("Not starting job '{}': it belongs to another target", label);
+            }
+            in_target && !jobs[label].config.is_template()
+        });
+        let configs: HashMap<String, JobConfig> = jobs.iter()
+            .map(|(label, instance)| (label.clone(), instance.config.clone()))
+            .collect();
+        Ok((order, configs))
+    }
+    
+    /// Like `start_plan`, but falls back to map order if the dependencies cannot be ordered
+    async fn job_order(&self) -> (Vec<String>, HashMap<String, JobConfig>) {
+        let jobs = self.jobs.read().await;
+        let order = dependency::start_order(jobs.values().map(|instance| &instance.config))
+            .unwrap_or_else(|_| jobs.keys().cloned().collect());
+        let configs: HashMap<String, JobConfig> = jobs.iter()
+            .map(|(label, instance)| (label.clone(), instance.config.clone()))
+            .collect();
+        (order, configs)
+    }
+    
+    /// Stop every running job, dependents before their dependencies
+    ///
+    /// Each job gets `grace`, or its own `stop_timeout_sec` if none is given, to
+    /// exit after its stop signal before it is killed.
+    pub async fn shutdown_all(&self, grace: Option<Duration>) {
+        let (order, _) = self.job_order().await;
+        
+        info!("Stopping all jobs");
+        
+        for label in order.into_iter().rev() {
+            let running = self.get_job_status(&label).await
+                .map_or(false, |status| status.pid.is_some() || status.state == JobState::Waiting);
+            
+            if running {
+                let stopped = match grace {
+                    Some(grace) => self.stop_job_with_timeout(&label, grace).await,
+                    None => self.stop_job(&label).await,
+                };
+                if let Err(e) = stopped {
+                    error!("Failed to stop job '{}' during shutdown: {}", label, e);
+                }
+            }
+        }
+        
+        info!("All jobs stopped");
+    }
+    
+    /// Bind a job's sockets and start it when one of them becomes readable
+    ///
+    /// Does nothing if the job has no sockets or they are already open. Jobs blocked on
+    /// this one start once it listens, without starting it.
+    pub async fn listen_sockets(&self, label: &str) -> Result<()> {
+        let mut jobs = self.jobs.write().await;
+        let instance = jobs.get_mut(label)
+            .ok_or_else(|| NusaError::JobNotFound(label.to_string()))?;
+        
+        if instance.config.sockets.is_empty() || !instance.sockets.is_empty() {
+            return Ok(());
+        }
+        
+        let bound = instance.config.sockets.iter()
+            .map(|socket| ActivationSocket::bind(socket).map(Arc::new))
+            .collect::<Result<Vec<_>>>()?;
+        
+        for socket in &bound {
+            info!("Listening on {} socket {} for job '{}'", socket.config().socket_type, socket.config().listen, label);
+        }
+        
+        instance.sockets = bound.clone();
+        instance.activation_handle = Some(self.spawn_activation(label.to_string(), bound));
+        drop(jobs);
+        
+        self.start_unblocked(label).await;
+        Ok(())
+    }
+    
+    fn spawn_activation(&self, label: String, bound: Vec<Arc<ActivationSocket>>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(sockets::run_activation(self.clone(), label, bound))
+    }
+    
+    /// Stop activating a job and close its sockets; a running process keeps its copies
+    async fn close_sockets(&self, label: &str) {
+        let handle = match self.jobs.write().await.get_mut(label) {
+            Some(instance) => {
+                instance.sockets.clear();
+                instance.activation_handle.take()
+            }
+            None => None,
+        };
+        
+        // Wait for the task to drop its references, so the sockets are closed on return
+        if let Some(handle) = handle {
+            handle.abort();
+            let _ = handle.await;
+        }
+    }
+    
+    /// Status of every open activation socket, sorted by job
+    pub async fn list_sockets(&self) -> Vec<SocketStatus> {
+        let jobs = self.jobs.read().await;
+        let mut sockets: Vec<SocketStatus> = jobs.iter()
+            .flat_map(|(label, instance)| {
+                instance.sockets.iter().map(move |socket| SocketStatus::new(label, &instance.state, socket))
+            })
+            .collect();
+        sockets.sort_by(|a, b| a.job.cmp(&b.job));
+        sockets
+    }
+    
+    fn spawn_scheduler(&self, label: String, schedule: crate::job::config::ScheduleConfig) {
+        debug!("Scheduling job '{}'", label);
+        tokio::spawn(scheduler::run_schedule(
+            self.clone(),
+            label,
+            schedule,
+            self.settings().state_dir.clone(),
+        ));
+    }
+    
+    /// Start a job once its `start_delay_sec` has passed, as automatic starts do
+    ///
+    /// With a delay this returns at once, leaving the job Waiting; stopping or
+    /// unloading it cancels the start.
+    pub async fn start_job_after_delay(&self, label: &str) -> Result<()> {
+        let mut jobs = self.jobs.write().await;
+        let instance = jobs.get(label)
+            .ok_or_else(|| NusaError::JobNotFound(label.to_string()))?;
+        
+        let delay = instance.config.supervision.start_delay();
+        if delay.is_zero() {
+            drop(jobs);
+            return self.start_job(label).await;
+        }
+        
+        if !matches!(instance.state, JobState::Running | JobState::Starting | JobState::Waiting) {
+            self.delay_start(&mut jobs, label, delay);
+        }
+        
+        Ok(())
+    }
+    
+    /// Mark a job Waiting and start it after `delay`, unless it is stopped first
+    fn delay_start(&self, jobs: &mut HashMap<String, JobInstance>, label: &str, delay: Duration) {
+        let Some(instance) = jobs.get_mut(label) else {
+            return;
+        };
+        
+        info!("Starting job '{}' in {}s", label, delay.as_secs());
+        instance.state = JobState::Waiting;
+        
+        let self_clone = self.clone();
+        let label = label.to_string();
+        let handle = tokio::spawn(async move {
+            time::sleep(delay).await;
+            
+            // Claimed together with taking the handle, so a stop either aborts the wait or sees the start
+            let mut jobs = self_clone.jobs.write().await;
+            match jobs.get_mut(&label) {
+                Some(instance) if instance.state == JobState::Waiting => instance.start_delay_handle = None,
+                _ => return,
+            }
+            let restarting = match self_clone.claim_start(&mut jobs, &label).await {
+                Ok(StartClaim::Claimed { restarting }) => restarting,
+                Ok(StartClaim::Active(_)) => return,
+                Err(e) => {
+                    error!("Failed to start job '{}' after its delay: {}", label, e);
+                    return;
+                }
+            };
+            drop(jobs);
+            
+            if let Err(e) = self_clone.launch_job(&label, restarting).await {
+                error!("Failed to start job '{}' after its delay: {}", label, e);
+            }
+        });
+        
+        if let Some(previous) = instance.start_delay_handle.replace(handle) {
+            previous.abort();
+        }
+    }
+    
     /// Start a job
-    #[instrument(skip(self), fields(job = %label))]
     pub async fn start_job(&self, label: &str) -> Result<()> {
+        self.start_job_detailed(label).await.map(|_| ())
+    }
+    
+    /// Start a job, returning the PID and start time of its process
+    ///
+    /// Returns the existing process if the job is already running, and `None`
+    /// if nothing was spawned (job still starting, waiting out a backoff, or
+    /// skipped because a start condition does not hold).
+    #[instrument(skip(self), fields(job = %label))]
+    pub async fn start_job_detailed(&self, label: &str) -> Result<Option<StartedJob>> {
         debug!("Starting job");
         
+        self.ensure_instance(label).await?;
+        
         let mut jobs = self.jobs.write().await;
+        let restarting = match self.claim_start(&mut jobs, label).await? {
+            StartClaim::Claimed { restarting } => restarting,
+            StartClaim::Active(started) => return Ok(started),
+        };
+        
+        // Drop write lock to spawn process; the Starting state keeps other callers out
+        drop(jobs);
+        
+        self.launch_job(label, restarting).await
+    }
+    
+    /// Load instance `instance` of the template `template_label` (e.g. `worker@`) as
+    /// `worker@<instance>`, returning its label
+    pub async fn instantiate(&self, template_label: &str, instance: &str) -> Result<String> {
+        let template = self.jobs.read().await
+            .get(template_label)
+            .map(|job| job.config.clone())
+            .ok_or_else(|| NusaError::JobNotFound(template_label.to_string()))?;
+        if !template.is_template() {
+            return Err(ConfigError::Validation(format!("Job '{}' is not a template", template_label)).into());
+        }
+        
+        let config = template.instantiate(instance);
+        config.validate().await?;
+        
+        let label = config.label.clone();
+        self.load_job(config).await?;
+        info!("Instantiated job '{}' from template '{}'", label, template_label);
+        Ok(label)
+    }
+    
+    /// Instantiate `label` from its loaded template if it names an instance that is not loaded yet
+    async fn ensure_instance(&self, label: &str) -> Result<()> {
+        let Some((template, instance)) = config::split_instance(label) else {
+            return Ok(());
+        };
+        {
+            let jobs = self.jobs.read().await;
+            if jobs.contains_key(label) || !jobs.contains_key(template) {
+                return Ok(());
+            }
+        }
+        
+        // A concurrent start may have instantiated it first
+        match self.instantiate(template, instance).await {
+            Ok(_) | Err(NusaError::JobExists(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+    
+    /// Check that a job may start and, if so, mark it Starting under the caller's lock
+    async fn claim_start(&self, jobs: &mut HashMap<String, JobInstance>, label: &str) -> Result<StartClaim> {
+        // A job cannot start until all of its required jobs are running, or listening
+        // if socket-activated; it is started automatically once they are (see
+        // `start_unblocked`)
+        let requires = jobs.get(label)
+            .ok_or_else(|| NusaError::JobNotFound(label.to_string()))?
+            .config.requires.clone();
+        let blocked_by = requires.iter().find_map(|dep| match jobs.get(dep) {
+            None => Some(format!("required job '{}' is not loaded", dep)),
+            Some(d) if d.satisfies_requires() => None,
+            Some(d) => match &d.state {
+                JobState::Failed(_) => Some(format!("required job '{}' failed", dep)),
+                state => Some(format!("required job '{}' is {}", dep, state)),
+            },
+        });
         
         let instance = jobs.get_mut(label)
             .ok_or_else(|| NusaError::JobNotFound(label.to_string()))?;
         
+        if instance.config.is_template() {
+            return Err(NusaError::Process(ProcessError::Other(format!(
+                "Job '{}' is a template; start an instance such as '{}1'",
+                label, label
+            ))));
+        }
+        
+        if let Some(reason) = blocked_by {
+            warn!("Job is blocked: {}", reason);
+            instance.state = JobState::Blocked(reason.clone());
+            self.event_dispatcher.send(JobEvent::JobBlocked(label.to_string(), reason.clone())).await?;
+            return Err(NusaError::Blocked(label.to_string(), reason));
+        }
+        
         // Check current state
         match &instance.state {
             JobState::Running | JobState::Starting => {
                 warn!("Job is already running or starting");
-                return Ok(());
+                let started = instance.pid
+                    .zip(instance.start_time)
+                    .map(|(pid, start_time)| StartedJob { pid, started_at: SystemTime::now() - start_time.elapsed() });
+                return Ok(StartClaim::Active(started));
             }
             JobState::Backoff => {
                 if let Some(until) = instance.backoff_until {
                     if Instant::now() < until {
                         let wait_secs = (until - Instant::now()).as_secs();
                         warn!("Job in backoff, waiting {} seconds", wait_secs);
-                        return Ok(());
+                        return Ok(StartClaim::Active(None));
                     }
                 }
                 // Backoff expired, proceed
@@ -158,52 +1071,366 @@ impl JobManager {
             _ => {} // Other states are fine
         }
         
-        // Update state
-        instance.state = JobState::Starting;
-        instance.backoff_until = None;
+        // Supervised restarts keep counting toward max_restarts
+        let restarting = instance.state == JobState::Backoff;
+        
+        // Update state
+        instance.state = JobState::Starting;
+        instance.backoff_until = None;
+        
+        Ok(StartClaim::Claimed { restarting })
+    }
+    
+    /// Spawn the process of a job already claimed by `claim_start`
+    ///
+    /// Returns `None` if the job was skipped because a start condition does not hold.
+    async fn launch_job(&self, label: &str, restarting: bool) -> Result<Option<StartedJob>> {
+        let job_conditions = self.jobs.read().await
+            .get(label)
+            .map(|instance| instance.config.conditions.clone())
+            .ok_or_else(|| NusaError::JobNotFound(label.to_string()))?;
+        if let Some(reason) = conditions::unmet_condition(label, &job_conditions).await {
+            info!("Skipping job '{}': {}", label, reason);
+            if let Some(instance) = self.jobs.write().await.get_mut(label) {
+                instance.state = JobState::Skipped(reason.clone());
+            }
+            self.event_dispatcher.send(JobEvent::JobSkipped(label.to_string(), reason)).await?;
+            return Ok(None);
+        }
+        
+        // A socket-activated job started by hand still gets its sockets
+        if let Err(e) = self.listen_sockets(label).await {
+            let mut jobs = self.jobs.write().await;
+            if let Some(instance) = jobs.get_mut(label) {
+                instance.state = JobState::Failed(format!("Failed to start: {}", e));
+            }
+            return Err(e);
+        }
+        
+        let (mut config, sockets) = self.jobs.read().await
+            .get(label)
+            .map(|instance| (instance.config.clone(), instance.sockets.clone()))
+            .ok_or_else(|| NusaError::JobNotFound(label.to_string()))?;
+        let listen_fds: Vec<ListenFd> = sockets.iter()
+            .map(|socket| ListenFd {
+                fd: socket.as_raw_fd(),
+                name: socket.config().fd_name(label).to_string(),
+            })
+            .collect();
+        
+        let notify_ready = config.supervision.notify_ready;
+        let notify_socket = if notify_ready {
+            // A bad user or group fails the spawn itself, with the clearer error
+            let credentials = Credentials::resolve(&config.program).ok().flatten();
+            let runtime_dir = self.settings().runtime_dir.clone();
+            match NotifySocket::bind(runtime_dir.as_deref(), label, credentials.as_ref()) {
+                Ok(socket) => {
+                    config.environment.push(EnvironmentVar {
+                        key: "NOTIFY_SOCKET".to_string(),
+                        value: socket.path().display().to_string(),
+                    });
+                    Some(socket)
+                }
+                Err(e) => {
+                    if let Some(instance) = self.jobs.write().await.get_mut(label) {
+                        instance.state = JobState::Failed(format!("Failed to start: {}", e));
+                    }
+                    return Err(e);
+                }
+            }
+        } else {
+            None
+        };
+        
+        // Spawn process
+        match self.spawner.spawn(&config, &listen_fds).await {
+            Ok((pid, handle)) => {
+                // Re-acquire lock and update instance
+                let mut jobs = self.jobs.write().await;
+                let instance = match jobs.get_mut(label) {
+                    Some(instance) if instance.state == JobState::Starting => instance,
+                    instance => {
+                        // Unloaded or stopped while spawning: nothing would supervise the process
+                        let error = match instance {
+                            Some(_) => NusaError::Process(ProcessError::Other(format!(
+                                "Job '{}' was stopped while starting", label
+                            ))),
+                            None => NusaError::JobNotFound(label.to_string()),
+                        };
+                        drop(jobs);
+                        warn!("Killing process of job '{}' [PID: {}]: {}", label, pid, error);
+                        let _ = self.spawner.signal(pid, Signal::SIGKILL, config.supervision.kill_process_group);
+                        return Err(error);
+                    }
+                };
+                
+                // A notifying job stays Starting until it sends READY=1
+                instance.state = if notify_ready { JobState::Starting } else { JobState::Running };
+                instance.pid = Some(pid);
+                instance.sent_signal = None;
+                instance.start_time = Some(Instant::now());
+                instance.process_start_ticks = usage::read_start_ticks(Path::new("/proc"), pid);
+                instance.process_handle = Some(handle);
+                instance.ready = !notify_ready && config.health.is_none();
+                if !restarting {
+                    instance.restart_count = 0;
+                    instance.restart_times.clear();
+                }
+                instance.needs_restart = false;
+                
+                let started_at = SystemTime::now();
+                self.event_dispatcher.send(JobEvent::JobStarted(label.to_string(), pid, started_at)).await?;
+                
+                if let Some(timeout) = config.supervision.start_timeout().filter(|_| !instance.ready) {
+                    self.spawn_start_watchdog(label.to_string(), pid, timeout);
+                }
+                if let Some(max_runtime) = config.supervision.max_runtime() {
+                    self.spawn_runtime_watchdog(label.to_string(), pid, max_runtime);
+                }
+                
+                drop(jobs);
+                
+                // Health checks and dependents of a notifying job wait for its readiness
+                if let Some(socket) = notify_socket {
+                    self.spawn_notify(label.to_string(), pid, socket);
+                } else {
+                    if let Some(health) = config.health {
+                        self.spawn_health_checks(label.to_string(), pid, health);
+                    }
+                    self.start_unblocked(label).await;
+                }
+                
+                info!("Job started successfully [PID: {}]", pid);
+                Ok(Some(StartedJob { pid, started_at }))
+            }
+            Err(e) => {
+                let mut jobs = self.jobs.write().await;
+                let instance = match jobs.get_mut(label) {
+                    Some(instance) if instance.state == JobState::Starting => instance,
+                    Some(_) => return Err(e),
+                    None => return Err(NusaError::JobNotFound(label.to_string())),
+                };
+                
+                error!("Failed to start job: {}", e);
+                
+                // The program may still appear, e.g. while it is being deployed
+                if instance.config.supervision.retries_spawn_failure() {
+                    self.event_dispatcher.send(JobEvent::SpawnFailed(label.to_string(), e.to_string())).await?;
+                    instance.start_time = None;
+                    self.restart_or_fail(label, instance, None).await?;
+                    return Err(e);
+                }
+                
+                // Update state to failed
+                instance.state = JobState::Failed(format!("Failed to start: {}", e));
+                Err(e)
+            }
+        }
+    }
+    
+    fn spawn_notify(&self, label: String, pid: u32, socket: NotifySocket) {
+        tokio::spawn(notify::run_notify(self.clone(), label, pid, socket));
+    }
+    
+    fn spawn_health_checks(&self, label: String, pid: u32, health: HealthConfig) {
+        tokio::spawn(health::run_health_checks(self.clone(), label, pid, health));
+    }
+    
+    fn spawn_start_watchdog(&self, label: String, pid: u32, timeout: Duration) {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            time::sleep(timeout).await;
+            if let Err(e) = manager.fail_unready(&label, pid, timeout).await {
+                warn!("Failed to stop job '{}' after its start timeout: {}", label, e);
+            }
+        });
+    }
+    
+    /// Kill and fail a job whose process `pid` is still not ready after its start timeout
+    async fn fail_unready(&self, label: &str, pid: u32, timeout: Duration) -> Result<()> {
+        let unready = self.jobs.read().await
+            .get(label)
+            .map_or(false, |instance| instance.pid == Some(pid) && !instance.ready);
+        if !unready {
+            return Ok(());
+        }
+        
+        warn!("Job '{}' did not become ready within {:?}, killing it", label, timeout);
+        self.event_dispatcher.send(JobEvent::JobStartTimedOut(label.to_string(), timeout)).await?;
+        
+        self.kill_job(label).await?;
+        
+        let state = JobState::Failed(format!("Not ready within {}s of starting", timeout.as_secs()));
+        if let Some(instance) = self.jobs.write().await.get_mut(label) {
+            instance.state = state.clone();
+        }
+        self.event_dispatcher.send(JobEvent::JobFailed(label.to_string(), state)).await?;
+        
+        Ok(())
+    }
+    
+    fn spawn_runtime_watchdog(&self, label: String, pid: u32, max_runtime: Duration) {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            time::sleep(max_runtime).await;
+            if let Err(e) = manager.fail_overrunning(&label, pid, max_runtime).await {
+                warn!("Failed to stop job '{}' after its max runtime: {}", label, e);
+            }
+        });
+    }
+    
+    /// Kill and fail a job whose process `pid` is still running after `max_runtime`
+    async fn fail_overrunning(&self, label: &str, pid: u32, max_runtime: Duration) -> Result<()> {
+        let running = self.jobs.read().await
+            .get(label)
+            .map_or(false, |instance| instance.pid == Some(pid));
+        if !running {
+            return Ok(());
+        }
+        
+        warn!("Job '{}' exceeded its max runtime of {:?}, killing it", label, max_runtime);
+        self.kill_job(label).await?;
+        
+        let state = JobState::Failed(format!("Exceeded max runtime ({}s)", max_runtime.as_secs()));
+        if let Some(instance) = self.jobs.write().await.get_mut(label) {
+            instance.state = state.clone();
+        }
+        self.event_dispatcher.send(JobEvent::JobFailed(label.to_string(), state)).await?;
+        
+        Ok(())
+    }
+    
+    /// Record that process `pid` of a job became ready
+    ///
+    /// A notifying job moves from Starting to Running here, which starts its health
+    /// checks and the jobs blocked on it.
+    pub async fn mark_ready(&self, label: &str, pid: u32) {
+        let health = {
+            let mut jobs = self.jobs.write().await;
+            let instance = match jobs.get_mut(label) {
+                Some(instance) if instance.pid == Some(pid) && !instance.ready => instance,
+                _ => return,
+            };
+            
+            debug!("Job '{}' is ready", label);
+            instance.ready = true;
+            if instance.state != JobState::Starting {
+                return;
+            }
+            instance.state = JobState::Running;
+            instance.config.health.clone()
+        };
+        
+        info!("Job '{}' reported readiness [PID: {}]", label, pid);
+        if let Some(health) = health {
+            self.spawn_health_checks(label.to_string(), pid, health);
+        }
+        self.start_unblocked(label).await;
+    }
+    
+    /// Start the jobs blocked on `label` whose required jobs are now all running (or
+    /// listening, see `JobInstance::satisfies_requires`)
+    async fn start_unblocked(&self, label: &str) {
+        let unblocked: Vec<String> = {
+            let jobs = self.jobs.read().await;
+            jobs.iter()
+                .filter(|(_, instance)| {
+                    matches!(instance.state, JobState::Blocked(_))
+                        && instance.config.requires.iter().any(|dep| dep == label)
+                        && instance.config.requires.iter().all(|dep| {
+                            jobs.get(dep).map_or(false, JobInstance::satisfies_requires)
+                        })
+                })
+                .map(|(dependent, _)| dependent.clone())
+                .collect()
+        };
+        
+        for dependent in unblocked {
+            info!("Job '{}' is up, starting blocked job '{}'", label, dependent);
+            self.spawn_start(dependent);
+        }
+    }
+    
+    fn spawn_start(&self, label: String) {
+        let manager = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = manager.start_job(&label).await {
+                error!("Failed to start job '{}': {}", label, e);
+            }
+        });
+    }
+    
+    /// Stop a job, escalating to SIGKILL if it outlives its `stop_timeout_sec`
+    pub async fn stop_job(&self, label: &str) -> Result<()> {
+        let timeout = self.supervision(label).await?.stop_timeout();
+        self.stop_job_with_timeout(label, timeout).await
+    }
+    
+    /// Stop a job, waiting up to `timeout` after its stop signal before sending SIGKILL
+    pub async fn stop_job_with_timeout(&self, label: &str, timeout: Duration) -> Result<()> {
+        let signal = self.supervision(label).await?.stop_signal();
+        self.terminate_job(label, signal, timeout).await
+    }
+    
+    /// Stop a job immediately with SIGKILL
+    pub async fn kill_job(&self, label: &str) -> Result<()> {
+        let timeout = self.supervision(label).await?.stop_timeout();
+        self.terminate_job(label, Signal::SIGKILL, timeout).await
+    }
+    
+    /// Deliver a signal, given by name (`USR1`, `SIGHUP`) or number, to a running job
+    ///
+    /// Returns the PID it was sent to.
+    pub async fn send_signal(&self, label: &str, signal: &str) -> Result<u32> {
+        let signal = config::parse_signal(signal).ok_or_else(|| {
+            NusaError::Process(ProcessError::Other(format!("Unknown signal: {}", signal)))
+        })?;
+        
+        let mut jobs = self.jobs.write().await;
+        let instance = jobs.get_mut(label)
+            .ok_or_else(|| NusaError::JobNotFound(label.to_string()))?;
+        let pid = instance.pid.ok_or_else(|| {
+            NusaError::Process(ProcessError::Other(format!("Job '{}' is not running", label)))
+        })?;
         
-        // Drop write lock temporarily to spawn process
-        let config = instance.config.clone();
+        // Recorded before signalling, so the exit can't be handled before it is set
+        instance.sent_signal = Some(signal as i32);
+        let grace = instance.config.supervision.stop_timeout();
         drop(jobs);
         
-        // Spawn process
-        match self.spawner.spawn(&config).await {
-            Ok((pid, handle)) => {
-                // Re-acquire lock and update instance
-                let mut jobs = self.jobs.write().await;
-                let instance = jobs.get_mut(label).unwrap();
-                
-                instance.state = JobState::Running;
-                instance.pid = Some(pid);
-                instance.start_time = Some(Instant::now());
-                instance.process_handle = Some(handle);
-                instance.restart_count = 0;
-                
-                self.event_dispatcher.send(JobEvent::JobStarted(
-                    label.to_string(),
-                    pid,
-                    instance.start_time.unwrap()
-                )).await?;
-                
-                info!("Job started successfully [PID: {}]", pid);
-                Ok(())
-            }
-            Err(e) => {
-                // Update state to failed
-                let mut jobs = self.jobs.write().await;
-                let instance = jobs.get_mut(label).unwrap();
-                
-                instance.state = JobState::Failed(format!("Failed to start: {}", e));
-                
-                error!("Failed to start job: {}", e);
-                Err(e)
+        self.spawner.signal(pid, signal, false).map_err(|e| {
+            NusaError::Process(ProcessError::Other(format!(
+                "Failed to send {} to job '{}': {}", signal, label, e
+            )))
+        })?;
+        
+        info!("Sent {} to job '{}' (pid {})", signal, label, pid);
+        
+        // A process still running after its stop timeout survived the signal, so a later
+        // death by the same signal comes from elsewhere
+        let manager = self.clone();
+        let label = label.to_string();
+        tokio::spawn(async move {
+            time::sleep(grace).await;
+            if let Some(instance) = manager.jobs.write().await.get_mut(&label) {
+                if instance.pid == Some(pid) && instance.sent_signal == Some(signal as i32) {
+                    instance.sent_signal = None;
+                }
             }
-        }
+        });
+        
+        Ok(pid)
+    }
+    
+    async fn supervision(&self, label: &str) -> Result<SupervisionConfig> {
+        self.jobs.read().await
+            .get(label)
+            .map(|instance| instance.config.supervision.clone())
+            .ok_or_else(|| NusaError::JobNotFound(label.to_string()))
     }
     
-    /// Stop a job
     #[instrument(skip(self), fields(job = %label))]
-    pub async fn stop_job(&self, label: &str) -> Result<()> {
+    async fn terminate_job(&self, label: &str, signal: Signal, timeout: Duration) -> Result<()> {
         debug!("Stopping job");
         
         let mut jobs = self.jobs.write().await;
@@ -213,20 +1440,26 @@ impl JobManager {
         
         // Update state
         let previous_state = std::mem::replace(&mut instance.state, JobState::Stopping);
+        instance.sent_signal = Some(signal as i32);
         
         // Get PID and handle
         let pid = instance.pid;
         let handle = instance.process_handle.take();
+        let group = instance.config.supervision.kill_process_group;
+        
+        // Cancel a delayed start that has not claimed the job yet
+        if let Some(delayed) = instance.start_delay_handle.take() {
+            delayed.abort();
+        }
         
         drop(jobs); // Release lock
         
-        // Send SIGTERM if running
+        self.supervisor.cancel_restart(label).await;
+        
+        // Signal the process if running
         if let Some(pid) = pid {
-            if let Err(e) = nix::sys::signal::kill(
-                nix::unistd::Pid::from_raw(pid as i32),
-                nix::sys::signal::Signal::SIGTERM
-            ) {
-                warn!("Failed to send SIGTERM to job '{}': {}", label, e);
+            if let Err(e) = self.spawner.signal(pid, signal, group) {
+                warn!("Failed to send {} to job '{}': {}", signal, label, e);
             }
             
             // Wait for process to terminate with timeout
@@ -235,21 +1468,22 @@ impl JobManager {
                     _ = handle => {
                         debug!("Process terminated gracefully");
                     }
-                    _ = time::sleep(Duration::from_secs(10)) => {
+                    _ = time::sleep(timeout) => {
                         // Force kill after timeout
                         warn!("Job '{}' did not terminate gracefully, sending SIGKILL", label);
-                        let _ = nix::sys::signal::kill(
-                            nix::unistd::Pid::from_raw(pid as i32),
-                            nix::sys::signal::Signal::SIGKILL
-                        );
+                        if let Some(instance) = self.jobs.write().await.get_mut(label) {
+                            instance.sent_signal = Some(Signal::SIGKILL as i32);
+                        }
+                        let _ = self.spawner.signal(pid, Signal::SIGKILL, group);
                     }
                 }
             }
         }
         
-        // Update state to stopped
+        // Update state to stopped, unless the job was unloaded meanwhile
         let mut jobs = self.jobs.write().await;
-        let instance = jobs.get_mut(label).unwrap();
+        let instance = jobs.get_mut(label)
+            .ok_or_else(|| NusaError::JobNotFound(label.to_string()))?;
         
         instance.state = JobState::Stopped;
         instance.pid = None;
@@ -265,6 +1499,42 @@ impl JobManager {
         Ok(())
     }
     
+    /// React to a job failing its health check: restart it if its policy allows, otherwise fail it
+    ///
+    /// Boxed because it re-enters `start_job`, which spawns the health check that calls it.
+    pub fn handle_unhealthy<'a>(
+        &'a self,
+        label: &'a str,
+        failures: u32,
+        reason: String,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            self.event_dispatcher.send(JobEvent::HealthCheckFailed(label.to_string(), failures)).await?;
+            
+            let supervision = self.get_job_status(label).await
+                .ok_or_else(|| NusaError::JobNotFound(label.to_string()))?
+                .config.supervision;
+            
+            let restart = supervision.keep_alive
+                && matches!(supervision.restart_policy, RestartPolicy::Always | RestartPolicy::OnFailure);
+            
+            if restart {
+                info!("Restarting unhealthy job '{}'", label);
+                return self.restart_job(label).await;
+            }
+            
+            self.stop_job(label).await?;
+            
+            let state = JobState::Failed(format!("Health check failed: {}", reason));
+            if let Some(instance) = self.jobs.write().await.get_mut(label) {
+                instance.state = state.clone();
+            }
+            self.event_dispatcher.send(JobEvent::JobFailed(label.to_string(), state)).await?;
+            
+            Ok(())
+        })
+    }
+    
     /// Restart a job
     pub async fn restart_job(&self, label: &str) -> Result<()> {
         self.stop_job(label).await?;
@@ -272,53 +1542,382 @@ impl JobManager {
         self.start_job(label).await
     }
     
+    /// Re-read a job's config file and apply what changed
+    ///
+    /// Supervision, dependency, hook, and description changes take effect immediately. Anything
+    /// else (program, environment, limits, ...) needs a new process: with `restart` a running
+    /// job is restarted now, otherwise it keeps running and is marked as needing a restart.
+    #[instrument(skip(self), fields(job = %label))]
+    pub async fn reload_job(&self, label: &str, restart: bool) -> Result<ReloadOutcome> {
+        let (source, current) = {
+            let jobs = self.jobs.read().await;
+            let instance = jobs.get(label)
+                .ok_or_else(|| NusaError::JobNotFound(label.to_string()))?;
+            let source = instance.source_path.clone().ok_or_else(|| {
+                NusaError::System(format!("Job '{}' was not loaded from a file", label))
+            })?;
+            (source, instance.config.clone())
+        };
+        
+        let config = match JobConfigFile::from_file(&source).await? {
+            JobConfigFile::Single(config) if config.label != label => {
+                return Err(ConfigError::Validation(format!(
+                    "{} now defines job '{}' instead of '{}'",
+                    source.display(), config.label, label
+                )).into());
+            }
+            file => file.into_jobs()
+                .into_iter()
+                .find(|config| config.label == label)
+                .ok_or_else(|| ConfigError::Validation(format!(
+                    "{} no longer defines job '{}'",
+                    source.display(), label
+                )))?,
+        };
+        
+        let changes = diff_configs(&current, &config);
+        if changes.is_empty() {
+            return Ok(ReloadOutcome::Unchanged);
+        }
+        
+        let sockets_changed = changes.iter().any(|change| change.field.starts_with("sockets"));
+        
+        let needs_restart = changes.iter().any(|change| !is_live_field(&change.field));
+        
+        let running = {
+            let mut jobs = self.jobs.write().await;
+            
+            let configs = jobs.iter()
+                .filter(|(other, _)| other.as_str() != label)
+                .map(|(_, instance)| &instance.config)
+                .chain(std::iter::once(&config));
+            if let Some(cycle) = dependency::find_cycle(configs) {
+                return Err(dependency::cycle_error(&cycle).into());
+            }
+            
+            let instance = jobs.get_mut(label)
+                .ok_or_else(|| NusaError::JobNotFound(label.to_string()))?;
+            let running = instance.pid.is_some();
+            
+            instance.config = config;
+            if needs_restart && running && !restart {
+                instance.needs_restart = true;
+            }
+            running
+        };
+        
+        let fields: Vec<&str> = changes.iter().map(|change| change.field.as_str()).collect();
+        info!("Reloaded config from {} ({})", source.display(), fields.join(", "));
+        
+        // Rebind changed sockets; a job whose sockets were open keeps being activated
+        if sockets_changed {
+            let listening = self.jobs.read().await
+                .get(label)
+                .map_or(false, |instance| !instance.sockets.is_empty());
+            self.close_sockets(label).await;
+            if listening {
+                self.listen_sockets(label).await?;
+            }
+        }
+        
+        let outcome = if !needs_restart || !running {
+            ReloadOutcome::Applied
+        } else if restart {
+            self.restart_job(label).await?;
+            ReloadOutcome::Restarted
+        } else {
+            warn!("Job '{}' keeps running with its old config until it is restarted", label);
+            ReloadOutcome::NeedsRestart
+        };
+        
+        self.event_dispatcher.send(JobEvent::JobReloaded(label.to_string(), outcome)).await?;
+        
+        Ok(outcome)
+    }
+    
+    /// Unload a job on request, refusing while other loaded jobs depend on it
+    pub async fn remove_job(&self, label: &str) -> Result<()> {
+        let dependents = {
+            let jobs = self.jobs.read().await;
+            if !jobs.contains_key(label) {
+                return Err(NusaError::JobNotFound(label.to_string()));
+            }
+            dependency::dependents(label, jobs.values().map(|instance| &instance.config))
+        };
+        
+        if !dependents.is_empty() {
+            return Err(ConfigError::Validation(format!(
+                "Cannot unload job '{}': {} depend(s) on it",
+                label,
+                dependents.join(", ")
+            )).into());
+        }
+        
+        self.unload_job(label).await
+    }
+    
+    /// Stop a job if it is running and remove it
+    #[instrument(skip(self), fields(job = %label))]
+    pub async fn unload_job(&self, label: &str) -> Result<()> {
+        self.supervisor.cancel_restart(label).await;
+        self.close_sockets(label).await;
+        
+        // A job without a process is removed under the lock that checks for one, so a start
+        // cannot claim it in between; one still spawning kills its process (see `launch_job`)
+        let removed = {
+            let mut jobs = self.jobs.write().await;
+            match jobs.get(label) {
+                None => return Err(NusaError::JobNotFound(label.to_string())),
+                Some(instance) if instance.pid.is_some() => None,
+                Some(_) => jobs.remove(label),
+            }
+        };
+        let instance = match removed {
+            Some(instance) => instance,
+            None => {
+                self.stop_job(label).await?;
+                self.jobs.write().await.remove(label)
+                    .ok_or_else(|| NusaError::JobNotFound(label.to_string()))?
+            }
+        };
+        if let Some(delayed) = instance.start_delay_handle {
+            delayed.abort();
+        }
+        
+        info!("Job unloaded: {}", label);
+        self.event_dispatcher.send(JobEvent::JobUnloaded(label.to_string())).await?;
+        
+        Ok(())
+    }
+    
+    /// Load every job config in a directory, returning (loaded, failed) counts
+    ///
+    /// A missing or unreadable directory loads nothing; the job limit stops loading early.
+    pub async fn load_directory(&self, config_dir: &Path) -> (usize, usize) {
+        info!("Loading jobs from: {}", config_dir.display());
+        
+        let paths = match config_files(config_dir, self.recursive_config()) {
+            Ok(paths) => paths,
+            Err(e) => {
+                warn!("Failed to read config directory {}: {}", config_dir.display(), e);
+                return (0, 0);
+            }
+        };
+        
+        let mut loaded = 0;
+        let mut failed = 0;
+        
+        'files: for path in paths {
+            let configs = match JobConfigFile::from_file(&path).await {
+                Ok(file) => file.into_jobs(),
+                Err(e) => {
+                    error!("Failed to parse config file {}: {}", path.display(), e);
+                    failed += 1;
+                    continue;
+                }
+            };
+            
+            for config in configs {
+                match self.load_job_from(config, Some(path.clone())).await {
+                    Ok(()) => loaded += 1,
+                    Err(e @ NusaError::JobLimitExceeded(_)) => {
+                        warn!("{}; skipping {} and any remaining config files", e, path.display());
+                        failed += 1;
+                        break 'files;
+                    }
+                    Err(e) => {
+                        error!("Failed to load job from {}: {}", path.display(), e);
+                        failed += 1;
+                    }
+                }
+            }
+        }
+        
+        info!("Loaded {} jobs ({} failed)", loaded, failed);
+        (loaded, failed)
+    }
+    
+    /// Rescan the config directory set with `set_config_dir`
+    pub async fn reload_all(&self) -> Result<RescanSummary> {
+        let config_dir = self.settings().config_dir.clone().ok_or_else(|| {
+            NusaError::System("The daemon has no config directory to rescan".into())
+        })?;
+        self.rescan_directory(&config_dir).await
+    }
+    
+    /// Bring the loaded jobs in line with a config directory (on SIGHUP or `reload`)
+    ///
+    /// New files are loaded, changed ones reloaded (restarting jobs that need it), and jobs
+    /// whose file in the directory is gone are stopped and unloaded. Each action emits its
+    /// usual event; jobs loaded from elsewhere are left alone. Rescans and single-file
+    /// updates run one at a time, so overlapping requests never interleave.
+    pub async fn rescan_directory(&self, config_dir: &Path) -> Result<RescanSummary> {
+        let _serialized = self.reload_lock.lock().await;
+        info!("Rescanning jobs in: {}", config_dir.display());
+        
+        let recursive = self.recursive_config();
+        let mut paths = config_files(config_dir, recursive)?;
+        
+        let sources: Vec<PathBuf> = self.jobs.read().await
+            .values()
+            .filter_map(|instance| instance.source_path.clone())
+            .filter(|path| reconcile::in_config_dir(path, config_dir, recursive))
+            .collect();
+        for source in sources {
+            if !paths.contains(&source) {
+                paths.push(source);
+            }
+        }
+        
+        let mut summary = RescanSummary::default();
+        for path in paths {
+            self.sync_config_file(&path, &mut summary).await;
+        }
+        
+        info!("Rescanned {}: {}", config_dir.display(), summary);
+        Ok(summary)
+    }
+    
+    /// Bring the loaded jobs in line with one config file: load the jobs it newly defines,
+    /// reload the ones it still defines (restarting them when needed), and unload the ones
+    /// it no longer defines or all of them if the file is gone
+    pub async fn apply_config_file(&self, path: &Path) -> RescanSummary {
+        let _serialized = self.reload_lock.lock().await;
+        let mut summary = RescanSummary::default();
+        self.sync_config_file(path, &mut summary).await;
+        summary
+    }
+    
+    async fn sync_config_file(&self, path: &Path, summary: &mut RescanSummary) {
+        let loaded = self.jobs_for_source(path).await;
+        
+        let configs = if path.exists() {
+            match JobConfigFile::from_file(path).await {
+                Ok(file) => file.into_jobs(),
+                Err(e) => {
+                    error!("Failed to load {}: {}", path.display(), e);
+                    summary.failed += 1;
+                    return;
+                }
+            }
+        } else if loaded.is_empty() {
+            debug!("Ignoring removal of {}, no job was loaded from it", path.display());
+            return;
+        } else {
+            Vec::new()
+        };
+        
+        for label in &loaded {
+            if configs.iter().any(|config| config.label == *label) {
+                match self.reload_job(label, true).await {
+                    Ok(ReloadOutcome::Unchanged) => {
+                        debug!("Job '{}' is unchanged in {}", label, path.display());
+                        summary.unchanged += 1;
+                    }
+                    Ok(outcome) => {
+                        info!("Reloaded job '{}' from {}: {}", label, path.display(), outcome);
+                        summary.reloaded += 1;
+                    }
+                    Err(e) => {
+                        error!("Failed to reload job '{}' from {}: {}", label, path.display(), e);
+                        summary.failed += 1;
+                    }
+                }
+                continue;
+            }
+            
+            match self.unload_job(label).await {
+                Ok(()) => {
+                    info!("Unloaded job '{}': {} no longer defines it", label, path.display());
+                    summary.removed += 1;
+                }
+                Err(e) => {
+                    error!("Failed to unload job '{}': {}", label, e);
+                    summary.failed += 1;
+                }
+            }
+        }
+        
+        for config in configs.into_iter().filter(|config| !loaded.contains(&config.label)) {
+            let label = config.label.clone();
+            match self.load_job_from(config, Some(path.to_path_buf())).await {
+                Ok(()) => {
+                    info!("Loaded job '{}' from new config {}", label, path.display());
+                    summary.loaded += 1;
+                }
+                Err(e) => {
+                    error!("Failed to load job '{}' from {}: {}", label, path.display(), e);
+                    summary.failed += 1;
+                }
+            }
+        }
+    }
+    
+    /// Labels of the jobs loaded from `path`
+    pub async fn jobs_for_source(&self, path: &Path) -> Vec<String> {
+        self.jobs.read().await
+            .iter()
+            .filter(|(_, instance)| instance.source_path.as_deref() == Some(path))
+            .map(|(label, _)| label.clone())
+            .collect()
+    }
+    
+    /// Label of a job loaded from `path`, if any
+    pub async fn job_for_source(&self, path: &Path) -> Option<String> {
+        self.jobs.read().await
+            .iter()
+            .find(|(_, instance)| instance.source_path.as_deref() == Some(path))
+            .map(|(label, _)| label.clone())
+    }
+    
+    /// Check what starting a job would do without starting it
+    pub async fn check_job(&self, label: &str) -> Result<SpawnCheck> {
+        let config = self.jobs.read().await
+            .get(label)
+            .map(|instance| instance.config.clone())
+            .ok_or_else(|| NusaError::JobNotFound(label.to_string()))?;
+        
+        Ok(check::check_spawn(&config))
+    }
+    
     /// Get job status
     pub async fn get_job_status(&self, label: &str) -> Option<JobStatus> {
         let jobs = self.jobs.read().await;
-        jobs.get(label).map(|instance| {
-            let uptime = instance.start_time.map(|t| t.elapsed());
-            
-            JobStatus {
-                label: label.to_string(),
-                state: instance.state.clone(),
-                pid: instance.pid,
-                restart_count: instance.restart_count,
-                uptime,
-                exit_code: instance.last_exit_code,
-                exit_signal: instance.last_exit_signal,
-                config: instance.config.clone(),
-            }
-        })
+        jobs.get(label).map(|instance| instance.status(label))
+    }
+    
+    /// Recent restarts of a job, oldest first, to tell a flapping job's pattern of failures
+    pub async fn get_job_history(&self, label: &str) -> Option<Vec<RestartRecord>> {
+        let jobs = self.jobs.read().await;
+        jobs.get(label).map(|instance| instance.restart_history.iter().cloned().collect())
     }
     
     /// List all jobs
     pub async fn list_jobs(&self) -> Vec<JobStatus> {
         let jobs = self.jobs.read().await;
         jobs.iter()
-            .map(|(label, instance)| {
-                let uptime = instance.start_time.map(|t| t.elapsed());
-                
-                JobStatus {
-                    label: label.clone(),
-                    state: instance.state.clone(),
-                    pid: instance.pid,
-                    restart_count: instance.restart_count,
-                    uptime,
-                    exit_code: instance.last_exit_code,
-                    exit_signal: instance.last_exit_signal,
-                    config: instance.config.clone(),
-                }
-            })
+            .map(|(label, instance)| instance.status(label))
             .collect()
     }
     
+    /// Preview what rescanning `dir`, whose configs are `on_disk`, would add, remove, or update
+    pub async fn plan_reconcile(&self, dir: &Path, on_disk: &[(PathBuf, JobConfig)]) -> ReconcilePlan {
+        let recursive = self.recursive_config();
+        let jobs = self.jobs.read().await;
+        let loaded: Vec<(Option<PathBuf>, JobConfig)> = jobs.values()
+            .map(|instance| (instance.source_path.clone(), instance.config.clone()))
+            .collect();
+        
+        ReconcilePlan::compute(&loaded, on_disk, dir, recursive)
+    }
+    
     /// Handle process exit
     pub async fn handle_process_exit(
         &self,
         label: String,
+        pid: u32,
         exit_code: i32,
         signal: Option<i32>,
-        restart_needed: bool,
     ) -> Result<()> {
         debug!("Handling process exit for job: {}", label);
         
@@ -327,49 +1926,68 @@ impl JobManager {
         let instance = jobs.get_mut(&label)
             .ok_or_else(|| NusaError::JobNotFound(label.clone()))?;
         
+        // A process that was stopped or already replaced is handled by whoever stopped it
+        if instance.pid != Some(pid) || instance.state == JobState::Stopping {
+            debug!("Ignoring exit of stale process [PID: {}] for job: {}", pid, label);
+            return Ok(());
+        }
+        
         // Update exit information
         instance.last_exit_code = Some(exit_code);
         instance.last_exit_signal = signal;
         instance.pid = None;
         instance.process_handle = None;
         
-        // Determine next state
-        if restart_needed {
-            instance.state = JobState::Restarting;
-            instance.restart_count += 1;
-            
-            // Check restart limits
-            if instance.config.supervision.max_restarts > 0 &&
-               instance.restart_count >= instance.config.supervision.max_restarts {
-                instance.state = JobState::Failed(format!(
-                    "Exceeded max restarts ({})",
-                    instance.config.supervision.max_restarts
-                ));
-                self.event_dispatcher.send(JobEvent::JobFailed(
-                    label.clone(),
-                    instance.state.clone(),
-                )).await?;
-            } else {
-                // Schedule restart with backoff
-                let backoff_duration = self.calculate_backoff_duration(instance);
-                instance.backoff_until = Some(Instant::now() + backoff_duration);
-                instance.state = JobState::Backoff;
-                
-                // Send restart request
-                self.restart_tx.send(RestartRequest {
-                    label: label.clone(),
-                    delay: backoff_duration,
-                }).await
-                .map_err(|e| NusaError::System(format!("Failed to schedule restart: {}", e)))?;
-                
-                self.event_dispatcher.send(JobEvent::JobRestartScheduled(
+        // A daemon-style policy on a program that keeps finishing cleanly is
+        // almost always a misconfiguration; flag it instead of looping silently
+        let supervision = &instance.config.supervision;
+        let rapid_clean_exit = supervision.keep_alive
+            && matches!(supervision.restart_policy, RestartPolicy::Always)
+            && exit_code == 0
+            && signal.is_none()
+            && instance.start_time.map_or(false, |t| t.elapsed() < RAPID_EXIT_WINDOW);
+        
+        if rapid_clean_exit {
+            instance.rapid_clean_exits += 1;
+            if instance.rapid_clean_exits == RAPID_CLEAN_EXIT_THRESHOLD {
+                warn!(
+                    "Job '{}' exited cleanly {} times in a row within {:?} of starting; \
+                     restart_policy=always may be wrong for a program that is not a daemon",
+                    label, instance.rapid_clean_exits, RAPID_EXIT_WINDOW
+                );
+                self.event_dispatcher.send(JobEvent::RapidCleanExits(
                     label.clone(),
-                    backoff_duration,
-                    instance.restart_count,
+                    instance.rapid_clean_exits,
                 )).await?;
             }
         } else {
-            instance.state = JobState::Stopped;
+            instance.rapid_clean_exits = 0;
+        }
+        
+        // A scheduled job finishing cleanly just waits for its next run
+        let scheduled_run_done = instance.config.schedule.is_some()
+            && exit_code == 0
+            && signal.is_none();
+        
+        // Determine next state
+        // Only a signal the daemon didn't send is a crash to on-crash supervision
+        let expected_signal = signal.is_some() && signal == instance.sent_signal;
+        let supervision = &instance.config.supervision;
+        let restart_needed = if expected_signal && matches!(supervision.restart_policy, RestartPolicy::OnCrash) {
+            debug!("Job '{}' was ended by a signal the daemon sent, not restarting", label);
+            false
+        } else {
+            supervision.wants_restart(exit_code, signal)
+        };
+        if restart_needed && !scheduled_run_done {
+            let uptime = instance.start_time.map(|t| t.elapsed()).unwrap_or_default();
+            self.restart_or_fail(&label, instance, Some(uptime)).await?;
+        } else {
+            instance.state = if exit_code == 0 && signal.is_none() {
+                JobState::Completed
+            } else {
+                JobState::Stopped
+            };
             self.event_dispatcher.send(JobEvent::JobExited(
                 label.clone(),
                 exit_code,
@@ -381,12 +1999,83 @@ impl JobManager {
         Ok(())
     }
     
-    /// Calculate backoff duration for restarts
-    fn calculate_backoff_duration(&self, instance: &JobInstance) -> Duration {
-        let base_delay = instance.config.supervision.restart_delay_sec;
-        let multiplier = 2u64.pow(instance.restart_count.min(5)); // Cap exponential growth
+    /// Schedule a restart with backoff, or fail the job once it hit its restart limits
+    ///
+    /// `uptime` is how long the last process ran, `None` if it never started.
+    async fn restart_or_fail(&self, label: &str, instance: &mut JobInstance, uptime: Option<Duration>) -> Result<()> {
+        // A long enough run means earlier crashes no longer count toward max_restarts
+        if let Some(uptime) = uptime {
+            if instance.restart_count > 0 && instance.config.supervision.resets_restart_count(uptime) {
+                debug!("Job '{}' ran for {:?}, resetting restart count", label, uptime);
+                instance.restart_count = 0;
+            }
+        }
+        
+        instance.state = JobState::Restarting;
+        instance.restart_count += 1;
+        
+        // Check restart limits
+        let limit = if instance.config.supervision.max_restarts > 0 &&
+           instance.restart_count >= instance.config.supervision.max_restarts {
+            Some(format!("Exceeded max restarts ({})", instance.config.supervision.max_restarts))
+        } else if let Some(interval) = instance.restart_limit_hit() {
+            Some(format!(
+                "Restart rate limit hit ({} restarts within {}s)",
+                instance.config.supervision.restart_limit_burst,
+                interval.as_secs()
+            ))
+        } else {
+            None
+        };
         
-        Duration::from_secs(base_delay * multiplier)
+        // Name the exit that used up the last restart, unless the process never started
+        let failure = limit.map(|reason| match (uptime, instance.last_exit_code) {
+            (Some(_), Some(exit_code)) => {
+                format!("{}: {}", reason, ProcessError::from_exit(exit_code, instance.last_exit_signal))
+            }
+            _ => reason,
+        });
+        
+        if let Some(reason) = failure {
+            instance.state = JobState::Failed(reason);
+            self.event_dispatcher.send(JobEvent::JobFailed(
+                label.to_string(),
+                instance.state.clone(),
+            )).await?;
+        } else {
+            if instance.config.supervision.restart_limit_interval().is_some() {
+                instance.restart_times.push_back(Instant::now());
+            }
+            
+            // Only an exit leaves a code or signal; a failed spawn has neither
+            let (exit_code, signal) = match uptime {
+                Some(_) if instance.last_exit_signal.is_some() => (None, instance.last_exit_signal),
+                Some(_) => (instance.last_exit_code, None),
+                None => (None, None),
+            };
+            if instance.restart_history.len() == RESTART_HISTORY_LEN {
+                instance.restart_history.pop_front();
+            }
+            instance.restart_history.push_back(RestartRecord { at: SystemTime::now(), exit_code, signal });
+            instance.total_restarts += 1;
+            
+            // Schedule restart with backoff; the supervisor hands the job back when it's due
+            let backoff_duration = self.supervisor.schedule_restart(
+                label.to_string(),
+                instance.config.supervision.clone(),
+                instance.restart_count - 1,
+            ).await?;
+            instance.backoff_until = Some(Instant::now() + backoff_duration);
+            instance.state = JobState::Backoff;
+            
+            self.event_dispatcher.send(JobEvent::JobRestartScheduled(
+                label.to_string(),
+                backoff_duration,
+                instance.restart_count,
+            )).await?;
+        }
+        
+        Ok(())
     }
 }
 
@@ -395,75 +2084,222 @@ impl Clone for JobManager {
         Self {
             jobs: Arc::clone(&self.jobs),
             event_dispatcher: self.event_dispatcher.clone(),
-            spawner: ProcessSpawner::new(self.event_dispatcher.clone()),
-            restart_tx: self.restart_tx.clone(),
+            spawner: Arc::clone(&self.spawner),
+            supervisor: Arc::clone(&self.supervisor),
+            max_jobs: Arc::clone(&self.max_jobs),
+            settings: Arc::clone(&self.settings),
+            reload_lock: Arc::clone(&self.reload_lock),
+            started_at: self.started_at,
+            started: self.started,
         }
     }
 }
 
-async fn handle_restart_request(
-    request: RestartRequest,
-    jobs: Arc<RwLock<HashMap<String, JobInstance>>>,
-    event_dispatcher: EventDispatcher,
-) {
-    // Wait for the delay
-    time::sleep(request.delay).await;
-    
-    let mut jobs = jobs.write().await;
-    
-    if let Some(instance) = jobs.get_mut(&request.label) {
-        // Check if still in backoff/restarting state
-        if matches!(instance.state, JobState::Backoff | JobState::Restarting) {
-            // Reset state to stopped so it can be started again
-            instance.state = JobState::Stopped;
-            drop(jobs); // Release lock
-            
-            // Note: Actual restart will be triggered by external logic
-            // This is just the scheduler
-            let _ = event_dispatcher.send(JobEvent::JobReadyForRestart(request.label)).await;
+/// Job config files in a directory, and with `recursive` in all directories below it,
+/// sorted so jobs load in a stable order
+pub(crate) fn config_files(dir: &Path, recursive: bool) -> std::io::Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    let mut visited = HashSet::new();
+    collect_config_files(dir, recursive, &mut visited, &mut paths)?;
+    paths.sort();
+    Ok(paths)
+}
+
+/// `visited` holds the canonical directories read so far; a symlink leading back to one
+/// of them is skipped rather than followed forever
+fn collect_config_files(
+    dir: &Path,
+    recursive: bool,
+    visited: &mut HashSet<PathBuf>,
+    paths: &mut Vec<PathBuf>,
+) -> std::io::Result<()> {
+    if !visited.insert(std::fs::canonicalize(dir)?) {
+        warn!("Skipping {}: symlink loop in the config directory", dir.display());
+        return Ok(());
+    }
+    
+    for path in std::fs::read_dir(dir)?.flatten().map(|entry| entry.path()) {
+        if recursive && path.is_dir() {
+            if let Err(e) = collect_config_files(&path, recursive, visited, paths) {
+                warn!("Failed to read config directory {}: {}", path.display(), e);
+            }
+        } else if config::is_config_file(&path) {
+            paths.push(path);
         }
     }
+    Ok(())
 }
 
-#[derive(Debug)]
+/// Target a config file belongs to: the first directory below `config_dir` on its path,
+/// or `None` for a file directly in (or outside of) `config_dir`
+pub fn directory_target(config_dir: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(config_dir).ok()?;
+    let mut components = relative.components();
+    let first = components.next()?;
+    components.next()?;
+    first.as_os_str().to_str().map(str::to_string)
+}
+
+/// Whether a changed config field (dotted path) takes effect without restarting the process
+fn is_live_field(field: &str) -> bool {
+    matches!(field, "description" | "after" | "requires")
+        || field.starts_with("supervision.")
+        || field.starts_with("hooks.")
+        || field.starts_with("conditions.")
+}
+
+/// Result of `claim_start`
+enum StartClaim {
+    /// The job is now Starting and the caller must spawn it
+    Claimed { restarting: bool },
+    
+    /// Nothing to spawn: the job is running (with its process), starting, or in backoff
+    Active(Option<StartedJob>),
+}
+
+/// Process details returned when a job is started
+#[derive(Debug, Clone, Copy)]
+pub struct StartedJob {
+    pub pid: u32,
+    /// When the process started
+    pub started_at: SystemTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct JobStatus {
     pub label: String,
     pub state: JobState,
     pub pid: Option<u32>,
     pub restart_count: u32,
-    pub uptime: Option<Duration>,
+    /// Whole seconds the current process has been running, by its start time in `/proc`
+    pub uptime_sec: Option<u64>,
+    /// Whole seconds the daemon has been supervising the current process
+    #[serde(default)]
+    pub supervised_sec: Option<u64>,
+    /// Whole seconds after system boot at which the current process started, from `/proc`
+    #[serde(default)]
+    pub started_after_boot_sec: Option<u64>,
     pub exit_code: Option<i32>,
     pub exit_signal: Option<i32>,
     pub config: JobConfig,
+    #[serde(default)]
+    pub needs_restart: bool,
+    /// Config file the job was loaded from, if any
+    #[serde(default)]
+    pub source_path: Option<PathBuf>,
+    /// Resident memory of the running process, in bytes
+    #[serde(default)]
+    pub rss_bytes: Option<u64>,
+    /// CPU use of the running process since the previous status query (or its start),
+    /// in percent of one CPU
+    #[serde(default)]
+    pub cpu_percent: Option<f64>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", content = "data", rename_all = "kebab-case")]
 pub enum JobEvent {
     JobLoaded(String),
-    JobStarted(String, u32, Instant),
+    JobReloaded(String, ReloadOutcome),
+    JobUnloaded(String),
+    JobStarted(String, u32, SystemTime),
     JobStopped(String, JobState),
     JobExited(String, i32, Option<i32>, u32),
     JobFailed(String, JobState),
     JobRestartScheduled(String, Duration, u32),
     JobReadyForRestart(String),
+    RapidCleanExits(String, u32),
+    JobBlocked(String, String),
+    JobSkipped(String, String),
+    HealthCheckFailed(String, u32),
+    JobStartTimedOut(String, Duration),
+    SpawnFailed(String, String),
 }
 
-#[derive(Debug)]
-struct RestartRequest {
-    label: String,
-    delay: Duration,
+impl std::fmt::Display for JobEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobEvent::JobLoaded(label) => write!(f, "Job loaded: {}", label),
+            JobEvent::JobReloaded(label, outcome) => write!(f, "Job reloaded: {} ({})", label, outcome),
+            JobEvent::JobUnloaded(label) => write!(f, "Job unloaded: {}", label),
+            JobEvent::JobStarted(label, pid, _) => write!(f, "Job started: {} [PID: {}]", label, pid),
+            JobEvent::JobStopped(label, previous_state) => {
+                write!(f, "Job stopped: {} (was: {})", label, previous_state)
+            }
+            JobEvent::JobExited(label, code, signal, restart_count) => {
+                let signal_info = signal.map(|s| format!("terminated by {}", describe_signal(s)))
+                    .unwrap_or_else(|| "normally".to_string());
+                write!(
+                    f,
+                    "Job exited: {} with code {}, {} (restarts: {})",
+                    label, code, signal_info, restart_count
+                )
+            }
+            JobEvent::JobFailed(label, state) => write!(f, "Job failed: {} with state: {}", label, state),
+            JobEvent::JobRestartScheduled(label, delay, attempt) => write!(
+                f,
+                "Job restart scheduled: {} in {:?} (attempt {})",
+                label, delay, attempt
+            ),
+            JobEvent::JobReadyForRestart(label) => write!(f, "Job ready for restart: {}", label),
+            JobEvent::RapidCleanExits(label, count) => write!(
+                f,
+                "Job {} exited cleanly {} times in quick succession under restart_policy=always",
+                label, count
+            ),
+            JobEvent::JobBlocked(label, reason) => write!(f, "Job blocked: {} ({})", label, reason),
+            JobEvent::JobSkipped(label, reason) => write!(f, "Job skipped: {} ({})", label, reason),
+            JobEvent::HealthCheckFailed(label, failures) => write!(
+                f,
+                "Job health check failed: {} ({} consecutive failures)",
+                label, failures
+            ),
+            JobEvent::JobStartTimedOut(label, timeout) => {
+                write!(f, "Job start timed out: {} (not ready after {:?})", label, timeout)
+            }
+            JobEvent::SpawnFailed(label, error) => write!(f, "Job spawn failed: {} ({})", label, error),
+        }
+    }
+}
+
+impl JobState {
+    /// Every value `name` can return
+    pub const NAMES: [&'static str; 11] = [
+        "stopped", "completed", "starting", "running", "stopping", "restarting", "failed", "backoff", "waiting", "blocked", "skipped",
+    ];
+    
+    /// The state without its reason, e.g. `failed`
+    pub fn name(&self) -> &'static str {
+        match self {
+            JobState::Stopped => "stopped",
+            JobState::Completed => "completed",
+            JobState::Starting => "starting",
+            JobState::Running => "running",
+            JobState::Stopping => "stopping",
+            JobState::Restarting => "restarting",
+            JobState::Failed(_) => "failed",
+            JobState::Backoff => "backoff",
+            JobState::Waiting => "waiting",
+            JobState::Blocked(_) => "blocked",
+            JobState::Skipped(_) => "skipped",
+        }
+    }
 }
 
 impl std::fmt::Display for JobState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             JobState::Stopped => write!(f, "stopped"),
+            JobState::Completed => write!(f, "completed"),
             JobState::Starting => write!(f, "starting"),
             JobState::Running => write!(f, "running"),
             JobState::Stopping => write!(f, "stopping"),
             JobState::Restarting => write!(f, "restarting"),
             JobState::Failed(reason) => write!(f, "failed ({})", reason),
             JobState::Backoff => write!(f, "backoff"),
+            JobState::Waiting => write!(f, "waiting"),
+            JobState::Blocked(reason) => write!(f, "blocked ({})", reason),
+            JobState::Skipped(reason) => write!(f, "skipped ({})", reason),
         }
     }
 }
\ No newline at end of file
@@ -1,11 +1,20 @@
 //! Job management module for NusaLaunchd
 
+pub mod conditions;
 pub mod config;
+pub mod dependency;
+pub mod enabled;
+pub mod health;
+pub mod hooks;
 pub mod manager;
+pub mod notify;
+pub mod reconcile;
+pub mod scheduler;
+pub mod sockets;
 pub mod supervisor;
 pub mod validator;
 
 // Re-export commonly used types
-pub use config::{JobConfig, ProgramConfig, SupervisionConfig, RestartPolicy, EnvironmentVar};
-pub use manager::{JobManager, JobState, JobEvent, JobStatus};
+pub use config::{JobConfig, JobConfigFile, ProgramConfig, SupervisionConfig, RestartPolicy, BackoffStrategy, EnvironmentVar, LimitsConfig, ProcessConfig, ResourceLimit, LoggingConfig, LogTarget, ScheduleConfig, CalendarInterval, HealthConfig, HooksConfig, ConditionsConfig, SocketConfig, SocketType};
+pub use manager::{ChannelOptions, DaemonSummary, JobManager, JobState, JobEvent, JobStatus, ReloadOutcome, RescanSummary, RestartRecord, StartedJob};
 pub use supervisor::JobSupervisor;
\ No newline at end of file
@@ -0,0 +1,157 @@
+//! Readiness notification: a per-process `NOTIFY_SOCKET` that the job sends `READY=1` to (`sd_notify`)
+
+use std::ffi::CString;
+use std::os::unix::ffi::OsStringExt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::net::UnixDatagram;
+use tokio::time;
+use tracing::{debug, warn};
+
+use crate::daemon::dirs::{self, RUNTIME_DIR_MODE};
+use crate::job::manager::JobManager;
+use crate::process::credentials::Credentials;
+use crate::util::error::{NusaError, Result};
+
+/// How often to check whether the notifying process is still the job's process
+const PROCESS_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Largest notification datagram read; systemd's own limit is a page
+const MAX_MESSAGE_LEN: usize = 4096;
+
+/// Longest socket path the kernel accepts: `sun_path` less its terminating NUL
+const MAX_SOCKET_PATH: usize = 107;
+
+/// Name of the socket inside its directory
+const SOCKET_NAME: &str = "notify";
+
+/// Suffix `mkdtemp` replaces with random characters
+const UNIQUE_SUFFIX: &str = ".XXXXXX";
+
+/// A bound datagram socket for one process's notifications
+#[derive(Debug)]
+pub struct NotifySocket {
+    socket: UnixDatagram,
+    path: PathBuf,
+    dir: PathBuf,
+}
+
+impl NotifySocket {
+    /// Bind a fresh socket for a job in its own mode 0700 directory
+    ///
+    /// The directory goes under `<runtime_dir>/notify/`, or straight into the system temp
+    /// directory without a runtime directory. Its name is unpredictable, so no other user
+    /// can take its place. A job running as another user owns directory and socket, so
+    /// it can send to them.
+    pub fn bind(runtime_dir: Option<&Path>, label: &str, credentials: Option<&Credentials>) -> Result<Self> {
+        let (parent, prefix) = match runtime_dir {
+            Some(runtime_dir) => {
+                let parent = runtime_dir.join("notify");
+                dirs::ensure_dir(&parent, RUNTIME_DIR_MODE)?;
+                (parent, String::new())
+            }
+            None => (std::env::temp_dir(), "nusalaunchd-".to_string()),
+        };
+        
+        // The label only makes the path recognizable; cut it to what fits
+        let fixed = parent.as_os_str().len() + 1 + prefix.len() + UNIQUE_SUFFIX.len() + 1 + SOCKET_NAME.len();
+        let Some(room) = MAX_SOCKET_PATH.checked_sub(fixed) else {
+            return Err(NusaError::System(format!(
+                "Notify socket path under {} would exceed {} bytes",
+                parent.display(), MAX_SOCKET_PATH
+            )));
+        };
+        let mut end = label.len().min(room);
+        while !label.is_char_boundary(end) {
+            end -= 1;
+        }
+        
+        let dir = make_private_dir(&parent.join(format!("{}{}{}", prefix, &label[..end], UNIQUE_SUFFIX)))?;
+        let path = dir.join(SOCKET_NAME);
+        
+        let socket = UnixDatagram::bind(&path).map_err(|e| {
+            let _ = std::fs::remove_dir(&dir);
+            NusaError::System(format!("Failed to bind notify socket {}: {}", path.display(), e))
+        })?;
+        let socket = Self { socket, path, dir };
+        
+        if let Some(credentials) = credentials.filter(|credentials| credentials.needs_switch()) {
+            let uid = credentials.uid.map(|uid| uid.as_raw());
+            let gid = Some(credentials.gid.as_raw());
+            for path in [&socket.dir, &socket.path] {
+                std::os::unix::fs::chown(path, uid, gid).map_err(|e| {
+                    NusaError::System(format!("Failed to hand {} to the job's user: {}", path.display(), e))
+                })?;
+            }
+        }
+        
+        Ok(socket)
+    }
+    
+    /// Path to pass to the job as `NOTIFY_SOCKET`
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for NotifySocket {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+        let _ = std::fs::remove_dir(&self.dir);
+    }
+}
+
+/// Create a mode 0700 directory from `template`, whose trailing `XXXXXX` becomes random
+fn make_private_dir(template: &Path) -> Result<PathBuf> {
+    let template = CString::new(template.as_os_str().to_os_string().into_vec())
+        .map_err(|_| NusaError::System(format!("Invalid notify directory {}", template.display())))?;
+    let raw = template.into_raw();
+    
+    // SAFETY: `raw` is a NUL-terminated buffer we own; mkdtemp rewrites it in place
+    let created = unsafe { !libc::mkdtemp(raw).is_null() };
+    let template = unsafe { CString::from_raw(raw) };
+    let path = PathBuf::from(std::ffi::OsString::from_vec(template.into_bytes()));
+    
+    if !created {
+        return Err(NusaError::System(format!(
+            "Failed to create notify directory {}: {}",
+            path.display(), std::io::Error::last_os_error()
+        )));
+    }
+    Ok(path)
+}
+
+/// Whether a notification message contains `READY=1`
+///
+/// Messages are newline-separated `KEY=VALUE` assignments; anything but readiness is ignored.
+pub fn is_ready_message(message: &[u8]) -> bool {
+    message.split(|byte| *byte == b'\n').any(|line| line == b"READY=1")
+}
+
+/// Wait for process `pid` of a job to send `READY=1`, then mark it ready
+///
+/// Gives up when the job no longer runs that process, e.g. after it exited or hit its start timeout.
+pub async fn run_notify(manager: JobManager, label: String, pid: u32, socket: NotifySocket) {
+    let mut buf = vec![0u8; MAX_MESSAGE_LEN];
+    
+    loop {
+        match time::timeout(PROCESS_POLL_INTERVAL, socket.socket.recv(&mut buf)).await {
+            Ok(Ok(len)) if is_ready_message(&buf[..len]) => {
+                manager.mark_ready(&label, pid).await;
+                return;
+            }
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => {
+                warn!("Failed to read notify socket of job '{}': {}", label, e);
+                return;
+            }
+            Err(_) => {
+                let current = manager.get_job_status(&label).await.and_then(|status| status.pid);
+                if current != Some(pid) {
+                    debug!("Job '{}' [PID: {}] exited before notifying readiness", label, pid);
+                    return;
+                }
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,191 @@
+//! Reconciliation between loaded jobs and configs on disk
+
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use crate::job::config::JobConfig;
+
+/// What applying a config directory would change
+#[derive(Debug, Default, Serialize)]
+pub struct ReconcilePlan {
+    /// Jobs present on disk but not loaded
+    pub added: Vec<AddedJob>,
+    
+    /// Jobs loaded from the directory with no config there any more
+    pub removed: Vec<String>,
+    
+    /// Jobs whose on-disk config differs from the loaded one
+    pub updated: Vec<UpdatedJob>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AddedJob {
+    pub label: String,
+    pub source: PathBuf,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpdatedJob {
+    pub label: String,
+    pub source: PathBuf,
+    pub changes: Vec<FieldChange>,
+}
+
+/// A single changed config field, keyed by its dotted path (e.g. `supervision.max_restarts`)
+#[derive(Debug, Serialize, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+impl ReconcilePlan {
+    /// Compare the loaded configs, with the files they were loaded from, against configs
+    /// parsed from `dir`
+    ///
+    /// Only jobs loaded from `dir` (or below it, when `recursive`) can be removed; jobs
+    /// loaded from elsewhere or instantiated from templates are left alone, as a rescan does.
+    pub fn compute(
+        loaded: &[(Option<PathBuf>, JobConfig)],
+        on_disk: &[(PathBuf, JobConfig)],
+        dir: &Path,
+        recursive: bool,
+    ) -> Self {
+        let sources: HashMap<&str, Option<&Path>> = loaded.iter()
+            .map(|(source, config)| (config.label.as_str(), source.as_deref()))
+            .collect();
+        let loaded: HashMap<&str, &JobConfig> = loaded.iter()
+            .map(|(_, config)| (config.label.as_str(), config))
+            .collect();
+        
+        let mut plan = Self::default();
+        
+        for (source, config) in on_disk {
+            match loaded.get(config.label.as_str()) {
+                None => plan.added.push(AddedJob {
+                    label: config.label.clone(),
+                    source: source.clone(),
+                }),
+                Some(current) => {
+                    let changes = diff_configs(current, config);
+                    if !changes.is_empty() {
+                        plan.updated.push(UpdatedJob {
+                            label: config.label.clone(),
+                            source: source.clone(),
+                            changes,
+                        });
+                    }
+                }
+            }
+        }
+        
+        plan.removed = loaded.keys()
+            .filter(|label| sources[*label].map_or(false, |source| in_config_dir(source, dir, recursive)))
+            .filter(|label| !on_disk.iter().any(|(_, config)| config.label == **label))
+            .map(|label| label.to_string())
+            .collect();
+        
+        plan.added.sort_by(|a, b| a.label.cmp(&b.label));
+        plan.removed.sort();
+        plan.updated.sort_by(|a, b| a.label.cmp(&b.label));
+        
+        plan
+    }
+    
+    /// Whether applying the plan would change anything
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+    
+    /// Render the plan in a human-readable form
+    pub fn render(&self) -> String {
+        if self.is_empty() {
+            return "No changes".to_string();
+        }
+        
+        let mut out = String::new();
+        
+        for job in &self.added {
+            out.push_str(&format!("+ {} ({})\n", job.label, job.source.display()));
+        }
+        
+        for label in &self.removed {
+            out.push_str(&format!("- {}\n", label));
+        }
+        
+        for job in &self.updated {
+            out.push_str(&format!("~ {} ({})\n", job.label, job.source.display()));
+            for change in &job.changes {
+                out.push_str(&format!(
+                    "    {}: {} -> {}\n",
+                    change.field,
+                    change.old.as_deref().unwrap_or("(unset)"),
+                    change.new.as_deref().unwrap_or("(unset)"),
+                ));
+            }
+        }
+        
+        out.push_str(&format!(
+            "\n{} to add, {} to remove, {} to update",
+            self.added.len(), self.removed.len(), self.updated.len()
+        ));
+        
+        out
+    }
+}
+
+/// Whether a config file belongs to `dir`: directly in it, or anywhere below it when `recursive`
+pub fn in_config_dir(path: &Path, dir: &Path, recursive: bool) -> bool {
+    match recursive {
+        true => path.starts_with(dir),
+        false => path.parent() == Some(dir),
+    }
+}
+
+/// Field-level differences between two configs
+pub fn diff_configs(old: &JobConfig, new: &JobConfig) -> Vec<FieldChange> {
+    let old = flatten_config(old);
+    let new = flatten_config(new);
+    
+    let mut fields: Vec<&String> = old.keys().chain(new.keys()).collect();
+    fields.sort();
+    fields.dedup();
+    
+    fields.into_iter()
+        .filter(|field| old.get(*field) != new.get(*field))
+        .map(|field| FieldChange {
+            field: field.clone(),
+            old: old.get(field).cloned(),
+            new: new.get(field).cloned(),
+        })
+        .collect()
+}
+
+/// Flatten a config into dotted field paths with TOML-rendered values
+fn flatten_config(config: &JobConfig) -> BTreeMap<String, String> {
+    let mut fields = BTreeMap::new();
+    
+    if let Ok(toml::Value::Table(table)) = toml::Value::try_from(config) {
+        flatten_table("", &table, &mut fields);
+    }
+    
+    fields
+}
+
+fn flatten_table(prefix: &str, table: &toml::value::Table, fields: &mut BTreeMap<String, String>) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        
+        match value {
+            toml::Value::Table(inner) => flatten_table(&path, inner, fields),
+            other => {
+                fields.insert(path, other.to_string());
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,202 @@
+//! Interval and calendar scheduling for periodic jobs
+
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike};
+use std::path::{Path, PathBuf};
+use tokio::time;
+use tracing::{debug, error, info, warn};
+
+use crate::job::config::{CalendarInterval, ScheduleConfig};
+use crate::job::manager::{JobManager, JobState};
+use crate::util::error::{ConfigError, Result};
+
+/// How far ahead to look for a matching calendar date (covers Feb 29 on any weekday)
+const MAX_SEARCH_DAYS: u32 = 366 * 28;
+
+/// Check that exactly one trigger is set and calendar fields are in range
+pub fn validate_schedule(schedule: &ScheduleConfig) -> Result<()> {
+    match (&schedule.interval_sec, &schedule.calendar) {
+        (Some(_), Some(_)) => {
+            return Err(ConfigError::Validation(
+                "schedule: set either interval_sec or calendar, not both".into()
+            ).into());
+        }
+        (None, None) => {
+            return Err(ConfigError::Validation(
+                "schedule: one of interval_sec or calendar is required".into()
+            ).into());
+        }
+        (Some(0), None) => {
+            return Err(ConfigError::Validation(
+                "schedule: interval_sec must be at least 1".into()
+            ).into());
+        }
+        _ => {}
+    }
+    
+    if let Some(calendar) = &schedule.calendar {
+        let fields = [
+            ("minute", calendar.minute, 0, 59),
+            ("hour", calendar.hour, 0, 23),
+            ("day", calendar.day, 1, 31),
+            ("weekday", calendar.weekday, 0, 7),
+        ];
+        
+        for (name, value, min, max) in fields {
+            if let Some(value) = value {
+                if value < min || value > max {
+                    return Err(ConfigError::Validation(format!(
+                        "schedule.calendar.{} must be between {} and {}, got {}",
+                        name, min, max, value
+                    )).into());
+                }
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+/// The first run strictly after `after`
+pub fn next_run(schedule: &ScheduleConfig, after: DateTime<Local>) -> Option<DateTime<Local>> {
+    if let Some(interval) = schedule.interval_sec {
+        return Some(after + chrono::Duration::seconds(interval as i64));
+    }
+    
+    let calendar = schedule.calendar.as_ref()?;
+    let mut search_from = after.naive_local();
+    
+    // Skip local times that don't exist (DST gaps)
+    loop {
+        let next = next_calendar_match(calendar, search_from)?;
+        match Local.from_local_datetime(&next).earliest() {
+            Some(next) if next > after => return Some(next),
+            _ => search_from = next,
+        }
+    }
+}
+
+/// The first whole minute strictly after `after` matching the calendar
+fn next_calendar_match(calendar: &CalendarInterval, after: NaiveDateTime) -> Option<NaiveDateTime> {
+    let start = after.date().and_hms_opt(after.hour(), after.minute(), 0)? + chrono::Duration::minutes(1);
+    let mut date = start.date();
+    
+    for _ in 0..MAX_SEARCH_DAYS {
+        if date_matches(calendar, date) {
+            let from = if date == start.date() { start.time() } else { NaiveTime::MIN };
+            if let Some(time) = first_time_from(calendar, from) {
+                return Some(date.and_time(time));
+            }
+        }
+        date = date.succ_opt()?;
+    }
+    
+    None
+}
+
+fn date_matches(calendar: &CalendarInterval, date: NaiveDate) -> bool {
+    let day_matches = calendar.day.map_or(true, |day| date.day() == day);
+    let weekday_matches = calendar.weekday
+        .map_or(true, |weekday| date.weekday().num_days_from_sunday() == weekday % 7);
+    
+    day_matches && weekday_matches
+}
+
+fn first_time_from(calendar: &CalendarInterval, from: NaiveTime) -> Option<NaiveTime> {
+    for hour in from.hour()..24 {
+        if calendar.hour.map_or(false, |h| h != hour) {
+            continue;
+        }
+        
+        let first_minute = if hour == from.hour() { from.minute() } else { 0 };
+        for minute in first_minute..60 {
+            if calendar.minute.map_or(true, |m| m == minute) {
+                return NaiveTime::from_hms_opt(hour, minute, 0);
+            }
+        }
+    }
+    
+    None
+}
+
+/// Drive a scheduled job for as long as it stays loaded
+pub async fn run_schedule(
+    manager: JobManager,
+    label: String,
+    schedule: ScheduleConfig,
+    state_dir: Option<PathBuf>,
+) {
+    let stamp = state_dir.map(|dir| last_run_path(&dir, &label));
+    
+    if schedule.run_missed {
+        let last_run = stamp.as_deref().and_then(read_last_run);
+        let missed = last_run
+            .and_then(|last| next_run(&schedule, last))
+            .map_or(false, |due| due <= Local::now());
+        
+        if missed {
+            info!("Job '{}' missed a scheduled run while the daemon was down, running now", label);
+            if !trigger(&manager, &label, stamp.as_deref()).await {
+                return;
+            }
+        }
+    }
+    
+    loop {
+        let now = Local::now();
+        let Some(next) = next_run(&schedule, now) else {
+            warn!("Job '{}' has no upcoming scheduled run", label);
+            return;
+        };
+        
+        debug!("Next run of job '{}' at {}", label, next.to_rfc3339());
+        time::sleep((next - now).to_std().unwrap_or_default()).await;
+        
+        if !trigger(&manager, &label, stamp.as_deref()).await {
+            return;
+        }
+    }
+}
+
+/// Start a scheduled run; returns false once the job is no longer loaded
+async fn trigger(manager: &JobManager, label: &str, stamp: Option<&Path>) -> bool {
+    let Some(status) = manager.get_job_status(label).await else {
+        debug!("Job '{}' was unloaded, stopping its schedule", label);
+        return false;
+    };
+    
+    if matches!(status.state, JobState::Running | JobState::Starting) {
+        warn!("Skipping scheduled run of job '{}': previous run still in progress", label);
+        return true;
+    }
+    
+    info!("Starting scheduled run of job '{}'", label);
+    if let Err(e) = manager.start_job(label).await {
+        error!("Scheduled run of job '{}' failed to start: {}", label, e);
+    }
+    
+    if let Some(stamp) = stamp {
+        if let Err(e) = write_last_run(stamp, Local::now()) {
+            warn!("Failed to record last run of job '{}': {}", label, e);
+        }
+    }
+    
+    true
+}
+
+fn last_run_path(state_dir: &Path, label: &str) -> PathBuf {
+    state_dir.join("schedule").join(format!("{}.last_run", label))
+}
+
+fn read_last_run(path: &Path) -> Option<DateTime<Local>> {
+    let content = std::fs::read_to_string(path).ok()?;
+    DateTime::parse_from_rfc3339(content.trim())
+        .ok()
+        .map(|time| time.with_timezone(&Local))
+}
+
+fn write_last_run(path: &Path, time: DateTime<Local>) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, time.to_rfc3339())
+}
\ No newline at end of file
@@ -0,0 +1,358 @@
+//! Socket activation: listening sockets held by the daemon that start their job on demand
+
+use serde::{Deserialize, Serialize};
+use std::future::poll_fn;
+use std::io;
+use std::net::SocketAddr;
+use std::os::fd::{AsRawFd, OwnedFd, RawFd};
+use std::os::unix::fs::FileTypeExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+use std::time::Duration;
+use tokio::io::unix::AsyncFd;
+use tokio::io::Interest;
+use tokio::time;
+use tracing::{debug, error, info, warn};
+
+use crate::job::config::{SocketConfig, SocketType};
+use crate::job::manager::{JobManager, JobState};
+use crate::util::error::{ConfigError, NusaError, Result};
+
+/// How often to check whether an activated job has finished
+const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Minimum time between two activations, so a job that exits without
+/// accepting its pending connection is not restarted in a tight loop
+const MIN_ACTIVATION_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Where a socket listens
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListenAddr {
+    Inet(SocketAddr),
+    Unix(PathBuf),
+}
+
+impl ListenAddr {
+    /// Parse `host:port`, `[v6]:port`, a bare port, or an absolute unix socket path
+    pub fn parse(listen: &str) -> std::result::Result<Self, String> {
+        let listen = listen.trim();
+        
+        if listen.starts_with('/') {
+            return Ok(ListenAddr::Unix(PathBuf::from(listen)));
+        }
+        
+        if let Ok(port) = listen.parse::<u16>() {
+            return Ok(ListenAddr::Inet(SocketAddr::from(([0, 0, 0, 0], port))));
+        }
+        
+        listen.parse::<SocketAddr>()
+            .map(ListenAddr::Inet)
+            .map_err(|_| format!("'{}' is not a port, host:port, or absolute unix socket path", listen))
+    }
+}
+
+/// Longest name systemd accepts in `LISTEN_FDNAMES`
+const MAX_FD_NAME_LEN: usize = 255;
+
+/// Check that every `listen` parses, no address is used twice, and names fit `LISTEN_FDNAMES`
+pub fn validate_sockets(sockets: &[SocketConfig]) -> Result<()> {
+    let mut seen = Vec::new();
+    
+    for socket in sockets {
+        if let Some(name) = &socket.name {
+            let valid = !name.is_empty()
+                && name.len() <= MAX_FD_NAME_LEN
+                && name.chars().all(|c| c.is_ascii_graphic() && c != ':');
+            if !valid {
+                return Err(ConfigError::Validation(format!(
+                    "sockets: invalid name '{}' (1-{} printable ASCII characters, no ':' or spaces)",
+                    name, MAX_FD_NAME_LEN
+                )).into());
+            }
+        }
+        
+        let addr = ListenAddr::parse(&socket.listen)
+            .map_err(|e| ConfigError::Validation(format!("sockets: {}", e)))?;
+        
+        if seen.contains(&(addr.clone(), socket.socket_type)) {
+            return Err(ConfigError::Validation(
+                format!("sockets: '{}' ({}) is listed twice", socket.listen, socket.socket_type)
+            ).into());
+        }
+        seen.push((addr, socket.socket_type));
+        
+        if socket.idle_timeout_sec == Some(0) {
+            return Err(ConfigError::Validation(
+                format!("sockets: idle_timeout_sec of '{}' must be at least 1", socket.listen)
+            ).into());
+        }
+    }
+    
+    Ok(())
+}
+
+/// A bound socket waiting to activate its job
+#[derive(Debug)]
+pub struct ActivationSocket {
+    config: SocketConfig,
+    fd: OwnedFd,
+    unix_path: Option<PathBuf>,
+    activations: AtomicU64,
+    /// When the socket last had a new connection or datagram
+    last_activity: Mutex<Option<time::Instant>>,
+}
+
+impl ActivationSocket {
+    /// Create and bind the socket (listening, for stream sockets)
+    pub fn bind(config: &SocketConfig) -> Result<Self> {
+        let addr = ListenAddr::parse(&config.listen)
+            .map_err(|e| ConfigError::Validation(format!("sockets: {}", e)))?;
+        
+        let bind_error = |e: io::Error| {
+            NusaError::System(format!("Failed to bind {} socket {}: {}", config.socket_type, config.listen, e))
+        };
+        
+        let (fd, unix_path): (OwnedFd, _) = match (&addr, config.socket_type) {
+            (ListenAddr::Inet(addr), SocketType::Stream) => {
+                (std::net::TcpListener::bind(addr).map_err(bind_error)?.into(), None)
+            }
+            (ListenAddr::Inet(addr), SocketType::Dgram) => {
+                (std::net::UdpSocket::bind(addr).map_err(bind_error)?.into(), None)
+            }
+            (ListenAddr::Unix(path), socket_type) => {
+                remove_stale_socket(path).map_err(bind_error)?;
+                let fd: OwnedFd = match socket_type {
+                    SocketType::Stream => std::os::unix::net::UnixListener::bind(path).map_err(bind_error)?.into(),
+                    SocketType::Dgram => std::os::unix::net::UnixDatagram::bind(path).map_err(bind_error)?.into(),
+                };
+                (fd, Some(path.clone()))
+            }
+        };
+        
+        Ok(Self {
+            config: config.clone(),
+            fd,
+            unix_path,
+            activations: AtomicU64::new(0),
+            last_activity: Mutex::new(None),
+        })
+    }
+    
+    pub fn config(&self) -> &SocketConfig {
+        &self.config
+    }
+    
+    /// Times this socket has started its job
+    pub fn activations(&self) -> u64 {
+        self.activations.load(Ordering::Relaxed)
+    }
+    
+    /// Record a new connection or datagram
+    fn touch(&self) {
+        *self.last_activity.lock().unwrap() = Some(time::Instant::now());
+    }
+    
+    /// Whether the socket has gone without activity for its `idle_timeout_sec`
+    ///
+    /// Never true for a socket without an idle timeout.
+    pub fn is_idle(&self) -> bool {
+        let Some(timeout) = self.config.idle_timeout() else {
+            return false;
+        };
+        self.last_activity.lock().unwrap().map_or(true, |last| last.elapsed() >= timeout)
+    }
+    
+    /// Connections waiting to be accepted; only known for TCP listeners
+    pub fn pending(&self) -> Option<u32> {
+        let is_tcp = self.config.socket_type == SocketType::Stream && self.unix_path.is_none();
+        if !is_tcp {
+            return None;
+        }
+        
+        // For a listening socket Linux reports the accept queue length in tcpi_unacked
+        let mut info: libc::tcp_info = unsafe { std::mem::zeroed() };
+        let mut len = std::mem::size_of::<libc::tcp_info>() as libc::socklen_t;
+        // SAFETY: info and len describe a valid, writable tcp_info
+        let rc = unsafe {
+            libc::getsockopt(
+                self.fd.as_raw_fd(),
+                libc::IPPROTO_TCP,
+                libc::TCP_INFO,
+                (&mut info as *mut libc::tcp_info).cast(),
+                &mut len,
+            )
+        };
+        (rc == 0).then_some(info.tcpi_unacked)
+    }
+}
+
+impl AsRawFd for ActivationSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl Drop for ActivationSocket {
+    fn drop(&mut self) {
+        if let Some(path) = &self.unix_path {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// Unlink a leftover unix socket file; anything that is not a socket is left alone
+fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(path),
+        _ => Ok(()),
+    }
+}
+
+/// A socket as reported by `socket status`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SocketStatus {
+    pub job: String,
+    pub listen: String,
+    pub socket_type: SocketType,
+    
+    /// Current state of the job the socket activates
+    pub job_state: JobState,
+    
+    /// Times the socket has started its job
+    pub activations: u64,
+    
+    /// Connections waiting to be accepted, where the kernel reports it (TCP)
+    pub pending: Option<u32>,
+}
+
+impl SocketStatus {
+    pub fn new(job: &str, job_state: &JobState, socket: &ActivationSocket) -> Self {
+        Self {
+            job: job.to_string(),
+            listen: socket.config.listen.clone(),
+            socket_type: socket.config.socket_type,
+            job_state: job_state.clone(),
+            activations: socket.activations(),
+            pending: socket.pending(),
+        }
+    }
+}
+
+/// Start the job whenever one of its sockets becomes readable while the job is not running
+///
+/// Runs until the job is unloaded or its sockets are closed.
+pub async fn run_activation(manager: JobManager, label: String, sockets: Vec<Arc<ActivationSocket>>) {
+    let mut last_activation: Option<time::Instant> = None;
+    
+    loop {
+        // Wait until the job has no process; while it runs it accepts on the sockets itself
+        let activity = watch_activity(&label, &sockets);
+        loop {
+            match manager.get_job_status(&label).await {
+                None => {
+                    debug!("Job '{}' was unloaded, closing its sockets", label);
+                    return;
+                }
+                Some(status) if status.pid.is_none()
+                    && matches!(status.state, JobState::Stopped | JobState::Completed | JobState::Failed(_) | JobState::Blocked(_) | JobState::Skipped(_)) => break,
+                Some(status) => match &activity {
+                    Some(fds) => {
+                        let _ = time::timeout(IDLE_POLL_INTERVAL, record_activity(&sockets, fds)).await;
+                        
+                        if status.pid.is_some() && sockets.iter().all(|socket| socket.is_idle()) {
+                            info!("Sockets of job '{}' are idle, stopping it until the next connection", label);
+                            if let Err(e) = manager.stop_job(&label).await {
+                                warn!("Failed to stop idle job '{}': {}", label, e);
+                            }
+                        }
+                    }
+                    None => time::sleep(IDLE_POLL_INTERVAL).await,
+                },
+            }
+        }
+        
+        // Deregister before waiting for readiness again
+        drop(activity);
+        
+        if let Some(last) = last_activation {
+            time::sleep_until(last + MIN_ACTIVATION_INTERVAL).await;
+        }
+        
+        let index = match wait_readable(&sockets).await {
+            Ok(index) => index,
+            Err(e) => {
+                error!("Failed to wait on sockets of job '{}': {}", label, e);
+                return;
+            }
+        };
+        
+        let socket = &sockets[index];
+        socket.activations.fetch_add(1, Ordering::Relaxed);
+        socket.touch();
+        last_activation = Some(time::Instant::now());
+        
+        info!("Activity on {} socket {}, starting job '{}'", socket.config.socket_type, socket.config.listen, label);
+        if let Err(e) = manager.start_job(&label).await {
+            warn!("Socket activation of job '{}' failed: {}", label, e);
+        }
+    }
+}
+
+/// Index of the first socket with a pending connection or datagram
+async fn wait_readable(sockets: &[Arc<ActivationSocket>]) -> io::Result<usize> {
+    // Registered afresh on each wait: the registration reports a socket that is
+    // already readable, which a re-used edge-triggered one would not
+    let fds = sockets.iter()
+        .map(|socket| AsyncFd::with_interest(socket.as_raw_fd(), Interest::READABLE))
+        .collect::<io::Result<Vec<_>>>()?;
+    
+    poll_fn(|cx| {
+        for (index, fd) in fds.iter().enumerate() {
+            if let Poll::Ready(ready) = fd.poll_read_ready(cx) {
+                return Poll::Ready(ready.map(|_| index));
+            }
+        }
+        Poll::Pending
+    }).await
+}
+
+/// Readiness registrations for noticing new connections while the job runs, if any
+/// socket has an idle timeout
+fn watch_activity(label: &str, sockets: &[Arc<ActivationSocket>]) -> Option<Vec<AsyncFd<RawFd>>> {
+    if sockets.iter().all(|socket| socket.config.idle_timeout().is_none()) {
+        return None;
+    }
+    
+    // Idleness counts from the start of the run, also for a job started by hand
+    for socket in sockets {
+        socket.touch();
+    }
+    
+    // Kept for the whole run: edge-triggered, they report each new connection or datagram once
+    match sockets.iter()
+        .map(|socket| AsyncFd::with_interest(socket.as_raw_fd(), Interest::READABLE))
+        .collect::<io::Result<Vec<_>>>()
+    {
+        Ok(fds) => Some(fds),
+        Err(e) => {
+            warn!("Cannot watch sockets of job '{}' for activity, idle timeout disabled: {}", label, e);
+            None
+        }
+    }
+}
+
+/// Wait for the next new connection or datagram and record it on its socket
+async fn record_activity(sockets: &[Arc<ActivationSocket>], fds: &[AsyncFd<RawFd>]) {
+    poll_fn(|cx| {
+        for (socket, fd) in sockets.iter().zip(fds) {
+            if let Poll::Ready(Ok(mut ready)) = fd.poll_read_ready(cx) {
+                ready.clear_ready();
+                socket.touch();
+                return Poll::Ready(());
+            }
+        }
+        Poll::Pending
+    }).await
+}
\ No newline at end of file
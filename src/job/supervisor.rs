@@ -1,11 +1,11 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{mpsc, Mutex, RwLock};
 use tokio::time;
 use tracing::{info, warn, debug, instrument};
 
-use crate::job::config::{SupervisionConfig, RestartPolicy};
+use crate::job::config::SupervisionConfig;
 use crate::util::error::{NusaError, Result};
 
 pub struct JobSupervisor {
@@ -39,27 +39,13 @@ impl JobSupervisor {
             return false;
         }
         
-        // Check restart policy
-        match config.restart_policy {
-            RestartPolicy::Always => true,
-            RestartPolicy::Never => false,
-            RestartPolicy::OnFailure => exit_code != 0,
-            RestartPolicy::OnCrash => signal.is_some(),
-        }
+        // Check restart exit codes and policy
+        config.wants_restart(exit_code, signal)
     }
     
     /// Calculate backoff duration for restart
     pub fn calculate_backoff(&self, config: &SupervisionConfig, restart_count: u32) -> Duration {
-        let base_secs = config.restart_delay_sec;
-        
-        // Exponential backoff with cap
-        let exponent = restart_count.min(6); // Cap at 2^6 = 64x base delay
-        let multiplier = 2u64.pow(exponent);
-        
-        let backoff_secs = base_secs * multiplier;
-        
-        // Cap at 5 minutes max
-        Duration::from_secs(backoff_secs.min(300))
+        config.restart_backoff(restart_count)
     }
     
     /// Schedule a job for restart
@@ -157,7 +143,10 @@ impl JobSupervisor {
     }
     
     /// Start background task to process restart queue
-    pub fn start_restart_processor(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+    ///
+    /// Labels whose backoff has elapsed are sent on `ready_tx` for the job manager to
+    /// start; the task ends once the receiver is dropped.
+    pub fn start_restart_processor(self: Arc<Self>, ready_tx: mpsc::Sender<String>) -> tokio::task::JoinHandle<()> {
         tokio::spawn(async move {
             let mut interval = time::interval(Duration::from_secs(1));
             
@@ -170,8 +159,10 @@ impl JobSupervisor {
                 for label in ready_jobs {
                     info!("Job '{}' is ready for restart", label);
                     
-                    // In a real implementation, this would send an event
-                    // to the job manager to restart the job
+                    if ready_tx.send(label).await.is_err() {
+                        debug!("Job manager is gone, stopping restart processor");
+                        return;
+                    }
                 }
             }
         })
@@ -1,21 +1,62 @@
-use crate::job::config::JobConfig;
+use crate::job::config::{JobConfig, ProgramConfig, StdinSource};
+use crate::process::credentials::Credentials;
 use crate::util::error::{ConfigError, Result};
+use nix::unistd::{self, Gid, Uid};
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
 use std::path::Path;
 
 pub struct ConfigValidator;
 
 impl ConfigValidator {
     /// Validate a job configuration
-    pub async fn validate(config: &JobConfig) -> Result<()> {
+    ///
+    /// With `strict`, a program that is missing or not executable by the job's user is an
+    /// error; otherwise it is only logged, since the program may be installed later.
+    pub async fn validate(config: &JobConfig, strict: bool) -> Result<()> {
         // Check label
         Self::validate_label(&config.label)?;
         
         // Check program path
         Self::validate_program_path(&config.program.path)?;
+        if let Err(reason) = Self::check_executable(&config.program) {
+            if strict {
+                return Err(ConfigError::Validation(reason).into());
+            }
+            tracing::warn!("Job '{}': {}", config.label, reason);
+        }
+        
+        // Check umask if specified
+        if let Some(umask) = &config.program.umask {
+            Self::validate_umask(umask)?;
+        }
         
         // Check working directory if specified
         if let Some(working_dir) = &config.working_directory {
             Self::validate_working_directory(working_dir)?;
+            
+            if config.create_working_directory && !working_dir.exists() {
+                if let Err(reason) = check_writable(working_dir) {
+                    let reason = format!("Cannot create working directory {}: {}", working_dir.display(), reason);
+                    if strict {
+                        return Err(ConfigError::Validation(reason).into());
+                    }
+                    tracing::warn!("Job '{}': {}", config.label, reason);
+                }
+            }
+        }
+        
+        // Check the stdin file; a FIFO may be created just before the job starts
+        if let StdinSource::File(path) = &config.program.stdin {
+            let path = crate::process::output::resolve_log_path(config, path);
+            if !path.exists() {
+                let reason = format!("stdin {} does not exist", path.display());
+                if strict {
+                    return Err(ConfigError::Validation(reason).into());
+                }
+                tracing::warn!("Job '{}': {}", config.label, reason);
+            }
         }
         
         // Check environment variables
@@ -24,6 +65,12 @@ impl ConfigValidator {
         // Check supervision settings
         Self::validate_supervision(&config.supervision)?;
         
+        // Check resource limits
+        Self::validate_limits(&config.limits)?;
+        
+        // Check priority and OOM score
+        Self::validate_process(&config.process)?;
+        
         Ok(())
     }
     
@@ -62,8 +109,43 @@ impl ConfigValidator {
             ).into());
         }
         
-        // Note: We don't check if the file actually exists here
-        // because the program might be installed later
+        Ok(())
+    }
+    
+    /// Check that the program is a regular file the job's user (or the daemon's) may execute
+    fn check_executable(program: &ProgramConfig) -> std::result::Result<(), String> {
+        let credentials = Credentials::resolve(program).map_err(|e| e.to_string())?;
+        let (uid, groups) = effective_identity(credentials.as_ref());
+        Self::check_executable_by(&program.path, uid, &groups)
+    }
+    
+    /// Check that `path` is a regular file that `uid` (with `groups`) may execute
+    pub(crate) fn check_executable_by(
+        path: &Path,
+        uid: Uid,
+        groups: &[Gid],
+    ) -> std::result::Result<(), String> {
+        let metadata = std::fs::metadata(path)
+            .map_err(|e| format!("Program {} is not accessible: {}", path.display(), e))?;
+        
+        if !metadata.is_file() {
+            return Err(format!("Program {} is not a regular file", path.display()));
+        }
+        
+        if !may_execute(&metadata, uid, groups) {
+            return Err(format!("Program {} is not executable by uid {}", path.display(), uid));
+        }
+        
+        Ok(())
+    }
+    
+    /// Check that a umask is an octal value no larger than 0777; also run when a config is loaded
+    pub(crate) fn validate_umask(umask: &str) -> Result<()> {
+        if crate::job::config::parse_umask(umask).is_none() {
+            return Err(ConfigError::Validation(
+                format!("Invalid umask '{}': expected an octal value between 0000 and 0777", umask)
+            ).into());
+        }
         
         Ok(())
     }
@@ -78,7 +160,9 @@ impl ConfigValidator {
         Ok(())
     }
     
-    fn validate_environment(env_vars: &[crate::job::config::EnvironmentVar]) -> Result<()> {
+    /// Check environment keys are non-empty, well-formed and unique; also run when a config is loaded
+    pub(crate) fn validate_environment(env_vars: &[crate::job::config::EnvironmentVar]) -> Result<()> {
+        let mut seen = std::collections::HashSet::new();
         for env in env_vars {
             if env.key.trim().is_empty() {
                 return Err(ConfigError::Validation(
@@ -92,6 +176,13 @@ impl ConfigValidator {
                     format!("Invalid environment variable key: {}", env.key)
                 ).into());
             }
+            
+            // Otherwise the last entry would silently win
+            if !seen.insert(env.key.as_str()) {
+                return Err(ConfigError::Validation(
+                    format!("Duplicate environment variable key: {}", env.key)
+                ).into());
+            }
         }
         
         Ok(())
@@ -105,13 +196,164 @@ impl ConfigValidator {
             ).into());
         }
         
+        Self::validate_restart_limit(supervision)?;
+        
+        Self::validate_exit_codes(supervision)?;
+        
+        // Validate stop signal
+        if let Some(name) = &supervision.kill_signal {
+            if crate::job::config::parse_signal(name).is_none() {
+                return Err(ConfigError::Validation(
+                    format!("Unknown kill_signal: {}", name)
+                ).into());
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Check that a restart rate limit allows at least one restart; also run when a config is loaded
+    pub(crate) fn validate_restart_limit(supervision: &crate::job::config::SupervisionConfig) -> Result<()> {
+        if supervision.restart_limit_interval_sec > 0 && supervision.restart_limit_burst == 0 {
+            return Err(ConfigError::Validation(
+                "restart_limit_burst must be at least 1 when restart_limit_interval_sec is set".into()
+            ).into());
+        }
+        
+        Ok(())
+    }
+    
+    /// Check that restart exit codes are valid exit statuses; also run when a config is loaded
+    pub(crate) fn validate_exit_codes(supervision: &crate::job::config::SupervisionConfig) -> Result<()> {
+        let mut exit_codes = supervision.restart_on_exit_codes.iter().chain(&supervision.no_restart_exit_codes);
+        if let Some(code) = exit_codes.find(|code| !(0..=255).contains(*code)) {
+            return Err(ConfigError::Validation(
+                format!("Exit code must be between 0 and 255, got {}", code)
+            ).into());
+        }
+        
+        Ok(())
+    }
+    
+    /// Check `nice`, `oom_score_adj` and `cpu_affinity` ranges; also run when a config is loaded
+    pub(crate) fn validate_process(process: &crate::job::config::ProcessConfig) -> Result<()> {
+        if let Some(nice) = process.nice {
+            if !(-20..=19).contains(&nice) {
+                return Err(ConfigError::Validation(
+                    format!("nice must be between -20 and 19, got {}", nice)
+                ).into());
+            }
+        }
+        
+        if let Some(oom_score_adj) = process.oom_score_adj {
+            if !(-1000..=1000).contains(&oom_score_adj) {
+                return Err(ConfigError::Validation(
+                    format!("oom_score_adj must be between -1000 and 1000, got {}", oom_score_adj)
+                ).into());
+            }
+        }
+        
+        if let Some(affinity) = &process.cpu_affinity {
+            let cores = affinity.cores().ok_or_else(|| ConfigError::Validation(
+                format!("Invalid cpu_affinity: {:?}", affinity)
+            ))?;
+            if cores.is_empty() {
+                return Err(ConfigError::Validation("cpu_affinity cannot be empty".into()).into());
+            }
+            
+            let online = crate::process::spawner::online_cpus();
+            if let Some(core) = cores.iter().find(|core| **core >= online) {
+                return Err(ConfigError::Validation(
+                    format!("cpu_affinity core {} is out of range; {} CPUs are online", core, online)
+                ).into());
+            }
+        }
+        
+        Ok(())
+    }
+    
+    fn validate_limits(limits: &crate::job::config::LimitsConfig) -> Result<()> {
+        let named = [
+            ("nofile", limits.nofile),
+            ("nproc", limits.nproc),
+            ("as_bytes", limits.as_bytes),
+            ("core", limits.core),
+            ("cpu_seconds", limits.cpu_seconds),
+        ];
+        
+        for (name, limit) in named {
+            if let Some(limit) = limit {
+                if limit.soft > limit.hard {
+                    return Err(ConfigError::Validation(format!(
+                        "Soft limit for {} ({}) exceeds hard limit ({})",
+                        name, limit.soft, limit.hard
+                    )).into());
+                }
+            }
+        }
+        
         Ok(())
     }
     
     /// Validate a configuration file without loading it
-    pub async fn validate_file<P: AsRef<Path>>(path: P) -> Result<JobConfig> {
+    pub async fn validate_file<P: AsRef<Path>>(path: P, strict: bool) -> Result<JobConfig> {
         let config = JobConfig::from_file(path).await?;
-        Self::validate(&config).await?;
+        Self::validate(&config, strict).await?;
         Ok(config)
     }
+}
+
+/// The user and groups a job runs as: its credentials, or the daemon's own identity
+pub(crate) fn effective_identity(credentials: Option<&Credentials>) -> (Uid, Vec<Gid>) {
+    match credentials {
+        Some(credentials) => (
+            credentials.uid.unwrap_or_else(unistd::geteuid),
+            credentials.groups.clone(),
+        ),
+        None => {
+            let mut groups = unistd::getgroups().unwrap_or_default();
+            groups.push(unistd::getegid());
+            (unistd::geteuid(), groups)
+        }
+    }
+}
+
+/// Whether the daemon could create `path`, or append to it if it exists, judged by the
+/// nearest existing ancestor
+pub(crate) fn check_writable(path: &Path) -> std::result::Result<(), String> {
+    let existing = path.ancestors()
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or_else(|| Path::new("/"));
+    
+    if existing == path && path.is_dir() {
+        return Err("is a directory".to_string());
+    }
+    
+    let c_path = CString::new(existing.as_os_str().as_bytes())
+        .map_err(|_| "path contains a NUL byte".to_string())?;
+    
+    // SAFETY: c_path is a valid NUL-terminated string
+    if unsafe { libc::access(c_path.as_ptr(), libc::W_OK) } != 0 {
+        return Err(format!("{} is not writable", existing.display()));
+    }
+    
+    Ok(())
+}
+
+/// Whether `uid` in `groups` has execute permission on a file, by its mode bits alone
+pub(crate) fn may_execute(metadata: &std::fs::Metadata, uid: Uid, groups: &[Gid]) -> bool {
+    let mode = metadata.mode();
+    
+    // root may execute anything with at least one execute bit
+    if uid.is_root() {
+        return mode & 0o111 != 0;
+    }
+    
+    if metadata.uid() == uid.as_raw() {
+        mode & 0o100 != 0
+    } else if groups.iter().any(|gid| gid.as_raw() == metadata.gid()) {
+        mode & 0o010 != 0
+    } else {
+        mode & 0o001 != 0
+    }
 }
\ No newline at end of file
@@ -0,0 +1,12 @@
+//! NusaLaunchd: a launchd-style init system for Linux
+//!
+//! The daemon and its `nusalaunchd` command line are built on these modules; tests use
+//! them directly, with `process::mock` behind the `test-util` feature.
+
+pub mod cli;
+pub mod control;
+pub mod daemon;
+pub mod event;
+pub mod job;
+pub mod process;
+pub mod util;
\ No newline at end of file
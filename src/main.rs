@@ -1,66 +1,191 @@
 use std::path::PathBuf;
+use std::time::Duration;
 use clap::Parser;
 use tracing::{info, error, warn};
 use tracing_subscriber;
 
-mod job;
-mod process;
-mod event;
-mod cli;
-mod util;
+use nusalaunchd::{cli, control, daemon, event, job, process, util};
 
 use job::JobManager;
 use util::error::Result;
-use cli::{CliArgs, Commands};
+use cli::{CliArgs, Commands, LogFormat};
 
-#[tokio::main]
-async fn main() -> Result<()> {
+fn main() -> Result<()> {
     // Parse command line arguments
-    let args = CliArgs::parse();
+    let mut args = CliArgs::parse();
+    
+    // Relative paths must outlive the daemon's chdir to /
+    args.config_dir = std::path::absolute(&args.config_dir)?;
+    if let Some(Commands::Daemon { daemon_opts }) = &mut args.command {
+        daemon_opts.make_absolute()?;
+    }
+    
+    // Detach before the runtime starts its threads; a fork keeps only the calling one
+    let detached = match &args.command {
+        Some(Commands::Daemon { daemon_opts }) => detach(&args, daemon_opts)?,
+        None => detach(&args, &cli::args::DaemonOptions::default())?,
+        _ => None,
+    };
+    let (pid_file, log_file) = match detached {
+        Some((pid_file, log_file)) => (Some(pid_file), Some(log_file)),
+        None => (None, None),
+    };
     
     // Initialize logging
-    init_logging(&args.log_level.to_string());
+    let log_handle = init_logging(&args.effective_log_level().to_string(), args.log_format, log_file);
+    cli::output::init_color();
     
+    tokio::runtime::Runtime::new()?.block_on(run(args, log_handle, pid_file))
+}
+
+async fn run(
+    args: CliArgs,
+    log_handle: daemon::LogHandle,
+    pid_file: Option<daemon::PidFile>,
+) -> Result<()> {
     info!("Starting NusaLaunchd v{}", env!("CARGO_PKG_VERSION"));
     
     match args.command {
-        Some(Commands::Daemon { daemon_opts }) => {
-            run_daemon(&args, daemon_opts).await
+        Some(Commands::Daemon { ref daemon_opts }) => {
+            run_daemon(&args, daemon_opts, log_handle, pid_file).await
         }
         Some(Commands::Job { job_command }) => {
             handle_job_command(job_command, &args).await
         }
         Some(Commands::Validate { path, strict }) => {
-            validate_config(path, strict).await
+            validate_config(path, strict, &args.format).await
+        }
+        Some(Commands::Status { detailed, watch }) => {
+            show_status(&args, detailed, watch).await
         }
-        Some(Commands::Status { detailed, watch, format }) => {
-            show_status(detailed, watch, format).await
+        Some(Commands::Export { path, output }) => {
+            export_config(path, output, &args.format).await
         }
         Some(Commands::Example { example_type, output }) => {
             generate_example(example_type, output).await
         }
         Some(Commands::Socket { socket_command }) => {
-            handle_socket_command(socket_command).await
+            handle_socket_command(socket_command, &args).await
+        }
+        Some(Commands::Reload) => {
+            reload_daemon(&args).await
+        }
+        Some(Commands::Summary) => {
+            show_summary(&args).await
+        }
+        Some(Commands::Events) => {
+            follow_events(&args).await
         }
         None => {
             // Default command: run as daemon
             info!("No command specified, running as daemon");
-            run_daemon(&args, cli::args::DaemonOptions::default()).await
+            run_daemon(&args, &cli::args::DaemonOptions::default(), log_handle, pid_file).await
         }
     }
 }
 
-async fn run_daemon(args: &CliArgs, _daemon_opts: cli::args::DaemonOptions) -> Result<()> {
+/// Daemonize unless told to stay in the foreground or not to run jobs at all
+///
+/// The PID file is locked first so a second instance still fails on the
+/// terminal; the daemon inherits the lock and records its own PID. Returns the
+/// PID file and the log file standing in for stderr.
+fn detach(
+    args: &CliArgs,
+    daemon_opts: &cli::args::DaemonOptions,
+) -> Result<Option<(daemon::PidFile, std::fs::File)>> {
+    if args.foreground || args.dry_run || daemon_opts.validate_only {
+        return Ok(None);
+    }
+    
+    let mut pid_file = daemon::PidFile::acquire(&daemon_opts.pid_file)?;
+    let log_file = daemon::daemonize::open_log_file(&daemon_opts.log_file)?;
+    
+    daemon::daemonize::daemonize()?;
+    pid_file.update()?;
+    
+    Ok(Some((pid_file, log_file)))
+}
+
+async fn run_daemon(
+    args: &CliArgs,
+    daemon_opts: &cli::args::DaemonOptions,
+    log_handle: daemon::LogHandle,
+    pid_file: Option<daemon::PidFile>,
+) -> Result<()> {
     info!("Starting NusaLaunchd daemon");
     
+    // Refuse to run next to another daemon; the file goes away when this returns
+    let _pid_file = match pid_file {
+        Some(pid_file) => Some(pid_file),
+        None if daemon_opts.validate_only || args.dry_run => None,
+        None => Some(daemon::PidFile::acquire(&daemon_opts.pid_file)?),
+    };
+    
+    if !daemon_opts.validate_only && !args.dry_run {
+        daemon::dirs::ensure_dir(&daemon_opts.runtime_dir, daemon::dirs::RUNTIME_DIR_MODE)?;
+        daemon::dirs::ensure_dir(&daemon_opts.state_dir, daemon::dirs::STATE_DIR_MODE)?;
+    }
+    
     // Create job manager
-    let (job_manager, event_rx) = JobManager::new().await?;
+    let (mut job_manager, event_rx) = JobManager::with_channels(job::ChannelOptions {
+        event_capacity: daemon_opts.event_capacity,
+        restart_capacity: daemon_opts.restart_capacity,
+        event_overflow: daemon_opts.event_overflow,
+    }).await?;
+    
+    // Jobs are started together once all are loaded, so dependencies come first
+    job_manager.set_auto_start(false);
+    job_manager.set_state_dir(daemon_opts.state_dir.clone());
+    job_manager.set_runtime_dir(daemon_opts.runtime_dir.clone());
+    job_manager.set_config_dir(args.config_dir.clone());
+    job_manager.set_recursive_config(daemon_opts.recursive_config);
+    job_manager.set_target(daemon_opts.target.clone());
+    job_manager.set_max_jobs(daemon_opts.max_jobs);
+    
+    // Validation only loads the jobs: no daemon config is applied and no event log written
+    if daemon_opts.validate_only {
+        tokio::spawn(event::EventDispatcher::process_events(event_rx, None));
+        let (loaded, failed) = job_manager.load_directory(&args.config_dir).await;
+        println!("Validated {} jobs ({} failed)", loaded + failed, failed);
+        if failed > 0 {
+            return Err(util::error::NusaError::System(
+                format!("{} job(s) failed validation", failed)
+            ));
+        }
+        return Ok(());
+    }
+    
+    // Apply the daemon's own config file, if any; kept for runtime reloads
+    let daemon_config = match &daemon_opts.daemon_config {
+        Some(path) => {
+            let config = daemon::DaemonConfig::from_file(path).await?;
+            let handle = daemon::DaemonConfigHandle::new(
+                path.clone(),
+                config,
+                log_handle,
+                job_manager.clone(),
+            );
+            handle.apply().await?;
+            Some(handle)
+        }
+        None => None,
+    };
+    
+    // Open the event log, if requested
+    let event_log = match &daemon_opts.event_log_path {
+        Some(path) => Some(util::logwriter::RotatingWriter::open(
+            path,
+            daemon_opts.event_log_max_size,
+            daemon_opts.event_log_max_files,
+        )?),
+        None => None,
+    };
     
     // Start event processor
-    let event_handle = tokio::spawn(event::EventDispatcher::process_events(event_rx));
+    let event_handle = tokio::spawn(event::EventDispatcher::process_events(event_rx, event_log));
     
     // Load jobs from config directory
-    load_jobs_from_directory(&job_manager, &args.config_dir).await?;
+    job_manager.load_directory(&args.config_dir).await;
     
     if args.dry_run {
         info!("Dry run mode - not starting jobs");
@@ -68,168 +193,442 @@ async fn run_daemon(args: &CliArgs, _daemon_opts: cli::args::DaemonOptions) -> R
         return Ok(());
     }
     
-    if args.foreground {
-        info!("Running in foreground mode");
-        
-        // Start signal handlers
-        setup_signal_handlers(job_manager.clone()).await?;
-        
-        // Keep daemon running
-        tokio::select! {
-            _ = event_handle => {
-                warn!("Event processor stopped");
-            }
-            _ = tokio::signal::ctrl_c() => {
-                info!("Received Ctrl+C, shutting down");
-            }
+    job_manager.set_auto_start(true);
+    match job::enabled::enabled_labels(&daemon_opts.state_dir, &daemon_opts.target)? {
+        Some(enabled) => job_manager.start_enabled(&enabled).await?,
+        None => {
+            info!("Target '{}' has no enabled jobs set up; starting all keep_alive jobs", daemon_opts.target);
+            job_manager.start_keep_alive().await?;
         }
-    } else {
-        info!("Daemon mode - use control tool to manage jobs");
-        // TODO: Implement daemonization
     }
     
-    Ok(())
-}
-
-async fn load_jobs_from_directory(job_manager: &JobManager, config_dir: &PathBuf) -> Result<()> {
-    info!("Loading jobs from: {}", config_dir.display());
+    // Serve the control socket in the runtime dir, unless the daemon config moves it
+    let configured_socket = match &daemon_config {
+        Some(handle) => handle.current().await.control_socket,
+        None => None,
+    };
+    let socket_path = configured_socket.unwrap_or_else(|| daemon_opts.runtime_dir.join(control::SOCKET_NAME));
+    let control_server = control::ControlServer::bind(
+        socket_path,
+        job_manager.clone(),
+        daemon_config.clone(),
+    )?;
+    if let Some(group) = &daemon_opts.control_group {
+        control_server.set_group(group)?;
+    }
+    tokio::spawn(control_server.run());
     
-    if !config_dir.exists() {
-        warn!("Config directory does not exist: {}", config_dir.display());
-        return Ok(());
+    if let Some(addr) = daemon_opts.metrics_addr {
+        let metrics_server = daemon::metrics::MetricsServer::bind(addr, job_manager.clone())?;
+        tokio::spawn(metrics_server.run());
     }
     
-    let mut loaded = 0;
-    let mut failed = 0;
-    
-    match std::fs::read_dir(config_dir) {
-        Ok(entries) => {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                
-                if path.extension().and_then(|s| s.to_str()) == Some("toml") {
-                    match job::config::JobConfig::from_file(&path).await {
-                        Ok(config) => {
-                            if let Err(e) = job_manager.load_job(config).await {
-                                error!("Failed to load job from {}: {}", path.display(), e);
-                                failed += 1;
-                            } else {
-                                loaded += 1;
-                            }
-                        }
-                        Err(e) => {
-                            error!("Failed to parse config file {}: {}", path.display(), e);
-                            failed += 1;
-                        }
-                    }
-                }
-            }
+    // Follow edits to the config directory; the watch ends when this is dropped
+    let _config_watcher = if daemon_opts.watch_config {
+        Some(daemon::watch::ConfigWatcher::start(&args.config_dir, job_manager.clone())?)
+    } else {
+        None
+    };
+    
+    if args.foreground {
+        info!("Running in foreground mode");
+    } else {
+        info!("Running as daemon (pid {}) - use control tool to manage jobs", std::process::id());
+    }
+    
+    // Start signal handlers
+    let shutdown = setup_signal_handlers(
+        job_manager.clone(),
+        daemon_opts.shutdown_timeout.map(Duration::from_secs),
+        args.config_dir.clone(),
+    ).await?;
+    
+    // Keep daemon running until the jobs have been shut down
+    tokio::select! {
+        _ = event_handle => {
+            warn!("Event processor stopped");
         }
-        Err(e) => {
-            error!("Failed to read config directory: {}", e);
+        _ = shutdown => {
+            info!("Shutdown complete");
         }
     }
     
-    info!("Loaded {} jobs ({} failed)", loaded, failed);
     Ok(())
 }
 
 async fn show_daemon_status(job_manager: &JobManager) {
-    let jobs = job_manager.list_jobs().await;
+    let mut jobs = job_manager.list_jobs().await;
+    jobs.sort_by(|a, b| a.label.cmp(&b.label));
     
     println!("NusaLaunchd Daemon Status");
     println!("=========================");
     println!("Total jobs: {}", jobs.len());
-    
-    for job in jobs {
-        let state_str = match job.state {
-            job::JobState::Running => "✓".to_string(),
-            job::JobState::Stopped => "✗".to_string(),
-            job::JobState::Failed(ref reason) => format!("⚠ ({})", reason),
-            _ => "?".to_string(),
-        };
-        
-        println!("  {} {} [{}]", state_str, job.label, job.state);
-    }
+    print!("{}", cli::status::render_table(&jobs, false));
 }
 
 async fn handle_job_command(
     job_command: cli::args::JobCommands,
-    _args: &CliArgs,
+    args: &CliArgs,
 ) -> Result<()> {
+    use cli::args::JobCommands;
+    
+    let mut failed = Vec::new();
+    
     match job_command {
-        cli::args::JobCommands::Start { labels, wait, timeout } => {
-            info!("Starting jobs: {:?}", labels);
-            // TODO: Implement job starting
-            Ok(())
+        JobCommands::Start { labels, wait } => {
+            let timeout = Duration::from_secs(args.timeout.unwrap_or(DEFAULT_WAIT_TIMEOUT_SEC));
+            let mut client = connect_daemon(args).await?;
+            for label in expand_remote_labels(&mut client, &labels).await? {
+                let result = start_remote_job(&mut client, &label, wait, timeout).await;
+                report_job_result(label, result, &mut failed);
+            }
         }
-        _ => {
-            warn!("Job command not fully implemented yet");
-            Ok(())
+        JobCommands::Stop { labels, force } => {
+            let timeout = args.timeout;
+            let mut client = connect_daemon(args).await?;
+            
+            // The daemon answers once the job is gone, which may take the whole grace period
+            client.set_timeout(timeout.map(|secs| Duration::from_secs(secs) + KILL_REPLY_MARGIN));
+            for label in expand_remote_labels(&mut client, &labels).await? {
+                let request = if force {
+                    control::ControlRequest::Kill { label: label.clone() }
+                } else {
+                    control::ControlRequest::Stop { label: label.clone(), timeout_sec: timeout }
+                };
+                let result = client.call::<Option<job::JobStatus>>(&request).await
+                    .map(|_| if force { "killed".to_string() } else { "stopped".to_string() });
+                report_job_result(label, result, &mut failed);
+            }
+        }
+        JobCommands::Restart { labels, skip_if_stopped } => {
+            let mut client = connect_daemon(args).await?;
+            for label in expand_remote_labels(&mut client, &labels).await? {
+                let result = restart_remote_job(&mut client, &label, skip_if_stopped).await;
+                report_job_result(label, result, &mut failed);
+            }
         }
+        JobCommands::Kill { labels, signal } => {
+            let signal = job::config::parse_signal(&signal).ok_or_else(|| {
+                util::error::NusaError::System(format!("Unknown signal: {}", signal))
+            })?;
+            let mut client = connect_daemon(args).await?;
+            for label in expand_remote_labels(&mut client, &labels).await? {
+                let request = control::ControlRequest::Signal {
+                    label: label.clone(),
+                    signal: signal.as_str().to_string(),
+                };
+                let result = client.call::<control::protocol::SignalResult>(&request).await
+                    .map(|sent| format!("sent {} to pid {}", signal, sent.pid));
+                report_job_result(label, result, &mut failed);
+            }
+        }
+        JobCommands::Unload { labels } => {
+            let mut client = connect_daemon(args).await?;
+            for label in expand_remote_labels(&mut client, &labels).await? {
+                let request = control::ControlRequest::Unload { label: label.clone() };
+                let result = client.call::<()>(&request).await.map(|()| "unloaded".to_string());
+                report_job_result(label, result, &mut failed);
+            }
+        }
+        JobCommands::Check { labels } => {
+            let mut client = connect_daemon(args).await?;
+            let mut checks = Vec::new();
+            for label in expand_remote_labels(&mut client, &labels).await? {
+                match client.call::<process::check::SpawnCheck>(&control::ControlRequest::Check { label: label.clone() }).await {
+                    Ok(check) => checks.push(check),
+                    Err(e) => report_job_result(label, Err(e), &mut failed),
+                }
+            }
+            print!("{}", cli::check::render(&checks, &args.format)?);
+            failed.extend(checks.iter().filter(|check| !check.ok()).map(|check| check.label.clone()));
+        }
+        JobCommands::History { label } => {
+            let mut client = connect_daemon(args).await?;
+            let history: Vec<job::RestartRecord> = client
+                .call(&control::ControlRequest::History { label })
+                .await?;
+            print!("{}", cli::output::render_history(&history, &args.format)?);
+        }
+        JobCommands::Status { label, show_config, show_tree } => {
+            let mut client = connect_daemon(args).await?;
+            let jobs: Vec<job::JobStatus> = client
+                .call(&control::ControlRequest::Status { label })
+                .await?;
+            print!("{}", cli::output::render_job_status(&jobs, &args.format, show_config)?);
+            if show_tree {
+                let processes = process::tree::read_processes(std::path::Path::new("/proc"));
+                for status in &jobs {
+                    match status.pid.and_then(|pid| process::tree::build_tree(&processes, pid)) {
+                        Some(tree) => print!("\n{}:\n{}", status.label, process::tree::render_tree(&tree)),
+                        None => println!("\n{}: not running", status.label),
+                    }
+                }
+            }
+        }
+        JobCommands::List { state_filter, loaded_only, running_only, failed_only, since_boot } => {
+            let filter = cli::status::JobFilter {
+                state: state_filter,
+                loaded: loaded_only,
+                running: running_only,
+                failed: failed_only,
+                since_boot,
+            };
+            filter.validate()?;
+            
+            let mut client = connect_daemon(args).await?;
+            let jobs: Vec<job::JobStatus> = client
+                .call(&control::ControlRequest::Status { label: None })
+                .await?;
+            let jobs = filter.apply(jobs)?;
+            print!("{}", cli::output::render_jobs(&jobs, &args.format, false)?);
+        }
+        JobCommands::Logs { label, lines, follow, since, until } => {
+            let mut client = connect_daemon(args).await?;
+            let status = remote_job_status(&mut client, &label).await?;
+            cli::logs::show(&status.config, lines, follow, since.as_deref(), until.as_deref()).await?;
+        }
+        JobCommands::Reload { labels, restart } => {
+            let mut client = connect_daemon(args).await?;
+            for label in labels {
+                let result = client
+                    .call::<job::ReloadOutcome>(&control::ControlRequest::Reload { label: Some(label.clone()), restart })
+                    .await
+                    .map(|outcome| outcome.to_string());
+                report_job_result(label, result, &mut failed);
+            }
+        }
+        JobCommands::Enable { labels, target, now, state_dir } => {
+            for label in &labels {
+                let result = job::enabled::enable(&state_dir, &target, label)
+                    .map(|added| if added { format!("enabled for {}", target) } else { format!("already enabled for {}", target) });
+                report_job_result(label.clone(), result, &mut failed);
+            }
+            
+            if now {
+                let mut client = connect_daemon(args).await?;
+                for label in labels {
+                    let result = start_remote_job(&mut client, &label, false, Duration::from_secs(0)).await;
+                    report_job_result(label, result, &mut failed);
+                }
+            }
+        }
+        JobCommands::Disable { labels, stop, state_dir } => {
+            for label in &labels {
+                let result = job::enabled::disable(&state_dir, label)
+                    .map(|targets| if targets.is_empty() { "not enabled".to_string() } else { format!("disabled for {}", targets.join(", ")) });
+                report_job_result(label.clone(), result, &mut failed);
+            }
+            
+            if stop {
+                let mut client = connect_daemon(args).await?;
+                for label in labels {
+                    let request = control::ControlRequest::Stop { label: label.clone(), timeout_sec: None };
+                    let result = client.call::<Option<job::JobStatus>>(&request).await
+                        .map(|_| "stopped".to_string());
+                    report_job_result(label, result, &mut failed);
+                }
+            }
+        }
+    }
+    
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(util::error::NusaError::System(format!("Failed: {}", failed.join(", "))))
     }
 }
 
-async fn validate_config(path: PathBuf, strict: bool) -> Result<()> {
-    info!("Validating config: {}", path.display());
+/// How long `job start --wait` waits without `--timeout`
+const DEFAULT_WAIT_TIMEOUT_SEC: u64 = 30;
+
+/// How long to wait for the daemon's socket without `--timeout`
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time allowed past a stop's grace period for the SIGKILL and the daemon's answer
+const KILL_REPLY_MARGIN: Duration = Duration::from_secs(5);
+
+/// Connect to the daemon's control socket, waiting up to `--timeout` for it and for
+/// each of its answers
+async fn connect_daemon(args: &CliArgs) -> Result<control::ControlClient> {
+    let timeout = args.timeout.map(Duration::from_secs);
+    let mut client = control::ControlClient::connect(
+        &args.control_socket,
+        timeout.unwrap_or(DEFAULT_CONNECT_TIMEOUT),
+    ).await?;
+    client.set_timeout(timeout);
+    Ok(client)
+}
+
+/// Start a job through the daemon, optionally waiting until it is running
+async fn start_remote_job(
+    client: &mut control::ControlClient,
+    label: &str,
+    wait: bool,
+    timeout: Duration,
+) -> Result<String> {
+    let started: control::protocol::StartResult = client
+        .call(&control::ControlRequest::Start { label: label.to_string() })
+        .await?;
+    
+    if !wait {
+        return Ok(match started.pid {
+            Some(pid) => format!("started [PID: {}]", pid),
+            None => "start requested".to_string(),
+        });
+    }
     
-    if path.is_dir() {
-        // Validate all .toml files in directory
-        let mut valid = 0;
-        let mut invalid = 0;
+    let deadline = tokio::time::Instant::now() + timeout;
+    
+    loop {
+        let status = remote_job_status(client, label).await?;
         
-        match std::fs::read_dir(&path) {
-            Ok(entries) => {
-                for entry in entries.flatten() {
-                    let file_path = entry.path();
-                    if file_path.extension().and_then(|s| s.to_str()) == Some("toml") {
-                        match job::config::JobConfig::from_file(&file_path).await {
-                            Ok(config) => {
-                                println!("✓ {}: {}", file_path.display(), config.label);
-                                valid += 1;
-                            }
-                            Err(e) => {
-                                println!("✗ {}: {}", file_path.display(), e);
-                                invalid += 1;
-                            }
-                        }
-                    }
-                }
+        match status.state {
+            job::JobState::Running => {
+                let pid = status.pid.map_or_else(|| "?".to_string(), |pid| pid.to_string());
+                return Ok(format!("running [PID: {}]", pid));
             }
-            Err(e) => {
-                error!("Failed to read directory: {}", e);
-                return Err(util::error::NusaError::System(e.to_string()));
+            job::JobState::Failed(reason) => {
+                return Err(util::error::NusaError::Control(reason));
             }
+            job::JobState::Skipped(reason) => {
+                return Ok(format!("skipped ({})", reason));
+            }
+            _ => {}
         }
         
-        println!("\nValidation complete: {} valid, {} invalid", valid, invalid);
-        if strict && invalid > 0 {
-            return Err(util::error::NusaError::System("Strict validation failed".into()));
+        if tokio::time::Instant::now() >= deadline {
+            return Err(util::error::NusaError::Control(format!(
+                "not running after {}s (state: {})",
+                timeout.as_secs(),
+                status.state
+            )));
         }
+        
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+}
+
+/// Restart a job through the daemon
+async fn restart_remote_job(
+    client: &mut control::ControlClient,
+    label: &str,
+    skip_if_stopped: bool,
+) -> Result<String> {
+    if skip_if_stopped && remote_job_status(client, label).await?.state != job::JobState::Running {
+        return Ok("skipped (not running)".to_string());
+    }
+    
+    client
+        .call::<Option<job::JobStatus>>(&control::ControlRequest::Restart { label: label.to_string() })
+        .await?;
+    Ok("restarted".to_string())
+}
+
+/// Expand label patterns such as `web-*` against the jobs loaded in the daemon
+async fn expand_remote_labels(client: &mut control::ControlClient, labels: &[String]) -> Result<Vec<String>> {
+    let loaded = if labels.iter().any(|label| cli::pattern::is_pattern(label)) {
+        let jobs: Vec<job::JobStatus> = client
+            .call(&control::ControlRequest::Status { label: None })
+            .await?;
+        jobs.into_iter().map(|status| status.label).collect()
     } else {
-        // Validate single file
-        match job::config::JobConfig::from_file(&path).await {
-            Ok(config) => {
-                println!("✓ Configuration is valid");
-                println!("  Label: {}", config.label);
-                println!("  Program: {}", config.program.path.display());
-                println!("  Supervision: keep_alive={}", config.supervision.keep_alive);
-            }
-            Err(e) => {
-                println!("✗ Configuration is invalid: {}", e);
-                if strict {
-                    return Err(e);
-                }
-            }
+        Vec::new()
+    };
+    
+    cli::pattern::expand_labels(labels, &loaded)
+}
+
+async fn remote_job_status(client: &mut control::ControlClient, label: &str) -> Result<job::JobStatus> {
+    let jobs: Vec<job::JobStatus> = client
+        .call(&control::ControlRequest::Status { label: Some(label.to_string()) })
+        .await?;
+    
+    jobs.into_iter()
+        .next()
+        .ok_or_else(|| util::error::NusaError::JobNotFound(label.to_string()))
+}
+
+fn report_job_result(label: String, result: Result<String>, failed: &mut Vec<String>) {
+    match result {
+        Ok(outcome) => println!("✓ {}: {}", label, outcome),
+        Err(e) => {
+            println!("✗ {}: {}", label, e);
+            failed.push(label);
         }
     }
+}
+
+async fn validate_config(path: PathBuf, strict: bool, format: &cli::args::OutputFormat) -> Result<()> {
+    info!("Validating config: {}", path.display());
+    
+    let all_valid = if path.is_dir() {
+        // Validate all config files in directory
+        let results = cli::validate::check_dir(&path, strict).await.map_err(|e| {
+            error!("Failed to read directory: {}", e);
+            e
+        })?;
+        print!("{}", cli::validate::render_dir(&results, format)?);
+        results.iter().all(|result| result.valid)
+    } else {
+        // Validate single file, listing each job if it defines several
+        let results = cli::validate::ValidationResult::check_file(&path, strict).await;
+        match results.as_slice() {
+            [result] => print!("{}", cli::validate::render_file(result, format)?),
+            _ => print!("{}", cli::validate::render_dir(&results, format)?),
+        }
+        results.iter().all(|result| result.valid)
+    };
+    
+    if strict && !all_valid {
+        return Err(util::error::NusaError::System("Strict validation failed".into()));
+    }
     
     Ok(())
 }
 
-async fn show_status(_detailed: bool, _watch: bool, _format: cli::args::OutputFormat) -> Result<()> {
-    // TODO: Implement status display
-    println!("Status command not fully implemented yet");
+async fn show_status(args: &CliArgs, detailed: bool, watch: bool) -> Result<()> {
+    let format = &args.format;
+    let mut client = connect_daemon(args).await?;
+    
+    loop {
+        let jobs: Vec<job::JobStatus> = client
+            .call(&control::ControlRequest::Status { label: None })
+            .await?;
+        let output = cli::output::render_jobs(&jobs, format, detailed)?;
+        
+        if !watch {
+            print!("{}", output);
+            return Ok(());
+        }
+        
+        // Clear the screen and redraw from the top
+        print!("\x1B[2J\x1B[H{}", output);
+        
+        tokio::select! {
+            _ = tokio::time::sleep(std::time::Duration::from_secs(1)) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+async fn export_config(
+    path: PathBuf,
+    output: Option<PathBuf>,
+    format: &cli::args::OutputFormat,
+) -> Result<()> {
+    // Check the file as the daemon would load it, but print the jobs as written
+    job::config::JobConfigFile::from_file(&path).await?;
+    let file = job::config::JobConfigFile::read(&path).await?;
+    let exported = cli::export::render_file(&file, format)?;
+    
+    if let Some(output_path) = output {
+        std::fs::write(&output_path, &exported)
+            .map_err(|e| util::error::NusaError::System(format!("Failed to write file: {}", e)))?;
+        println!("Config written to: {}", output_path.display());
+    } else {
+        print!("{}", exported);
+    }
+    
     Ok(())
 }
 
@@ -251,7 +650,7 @@ async fn generate_example(
             "# Cron-like service example\nlabel = \"cron-job\"\n\n[program]\npath = \"/usr/bin/bash\"\narguments = [\"-c\", \"echo 'Hello from cron'\"]\n"
         }
         cli::args::ExampleType::Socket => {
-            "# Socket-activated service example\nlabel = \"socket-service\"\n\n[program]\npath = \"/usr/bin/echo\"\n# Socket configuration will be added in Week 3\n"
+            "# Socket-activated service example\n# Started on the first connection to port 8080; the listening socket is fd 3 (LISTEN_FDS=1)\nlabel = \"socket-service\"\n\n[program]\npath = \"/usr/local/bin/my-service\"\n\n[[sockets]]\nlisten = \"127.0.0.1:8080\"\ntype = \"stream\"\n"
         }
     };
     
@@ -266,52 +665,160 @@ async fn generate_example(
     Ok(())
 }
 
-async fn handle_socket_command(
-    _socket_command: cli::args::SocketCommands,
-) -> Result<()> {
-    // TODO: Implement socket commands (Week 3)
-    println!("Socket commands will be implemented in Week 3");
+/// Have the daemon rescan its config directory and print what changed
+async fn reload_daemon(args: &CliArgs) -> Result<()> {
+    let mut client = connect_daemon(args).await?;
+    let summary: job::RescanSummary = client
+        .call(&control::ControlRequest::Reload { label: None, restart: false })
+        .await?;
+    
+    match args.format {
+        cli::args::OutputFormat::Table | cli::args::OutputFormat::Plain => println!("Reloaded: {}", summary),
+        _ => print!("{}", cli::output::encode(&summary, &args.format)?),
+    }
+    
     Ok(())
 }
 
-async fn setup_signal_handlers(job_manager: job::JobManager) -> Result<()> {
-    use signal_hook::consts::{SIGTERM, SIGINT};
+/// Print the daemon's job events as they happen, until interrupted or the daemon goes away
+async fn follow_events(args: &CliArgs) -> Result<()> {
+    let mut client = connect_daemon(args).await?;
+    client.subscribe_events().await?;
+    
+    loop {
+        let record = tokio::select! {
+            record = client.next_event() => record?,
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        };
+        let Some(record) = record else {
+            return Err(util::error::NusaError::System("Daemon closed the event stream".into()));
+        };
+        
+        // JSON and YAML print each record as one JSON line, like the event log
+        match args.format {
+            cli::OutputFormat::Table | cli::OutputFormat::Plain => {
+                let timestamp = record["timestamp"].as_str()
+                    .and_then(|timestamp| chrono::DateTime::parse_from_rfc3339(timestamp).ok())
+                    .map(|timestamp| timestamp.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_default();
+                println!("{}  {}", timestamp, record["message"].as_str().unwrap_or_default());
+            }
+            _ => println!("{}", record),
+        }
+    }
+}
+
+/// Print the daemon's job counts, restarts and uptime
+async fn show_summary(args: &CliArgs) -> Result<()> {
+    let mut client = connect_daemon(args).await?;
+    let summary: job::DaemonSummary = client.call(&control::ControlRequest::Summary).await?;
+    print!("{}", cli::output::render_summary(&summary, &args.format)?);
+    Ok(())
+}
+
+async fn handle_socket_command(socket_command: cli::args::SocketCommands, args: &CliArgs) -> Result<()> {
+    match socket_command {
+        cli::args::SocketCommands::Status => {
+            let mut client = connect_daemon(args).await?;
+            let sockets: Vec<job::sockets::SocketStatus> = client.call(&control::ControlRequest::Sockets).await?;
+            print!("{}", cli::output::render_sockets(&sockets, &args.format)?);
+        }
+        _ => {
+            warn!("Socket command not fully implemented yet");
+        }
+    }
+    Ok(())
+}
+
+/// Stop all jobs on SIGTERM/SIGINT; the returned task completes once they are down
+async fn setup_signal_handlers(
+    job_manager: job::JobManager,
+    grace: Option<Duration>,
+    config_dir: PathBuf,
+) -> Result<tokio::task::JoinHandle<()>> {
+    use signal_hook::consts::{SIGHUP, SIGTERM, SIGINT};
     use signal_hook_tokio::Signals;
     
-    let mut signals = Signals::new(&[SIGTERM, SIGINT])
+    let mut signals = Signals::new(&[SIGTERM, SIGINT, SIGHUP])
         .map_err(|e| util::error::NusaError::System(format!("Failed to setup signals: {}", e)))?;
     
     let handle = signals.handle();
     
-    tokio::spawn(async move {
+    let task = tokio::spawn(async move {
         while let Some(signal) = signals.next().await {
             match signal {
                 SIGTERM | SIGINT => {
                     info!("Received signal {}, shutting down gracefully", signal);
-                    // TODO: Graceful shutdown of all jobs
+                    job_manager.shutdown_all(grace).await;
                     break;
                 }
+                SIGHUP => {
+                    info!("Received SIGHUP, reloading job configs");
+                    if let Err(e) = job_manager.rescan_directory(&config_dir).await {
+                        error!("Failed to rescan {}: {}", config_dir.display(), e);
+                    }
+                }
                 _ => {}
             }
         }
         handle.close();
     });
     
-    Ok(())
+    Ok(task)
 }
 
-fn init_logging(level: &str) {
-    let filter = match level.to_lowercase().as_str() {
-        "trace" => tracing::Level::TRACE,
-        "debug" => tracing::Level::DEBUG,
-        "info" => tracing::Level::INFO,
-        "warn" => tracing::Level::WARN,
-        "error" => tracing::Level::ERROR,
-        _ => tracing::Level::INFO,
+fn init_logging(
+    level: &str,
+    format: LogFormat,
+    log_file: Option<std::fs::File>,
+) -> daemon::LogHandle {
+    use tracing_subscriber::fmt::writer::BoxMakeWriter;
+    use tracing_subscriber::prelude::*;
+    
+    let filter = daemon::config::parse_log_level(level)
+        .unwrap_or(tracing_subscriber::filter::LevelFilter::INFO);
+    
+    // Wrap the level filter so it can be changed on daemon config reload
+    let (filter, handle) = tracing_subscriber::reload::Layer::new(filter);
+    
+    // A detached daemon has no stderr left and logs to its log file instead
+    let ansi = log_file.is_none();
+    let writer = match log_file {
+        Some(file) => BoxMakeWriter::new(std::sync::Mutex::new(file)),
+        None => BoxMakeWriter::new(std::io::stderr),
     };
     
-    tracing_subscriber::fmt()
-        .with_max_level(filter)
-        .with_target(false)
+    // Only one of these is set; JSON output carries the fields of the
+    // current span (e.g. the job label) as a structured "span" object
+    let (text, json) = match format {
+        LogFormat::Text => (
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .with_writer(writer)
+                    .with_ansi(ansi)
+                    .with_target(false),
+            ),
+            None,
+        ),
+        LogFormat::Json => (
+            None,
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .with_writer(writer)
+                    .json()
+                    .flatten_event(true)
+                    .with_current_span(true)
+                    .with_span_list(false)
+                    .with_target(false),
+            ),
+        ),
+    };
+    
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(text)
+        .with(json)
         .init();
+    
+    handle
 }
\ No newline at end of file
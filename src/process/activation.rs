@@ -0,0 +1,180 @@
+//! Passing activation sockets to a job the way systemd does (`sd_listen_fds`)
+
+use std::collections::BTreeMap;
+use std::ffi::{CString, OsStr, OsString};
+use std::io;
+use std::os::fd::RawFd;
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Descriptor number of the first passed socket (`SD_LISTEN_FDS_START`)
+pub const LISTEN_FDS_START: RawFd = 3;
+
+const LISTEN_PID_PREFIX: &[u8] = b"LISTEN_PID=";
+
+/// Room for `LISTEN_PID=`, any PID, and the terminating NUL
+const LISTEN_PID_ENTRY_LEN: usize = 32;
+
+/// An open socket to pass to a job, with its name for `LISTEN_FDNAMES`
+#[derive(Debug, Clone)]
+pub struct ListenFd {
+    pub fd: RawFd,
+    pub name: String,
+}
+
+/// The program, arguments and environment of a socket-activated job, prepared before fork
+///
+/// `LISTEN_PID` has to be the child's own PID, which only exists after fork. std builds
+/// the child's environment before forking and installs it after the `pre_exec` hooks,
+/// so a hook cannot add the variable; instead `exec` fills it in and execs the program
+/// itself, using nothing but async-signal-safe calls.
+pub struct ActivationExec {
+    program: CString,
+    _argv: Vec<CString>,
+    argv_ptrs: Vec<*const libc::c_char>,
+    _envp: Vec<CString>,
+    /// `LISTEN_PID=` followed by NULs, the last entry of `envp_ptrs`
+    listen_pid: Vec<u8>,
+    envp_ptrs: Vec<*const libc::c_char>,
+    fds: Vec<RawFd>,
+}
+
+// SAFETY: the pointers only refer to heap buffers owned by the struct, which are never reallocated
+unsafe impl Send for ActivationExec {}
+unsafe impl Sync for ActivationExec {}
+
+impl ActivationExec {
+    /// Capture what `command` would exec, plus `LISTEN_FDS` and `LISTEN_FDNAMES` for `sockets`
+    ///
+    /// `arg0` replaces the program as argv[0]; `clear_env` mirrors `Command::env_clear`,
+    /// which std does not report back.
+    pub fn prepare(command: &Command, arg0: Option<&str>, clear_env: bool, sockets: &[ListenFd]) -> io::Result<Self> {
+        let mut env: BTreeMap<OsString, OsString> = if clear_env {
+            BTreeMap::new()
+        } else {
+            std::env::vars_os().collect()
+        };
+        for (key, value) in command.get_envs() {
+            match value {
+                Some(value) => env.insert(key.to_os_string(), value.to_os_string()),
+                None => env.remove(key),
+            };
+        }
+        env.remove(OsStr::new("LISTEN_PID"));
+        env.insert("LISTEN_FDS".into(), sockets.len().to_string().into());
+        let names: Vec<&str> = sockets.iter().map(|socket| socket.name.as_str()).collect();
+        env.insert("LISTEN_FDNAMES".into(), names.join(":").into());
+        
+        let program = resolve_program(command.get_program(), env.get(OsStr::new("PATH")).map(OsString::as_os_str))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "program not found in PATH"))?;
+        
+        let mut argv = vec![cstring(arg0.map_or(command.get_program(), OsStr::new).as_bytes())?];
+        for arg in command.get_args() {
+            argv.push(cstring(arg.as_bytes())?);
+        }
+        
+        let mut envp = Vec::with_capacity(env.len() + 1);
+        for (key, value) in env {
+            let mut entry = key.into_vec();
+            entry.push(b'=');
+            entry.extend(value.into_vec());
+            envp.push(cstring(&entry)?);
+        }
+        
+        // The PID is filled in by the child; the zero padding doubles as the terminator
+        let mut listen_pid = vec![0u8; LISTEN_PID_ENTRY_LEN];
+        listen_pid[..LISTEN_PID_PREFIX.len()].copy_from_slice(LISTEN_PID_PREFIX);
+        
+        let argv_ptrs = argv.iter()
+            .map(|arg| arg.as_ptr())
+            .chain(std::iter::once(std::ptr::null()))
+            .collect();
+        let envp_ptrs = envp.iter()
+            .map(|entry| entry.as_ptr())
+            .chain([listen_pid.as_ptr().cast(), std::ptr::null()])
+            .collect();
+        
+        Ok(Self {
+            program: cstring(program.as_os_str().as_bytes())?,
+            _argv: argv,
+            argv_ptrs,
+            _envp: envp,
+            listen_pid,
+            envp_ptrs,
+            fds: sockets.iter().map(|socket| socket.fd).collect(),
+        })
+    }
+    
+    /// Move the sockets to fd 3 onward, set `LISTEN_PID` and exec the program
+    ///
+    /// Only returns on failure.
+    ///
+    /// # Safety
+    ///
+    /// Must only be called in the child between fork and exec.
+    pub unsafe fn exec(&mut self) -> io::Error {
+        // Park every socket above the target range first, so moving one into
+        // place cannot close another that happens to sit there
+        let first_free = LISTEN_FDS_START + self.fds.len() as RawFd;
+        for fd in self.fds.iter_mut() {
+            let parked = libc::fcntl(*fd, libc::F_DUPFD_CLOEXEC, first_free);
+            if parked < 0 {
+                return io::Error::last_os_error();
+            }
+            *fd = parked;
+        }
+        
+        // dup2 clears FD_CLOEXEC on the new descriptor; the parked copies close on exec
+        for (index, fd) in self.fds.iter().enumerate() {
+            if libc::dup2(*fd, LISTEN_FDS_START + index as RawFd) < 0 {
+                return io::Error::last_os_error();
+            }
+        }
+        
+        // Leave the last byte as the terminator
+        let digits = LISTEN_PID_PREFIX.len()..LISTEN_PID_ENTRY_LEN - 1;
+        write_decimal(&mut self.listen_pid[digits], libc::getpid() as u32);
+        
+        libc::execve(self.program.as_ptr(), self.argv_ptrs.as_ptr(), self.envp_ptrs.as_ptr());
+        io::Error::last_os_error()
+    }
+}
+
+/// Write `value` in decimal at the start of `buf` (which must fit it), without allocating
+fn write_decimal(buf: &mut [u8], mut value: u32) {
+    let mut digits = [0u8; 10];
+    let mut len = 0;
+    loop {
+        digits[len] = b'0' + (value % 10) as u8;
+        len += 1;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    for (slot, digit) in buf.iter_mut().zip(digits[..len].iter().rev()) {
+        *slot = *digit;
+    }
+}
+
+fn cstring(bytes: &[u8]) -> io::Result<CString> {
+    CString::new(bytes).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "nul byte in program, argument or environment"))
+}
+
+/// Look a bare program name up in `path` like execvp; paths with a slash are used as they are
+fn resolve_program(program: &OsStr, path: Option<&OsStr>) -> Option<PathBuf> {
+    if program.as_bytes().contains(&b'/') {
+        return Some(PathBuf::from(program));
+    }
+    
+    let path = path.unwrap_or(OsStr::new("/usr/bin:/bin"));
+    std::env::split_paths(path)
+        .map(|dir| dir.join(program))
+        .find(|candidate| is_executable(candidate))
+}
+
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).map_or(false, |metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
\ No newline at end of file
@@ -0,0 +1,157 @@
+//! Dry run of `ProcessSpawner::spawn`: everything up to `Command::spawn`, without running the job
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::job::config::{parse_umask, JobConfig, LogTarget};
+use crate::job::validator::{check_writable, effective_identity, may_execute, ConfigValidator};
+use crate::process::credentials::Credentials;
+use crate::process::env_file;
+use crate::process::output::{resolve_log_path, OutputStream};
+
+/// What spawning a job would do, and what would stop it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpawnCheck {
+    pub label: String,
+    pub program: PathBuf,
+    pub arguments: Vec<String>,
+    
+    /// User name, when the job's user has a passwd entry
+    pub user: Option<String>,
+    pub uid: u32,
+    pub gid: u32,
+    pub groups: Vec<u32>,
+    
+    pub working_directory: Option<PathBuf>,
+    
+    /// Variables the job sets (env file, then inline); values are left out
+    pub environment: Vec<String>,
+    
+    /// Whether the daemon's environment is dropped first
+    pub clear_environment: bool,
+    
+    /// Where stdout and stderr would go
+    pub stdout: String,
+    pub stderr: String,
+    
+    /// Reasons the spawn would fail; empty when it would succeed
+    pub problems: Vec<String>,
+}
+
+impl SpawnCheck {
+    /// Whether the job would start
+    pub fn ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+/// Resolve and check everything `spawn` needs, against the real filesystem and user database
+pub fn check_spawn(config: &JobConfig) -> SpawnCheck {
+    let mut problems = Vec::new();
+    
+    let credentials = Credentials::resolve(&config.program).unwrap_or_else(|e| {
+        problems.push(e.to_string());
+        None
+    });
+    let (uid, groups) = effective_identity(credentials.as_ref());
+    let gid = credentials.as_ref().map_or_else(nix::unistd::getegid, |c| c.gid);
+    
+    if credentials.as_ref().map_or(false, |c| c.needs_switch()) && !nix::unistd::geteuid().is_root() {
+        problems.push(format!(
+            "Running as uid {} / gid {} needs the daemon to run as root",
+            uid, gid
+        ));
+    }
+    
+    let mut environment = Vec::new();
+    if let Some(path) = env_file::resolve_env_file(config) {
+        match env_file::read_env_file(&path) {
+            Ok(vars) => environment.extend(vars.into_iter().map(|(key, _)| key)),
+            Err(e) => problems.push(e.to_string()),
+        }
+    }
+    for env in &config.environment {
+        if !environment.contains(&env.key) {
+            environment.push(env.key.clone());
+        }
+    }
+    
+    if let Some(working_dir) = &config.working_directory {
+        match std::fs::metadata(working_dir) {
+            Ok(metadata) if !metadata.is_dir() => {
+                problems.push(format!("Working directory {} is not a directory", working_dir.display()));
+            }
+            Ok(metadata) if !uid.is_root() && !may_execute(&metadata, uid, &groups) => {
+                problems.push(format!("Working directory {} is not accessible by uid {}", working_dir.display(), uid));
+            }
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && config.create_working_directory => {
+                if let Err(reason) = check_writable(working_dir) {
+                    problems.push(format!("Cannot create working directory {}: {}", working_dir.display(), reason));
+                }
+            }
+            Err(e) => problems.push(format!("Working directory {}: {}", working_dir.display(), e)),
+        }
+    }
+    
+    if let Err(reason) = ConfigValidator::check_executable_by(&config.program.path, uid, &groups) {
+        problems.push(reason);
+    }
+    
+    if let Some(umask) = &config.program.umask {
+        if parse_umask(umask).is_none() {
+            problems.push(format!("Invalid umask '{}'", umask));
+        }
+    }
+    
+    let stdout = describe_output(config, OutputStream::Stdout, &mut problems);
+    let stderr = describe_output(config, OutputStream::Stderr, &mut problems);
+    
+    SpawnCheck {
+        label: config.label.clone(),
+        program: config.program.path.clone(),
+        arguments: config.program.arguments.clone(),
+        user: credentials.and_then(|c| c.user_name),
+        uid: uid.as_raw(),
+        gid: gid.as_raw(),
+        groups: groups.iter().map(|gid| gid.as_raw()).collect(),
+        working_directory: config.working_directory.clone(),
+        environment,
+        clear_environment: config.program.clear_environment,
+        stdout,
+        stderr,
+        problems,
+    }
+}
+
+/// Describe where a stream goes, checking that the daemon could open its log file
+fn describe_output(config: &JobConfig, stream: OutputStream, problems: &mut Vec<String>) -> String {
+    let logging = &config.logging;
+    let path = match stream {
+        OutputStream::Stdout => logging.stdout_path.as_ref(),
+        OutputStream::Stderr => logging.stderr_path.as_ref(),
+    };
+    
+    let target = match logging.log_target {
+        LogTarget::Daemon => "daemon log",
+        LogTarget::File => "discarded",
+        LogTarget::Syslog => "syslog",
+        LogTarget::Journald => "journald",
+        LogTarget::Stderr => "daemon stderr",
+    };
+    
+    match path {
+        Some(path) => {
+            let path = resolve_log_path(config, path);
+            if let Err(reason) = check_writable(&path) {
+                problems.push(format!("{} log {}: {}", stream.as_str(), path.display(), reason));
+            }
+            if logging.log_target.captures() {
+                format!("{} and {}", path.display(), target)
+            } else {
+                path.display().to_string()
+            }
+        }
+        None => target.to_string(),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,119 @@
+use std::ffi::CString;
+use nix::unistd::{self, Gid, Group, Uid, User};
+
+use crate::job::config::ProgramConfig;
+use crate::util::error::{ConfigError, Result};
+
+/// Resolved identity a job's process should run as
+#[derive(Debug, Clone, PartialEq)]
+pub struct Credentials {
+    /// User to switch to (None = keep the daemon's user)
+    pub uid: Option<Uid>,
+    
+    /// Primary group
+    pub gid: Gid,
+    
+    /// Supplementary groups
+    pub groups: Vec<Gid>,
+    
+    /// User name, when the user has a passwd entry
+    pub user_name: Option<String>,
+}
+
+impl Credentials {
+    /// Resolve the `user`/`group` settings of a program
+    ///
+    /// Returns `None` when neither is set, so the job inherits the daemon's identity.
+    pub fn resolve(program: &ProgramConfig) -> Result<Option<Self>> {
+        let user = match &program.user {
+            Some(spec) => Some(resolve_user(spec)?),
+            None => None,
+        };
+        
+        let group = match &program.group {
+            Some(spec) => Some(resolve_group(spec)?),
+            None => None,
+        };
+        
+        match (user, group) {
+            (None, None) => Ok(None),
+            
+            // Only a group: keep the daemon's user but switch primary group
+            (None, Some(gid)) => Ok(Some(Self {
+                uid: None,
+                gid,
+                groups: vec![gid],
+                user_name: None,
+            })),
+            
+            (Some((uid, entry)), group) => {
+                let gid = match (group, &entry) {
+                    (Some(gid), _) => gid,
+                    (None, Some(entry)) => entry.gid,
+                    (None, None) => {
+                        return Err(ConfigError::Validation(format!(
+                            "User {} has no passwd entry; set `group` explicitly",
+                            uid
+                        )).into());
+                    }
+                };
+                
+                // Supplementary groups come from the group database for named users
+                let groups = match &entry {
+                    Some(entry) => {
+                        let name = CString::new(entry.name.as_str())
+                            .map_err(|_| ConfigError::Validation(format!("Invalid user name: {}", entry.name)))?;
+                        unistd::getgrouplist(&name, gid).map_err(|e| {
+                            ConfigError::Validation(format!(
+                                "Failed to resolve groups for user '{}': {}",
+                                entry.name, e
+                            ))
+                        })?
+                    }
+                    None => vec![gid],
+                };
+                
+                Ok(Some(Self {
+                    uid: Some(uid),
+                    gid,
+                    groups,
+                    user_name: entry.map(|entry| entry.name),
+                }))
+            }
+        }
+    }
+    
+    /// Whether these differ from the daemon's own identity (switching needs root)
+    pub fn needs_switch(&self) -> bool {
+        let uid_differs = self.uid.map_or(false, |uid| uid != unistd::geteuid());
+        uid_differs || self.gid != unistd::getegid()
+    }
+}
+
+/// Resolve a user given by name or numeric UID
+fn resolve_user(spec: &str) -> Result<(Uid, Option<User>)> {
+    if let Ok(raw) = spec.parse::<u32>() {
+        let uid = Uid::from_raw(raw);
+        let entry = User::from_uid(uid).ok().flatten();
+        return Ok((uid, entry));
+    }
+    
+    match User::from_name(spec) {
+        Ok(Some(entry)) => Ok((entry.uid, Some(entry))),
+        Ok(None) => Err(ConfigError::Validation(format!("Unknown user: {}", spec)).into()),
+        Err(e) => Err(ConfigError::Validation(format!("Failed to look up user '{}': {}", spec, e)).into()),
+    }
+}
+
+/// Resolve a group given by name or numeric GID
+pub(crate) fn resolve_group(spec: &str) -> Result<Gid> {
+    if let Ok(raw) = spec.parse::<u32>() {
+        return Ok(Gid::from_raw(raw));
+    }
+    
+    match Group::from_name(spec) {
+        Ok(Some(entry)) => Ok(entry.gid),
+        Ok(None) => Err(ConfigError::Validation(format!("Unknown group: {}", spec)).into()),
+        Err(e) => Err(ConfigError::Validation(format!("Failed to look up group '{}': {}", spec, e)).into()),
+    }
+}
\ No newline at end of file
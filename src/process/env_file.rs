@@ -0,0 +1,62 @@
+//! Parsing of `env_file` (KEY=VALUE lines) for job environments
+
+use std::path::{Path, PathBuf};
+
+use crate::job::config::JobConfig;
+use crate::util::error::{ConfigError, Result};
+
+/// Resolve the job's env file, interpreting a relative path against its working directory
+pub fn resolve_env_file(config: &JobConfig) -> Option<PathBuf> {
+    let path = config.env_file.as_ref()?;
+    Some(match &config.working_directory {
+        Some(working_dir) if path.is_relative() => working_dir.join(path),
+        _ => path.clone(),
+    })
+}
+
+/// Read and parse an env file
+pub fn read_env_file(path: &Path) -> Result<Vec<(String, String)>> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        ConfigError::Parse(format!("Failed to read env file {}: {}", path.display(), e))
+    })?;
+    
+    parse_env_file(&content).map_err(|e| {
+        ConfigError::Parse(format!("{}: {}", path.display(), e)).into()
+    })
+}
+
+/// Parse KEY=VALUE lines, skipping blank lines and `#` comments
+///
+/// An optional `export ` prefix is ignored and values may be wrapped in
+/// matching single or double quotes.
+pub fn parse_env_file(content: &str) -> std::result::Result<Vec<(String, String)>, String> {
+    let mut vars = Vec::new();
+    
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line.split_once('=')
+            .ok_or_else(|| format!("line {}: expected KEY=VALUE", index + 1))?;
+        
+        let key = key.trim();
+        let valid_key = key.chars().next().map_or(false, |c| c == '_' || c.is_ascii_alphabetic())
+            && key.chars().all(|c| c == '_' || c.is_ascii_alphanumeric());
+        if !valid_key {
+            return Err(format!("line {}: invalid variable name '{}'", index + 1, key));
+        }
+        
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|quote| value.strip_prefix(*quote)?.strip_suffix(*quote))
+            .unwrap_or(value);
+        
+        vars.push((key.to_string(), value.to_string()));
+    }
+    
+    Ok(vars)
+}
\ No newline at end of file
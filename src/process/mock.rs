@@ -0,0 +1,158 @@
+//! Simulated processes for supervision tests
+//!
+//! `MockSpawner` executes nothing: each "process" runs until the test ends it
+//! with `exit` or `crash`, or the job manager signals it. Exits reach the manager
+//! as real ones do, so restarts, backoff and failure can be driven step by step.
+
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use nix::sys::signal::Signal;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::job::config::JobConfig;
+use crate::process::activation::ListenFd;
+use crate::process::spawner::{spawn_with_retries, ProcessExit, SpawnFuture, Spawner};
+use crate::util::error::ProcessError;
+
+/// First simulated PID; above the kernel's PID limit, so never a real process
+const FIRST_PID: u32 = 1 << 23;
+
+/// How a simulated process ended: exit code (-1 if signalled) and signal
+type Exit = (i32, Option<i32>);
+
+/// Spawner of simulated processes; clones share them
+#[derive(Clone)]
+pub struct MockSpawner {
+    exit_tx: mpsc::Sender<ProcessExit>,
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    next_pid: u32,
+    /// Running process of each job: PID and the sender that ends it
+    running: HashMap<String, (u32, oneshot::Sender<Exit>)>,
+    spawns: HashMap<String, u32>,
+    failing: HashSet<String>,
+    /// Spawn attempts left to fail transiently, as under resource pressure
+    transient: HashMap<String, u32>,
+    /// Signals simulated processes survive, as a daemon reloading on SIGHUP does
+    survived: HashSet<i32>,
+}
+
+impl MockSpawner {
+    /// A spawner reporting exits on `exit_tx`, as passed to `JobManager::with_spawner`
+    pub fn new(exit_tx: mpsc::Sender<ProcessExit>) -> Self {
+        Self {
+            exit_tx,
+            state: Arc::new(Mutex::new(MockState { next_pid: FIRST_PID, ..Default::default() })),
+        }
+    }
+    
+    /// How many processes have been spawned for a job
+    pub fn spawn_count(&self, label: &str) -> u32 {
+        self.state.lock().unwrap().spawns.get(label).copied().unwrap_or(0)
+    }
+    
+    /// PID of the job's running process
+    pub fn pid(&self, label: &str) -> Option<u32> {
+        self.state.lock().unwrap().running.get(label).map(|(pid, _)| *pid)
+    }
+    
+    /// End the job's running process with `exit_code`; false if none is running
+    pub fn exit(&self, label: &str, exit_code: i32) -> bool {
+        self.end(label, (exit_code, None))
+    }
+    
+    /// End the job's running process as killed by `signal`; false if none is running
+    pub fn crash(&self, label: &str, signal: Signal) -> bool {
+        self.end(label, (-1, Some(signal as i32)))
+    }
+    
+    /// Make spawning the job fail, as a missing program does, until switched off again
+    pub fn fail_spawns(&self, label: &str, fail: bool) {
+        let mut state = self.state.lock().unwrap();
+        if fail {
+            state.failing.insert(label.to_string());
+        } else {
+            state.failing.remove(label);
+        }
+    }
+    
+    /// Make the job's next `count` spawn attempts fail with EAGAIN, which `spawn_retries` retries
+    pub fn fail_next_spawns(&self, label: &str, count: u32) {
+        self.state.lock().unwrap().transient.insert(label.to_string(), count);
+    }
+    
+    /// Let simulated processes survive `signal` from the job manager instead of ending
+    pub fn survive_signal(&self, signal: Signal) {
+        self.state.lock().unwrap().survived.insert(signal as i32);
+    }
+    
+    fn end(&self, label: &str, exit: Exit) -> bool {
+        match self.state.lock().unwrap().running.remove(label) {
+            Some((_, end)) => end.send(exit).is_ok(),
+            None => false,
+        }
+    }
+}
+
+impl Spawner for MockSpawner {
+    fn spawn<'a>(&'a self, config: &'a JobConfig, _listen_fds: &'a [ListenFd]) -> SpawnFuture<'a> {
+        Box::pin(async move {
+            let label = config.label.clone();
+            let (end_tx, end_rx) = oneshot::channel();
+            
+            if self.state.lock().unwrap().failing.contains(&label) {
+                return Err(ProcessError::Spawn(format!(
+                    "Failed to spawn process '{}': simulated failure",
+                    config.program.path.display()
+                )).into());
+            }
+            
+            let mut end_tx = Some(end_tx);
+            let pid = spawn_with_retries(config, || {
+                let mut state = self.state.lock().unwrap();
+                if let Some(left) = state.transient.get_mut(&label).filter(|left| **left > 0) {
+                    *left -= 1;
+                    return Err(io::Error::from_raw_os_error(libc::EAGAIN));
+                }
+                
+                let pid = state.next_pid;
+                state.next_pid += 1;
+                *state.spawns.entry(label.clone()).or_insert(0) += 1;
+                state.running.insert(label.clone(), (pid, end_tx.take().unwrap()));
+                Ok(pid)
+            }).await?;
+            
+            let exit_tx = self.exit_tx.clone();
+            let handle = tokio::spawn(async move {
+                // A dropped sender (the spawner is gone) ends the process like SIGKILL
+                let (exit_code, signal) = end_rx.await.unwrap_or((-1, Some(Signal::SIGKILL as i32)));
+                let _ = exit_tx.send(ProcessExit { label, pid, exit_code, signal }).await;
+            });
+            
+            Ok((pid, handle))
+        })
+    }
+    
+    /// Every signal not made survivable with `survive_signal`, even a normally harmless
+    /// one, ends the simulated process
+    fn signal(&self, pid: u32, signal: Signal, _group: bool) -> nix::Result<()> {
+        let (label, survives) = {
+            let state = self.state.lock().unwrap();
+            let label = state.running.iter()
+                .find(|(_, (running, _))| *running == pid)
+                .map(|(label, _)| label.clone())
+                .ok_or(nix::errno::Errno::ESRCH)?;
+            (label, state.survived.contains(&(signal as i32)))
+        };
+        
+        if !survives {
+            self.crash(&label, signal);
+        }
+        Ok(())
+    }
+}
\ No newline at end of file
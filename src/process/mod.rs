@@ -1,6 +1,16 @@
 pub mod spawner;
+pub mod activation;
 pub mod monitor;
+pub mod credentials;
+pub mod check;
+pub mod env_file;
+pub mod output;
+pub mod tree;
+pub mod usage;
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock;
 
 // Re-export commonly used types
-pub use spawner::ProcessSpawner;
-pub use monitor::ProcessMonitor;
\ No newline at end of file
+pub use spawner::{ProcessSpawner, Spawner};
+pub use monitor::ProcessMonitor;
+pub use credentials::Credentials;
\ No newline at end of file
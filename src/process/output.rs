@@ -0,0 +1,248 @@
+use std::fs::{File, OpenOptions};
+use std::os::unix::fs::{FileTypeExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::net::UnixDatagram;
+use tracing::{info, warn};
+
+use crate::job::config::{JobConfig, LogTarget, LoggingConfig, StdinSource};
+use crate::util::error::{ConfigError, ProcessError, Result};
+use crate::util::logwriter::RotatingWriter;
+
+/// Permissions for newly created job log files
+const LOG_FILE_MODE: u32 = 0o640;
+
+/// Local syslog socket
+pub const SYSLOG_SOCKET: &str = "/dev/log";
+
+/// Native protocol socket of systemd-journald
+pub const JOURNALD_SOCKET: &str = "/run/systemd/journal/socket";
+
+/// Syslog priority of forwarded lines: facility `daemon`, severity `info`
+const SYSLOG_PRIORITY: u8 = 3 * 8 + 6;
+
+/// Journal priority of forwarded lines (`info`, as systemd uses for captured output)
+const JOURNAL_PRIORITY: u8 = 6;
+
+/// Which output stream a line came from
+#[derive(Debug, Clone, Copy)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl OutputStream {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputStream::Stdout => "stdout",
+            OutputStream::Stderr => "stderr",
+        }
+    }
+}
+
+/// Where forwarded output lines are written
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogSink {
+    /// The daemon's tracing log
+    Daemon,
+    /// The daemon's stderr, as `label: line`
+    Stderr,
+    /// A syslog datagram socket
+    Syslog(PathBuf),
+    /// A journald native protocol socket
+    Journald(PathBuf),
+}
+
+impl LogSink {
+    /// The sink for a configured log target; `file` never forwards anything
+    pub fn for_target(target: LogTarget) -> Self {
+        match target {
+            LogTarget::Syslog => LogSink::Syslog(PathBuf::from(SYSLOG_SOCKET)),
+            LogTarget::Journald => LogSink::Journald(PathBuf::from(JOURNALD_SOCKET)),
+            LogTarget::Stderr => LogSink::Stderr,
+            LogTarget::Daemon | LogTarget::File => LogSink::Daemon,
+        }
+    }
+}
+
+/// Where the lines of a piped stream go
+#[derive(Default)]
+pub struct PipedOutput {
+    /// Log file the lines are appended to, rotated by size
+    pub file: Option<RotatingWriter>,
+    /// Sink the lines are forwarded to
+    pub sink: Option<LogSink>,
+}
+
+/// Check that a `file` target or size limit has a file to write to
+pub fn validate_logging(logging: &LoggingConfig) -> Result<()> {
+    let has_file = logging.stdout_path.is_some() || logging.stderr_path.is_some();
+    
+    if logging.log_target == LogTarget::File && !has_file {
+        return Err(ConfigError::Validation(
+            "logging: log_target = \"file\" needs stdout_path or stderr_path".into()
+        ).into());
+    }
+    
+    if logging.log_max_bytes > 0 && !has_file {
+        return Err(ConfigError::Validation(
+            "logging: log_max_bytes needs stdout_path or stderr_path".into()
+        ).into());
+    }
+    
+    Ok(())
+}
+
+/// Resolve a log path, interpreting relative paths against the job's working directory
+pub fn resolve_log_path(config: &JobConfig, path: &Path) -> PathBuf {
+    match &config.working_directory {
+        Some(working_dir) if path.is_relative() => working_dir.join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Open the job's stdin
+///
+/// A FIFO is opened read-write, which never blocks on Linux, so the spawner does not wait
+/// for a writer; the job then also never reads EOF when writers come and go.
+pub fn stdin_for(config: &JobConfig) -> Result<Stdio> {
+    let path = match &config.program.stdin {
+        StdinSource::Null => return Ok(Stdio::null()),
+        StdinSource::Inherit => return Ok(Stdio::inherit()),
+        StdinSource::File(path) => resolve_log_path(config, path),
+    };
+    let open_error = |e: std::io::Error| {
+        ProcessError::Spawn(format!("Failed to open stdin {}: {}", path.display(), e))
+    };
+    
+    let is_fifo = std::fs::metadata(&path).map_err(open_error)?.file_type().is_fifo();
+    let file = OpenOptions::new()
+        .read(true)
+        .write(is_fifo)
+        .open(&path)
+        .map_err(open_error)?;
+    
+    Ok(Stdio::from(file))
+}
+
+/// Build the stdio handle for one output stream, and where its lines go if it is piped
+///
+/// A stream with a path is written to its file directly, unless the file rotates or the
+/// target captures output: then it is piped and the forwarder writes the file. Without a
+/// path the stream is piped to the target, or discarded for the `file` target.
+pub fn stdio_for(config: &JobConfig, stream: OutputStream) -> Result<(Stdio, PipedOutput)> {
+    let logging = &config.logging;
+    let path = match stream {
+        OutputStream::Stdout => logging.stdout_path.as_ref(),
+        OutputStream::Stderr => logging.stderr_path.as_ref(),
+    };
+    let captures = logging.log_target.captures();
+    
+    match path {
+        Some(path) => {
+            let path = resolve_log_path(config, path);
+            let open_error = |e: std::io::Error| {
+                ProcessError::Spawn(format!("Failed to open log file {}: {}", path.display(), e))
+            };
+            
+            if logging.log_max_bytes == 0 && !captures {
+                let file = open_log_file(&path).map_err(open_error)?;
+                return Ok((Stdio::from(file), PipedOutput::default()));
+            }
+            
+            let file = RotatingWriter::open_with_mode(&path, logging.log_max_bytes, logging.log_keep, LOG_FILE_MODE)
+                .map_err(open_error)?;
+            let sink = captures.then(|| LogSink::for_target(logging.log_target));
+            Ok((Stdio::piped(), PipedOutput { file: Some(file), sink }))
+        }
+        None if logging.log_target == LogTarget::File => Ok((Stdio::null(), PipedOutput::default())),
+        None => Ok((Stdio::piped(), PipedOutput {
+            file: None,
+            sink: Some(LogSink::for_target(logging.log_target)),
+        })),
+    }
+}
+
+/// Open a log file for appending, creating it (and its directory) if needed
+pub fn open_log_file(path: &Path) -> std::io::Result<File> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .mode(LOG_FILE_MODE)
+        .open(path)
+}
+
+/// Forward a piped output stream one line at a time to its log file and sink
+///
+/// If a syslog or journald socket can't be reached, lines go to the daemon log instead.
+pub fn forward<R>(reader: R, label: String, pid: u32, stream: OutputStream, output: PipedOutput)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let PipedOutput { mut file, sink } = output;
+        let socket = match sink {
+            Some(LogSink::Syslog(_)) | Some(LogSink::Journald(_)) => UnixDatagram::unbound()
+                .map_err(|e| warn!("Failed to create log socket for job '{}': {}", label, e))
+                .ok(),
+            _ => None,
+        };
+        let mut unreachable = false;
+        
+        let mut lines = BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if let Some(writer) = file.as_mut() {
+                // Flushed per line so the file can be tailed
+                if let Err(e) = writer.write_line(&line).and_then(|_| writer.flush()) {
+                    warn!("Failed to write log file {} of job '{}': {}", writer.path().display(), label, e);
+                }
+            }
+            
+            let sent = match (&sink, &socket) {
+                (None, _) => None,
+                (Some(LogSink::Syslog(path)), Some(socket)) => {
+                    Some((path, socket.send_to(syslog_message(&label, pid, &line).as_bytes(), path).await))
+                }
+                (Some(LogSink::Journald(path)), Some(socket)) => {
+                    Some((path, socket.send_to(journald_message(&label, pid, &line).as_bytes(), path).await))
+                }
+                (Some(LogSink::Stderr), _) => {
+                    eprintln!("{}: {}", label, line);
+                    None
+                }
+                _ => {
+                    info!(job = %label, stream = stream.as_str(), "{}", line);
+                    None
+                }
+            };
+            
+            if let Some((path, Err(e))) = sent {
+                if !unreachable {
+                    warn!("Failed to forward output of job '{}' to {}: {}", label, path.display(), e);
+                    unreachable = true;
+                }
+                info!(job = %label, stream = stream.as_str(), "{}", line);
+            }
+        }
+    });
+}
+
+/// An RFC 3164 message for the local syslog socket, which adds the timestamp and host itself
+pub fn syslog_message(label: &str, pid: u32, line: &str) -> String {
+    format!("<{}>{}[{}]: {}", SYSLOG_PRIORITY, label, pid, line)
+}
+
+/// A journald native protocol datagram: one `FIELD=value` per line
+pub fn journald_message(label: &str, pid: u32, line: &str) -> String {
+    format!(
+        "SYSLOG_IDENTIFIER={}\nSYSLOG_PID={}\nPRIORITY={}\nMESSAGE={}\n",
+        label, pid, JOURNAL_PRIORITY, line
+    )
+}
\ No newline at end of file
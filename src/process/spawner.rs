@@ -1,23 +1,61 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::time::Duration;
+use nix::sys::signal::Signal;
 use tokio::process::{Command, Child};
+use tokio::sync::mpsc;
 use tracing::{info, warn, debug, instrument};
-use crate::job::config::{JobConfig, RestartPolicy};
+use crate::job::config::{CpuAffinity, JobConfig, LimitsConfig, ProcessConfig, ResourceLimit, DEFAULT_PATH, parse_umask};
 use crate::event::dispatcher::EventDispatcher;
-use crate::util::error::{NusaError, Result};
+use crate::process::activation::{ActivationExec, ListenFd};
+use crate::process::credentials::Credentials;
+use crate::process::env_file;
+use crate::process::output::{self, OutputStream};
+use crate::util::error::{NusaError, ProcessError, Result};
+
+/// Initial delay between retries of a transiently failed spawn (doubles per attempt)
+const SPAWN_RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
+
+/// How a supervised process ended, reported back to the job manager
+#[derive(Debug)]
+pub struct ProcessExit {
+    pub label: String,
+    pub pid: u32,
+    pub exit_code: i32,
+    pub signal: Option<i32>,
+}
+
+/// What `Spawner::spawn` resolves to: the PID and the task reporting the process's exit
+pub type SpawnFuture<'a> = Pin<Box<dyn Future<Output = Result<(u32, tokio::task::JoinHandle<()>)>> + Send + 'a>>;
+
+/// How the job manager starts and signals processes
+///
+/// `ProcessSpawner` runs real programs and `mock::MockSpawner` simulates them for
+/// tests. Either sends a `ProcessExit` for every exit on the channel it was created
+/// with; the task returned by `spawn` completes once it has.
+pub trait Spawner: Send + Sync {
+    /// Start the process of a job, passing it `listen_fds` as activation sockets
+    fn spawn<'a>(&'a self, config: &'a JobConfig, listen_fds: &'a [ListenFd]) -> SpawnFuture<'a>;
+    
+    /// Deliver a signal to a process, or with `group` to the process group it leads
+    fn signal(&self, pid: u32, signal: Signal, group: bool) -> nix::Result<()>;
+}
 
 pub struct ProcessSpawner {
     event_dispatcher: EventDispatcher,
+    exit_tx: mpsc::Sender<ProcessExit>,
 }
 
 impl ProcessSpawner {
-    pub fn new(event_dispatcher: EventDispatcher) -> Self {
-        Self { event_dispatcher }
+    pub fn new(event_dispatcher: EventDispatcher, exit_tx: mpsc::Sender<ProcessExit>) -> Self {
+        Self { event_dispatcher, exit_tx }
     }
     
-    /// Spawn a process based on job configuration
-    #[instrument(skip(self, config), fields(job = %config.label))]
-    pub async fn spawn(&self, config: &JobConfig) -> Result<(u32, tokio::task::JoinHandle<()>)> {
+    /// Spawn a process based on job configuration, passing it `listen_fds` as activation sockets
+    #[instrument(skip(self, config, listen_fds), fields(job = %config.label))]
+    pub async fn spawn(&self, config: &JobConfig, listen_fds: &[ListenFd]) -> Result<(u32, tokio::task::JoinHandle<()>)> {
         debug!("Spawning process: {:?}", config.program.path);
         
         let mut command = Command::new(&config.program.path);
@@ -27,13 +65,37 @@ impl ProcessSpawner {
             command.args(&config.program.arguments);
         }
         
+        // Set a recognizable process title
+        if let Some(title) = config.process_title() {
+            command.arg0(title);
+        }
+        
+        // Drop the inherited environment if asked; the job's own variables still apply
+        if config.program.clear_environment {
+            command.env_clear();
+            if config.program.default_path {
+                command.env("PATH", DEFAULT_PATH);
+            }
+        }
+        
+        // Load the env file first so inline entries override it
+        if let Some(path) = env_file::resolve_env_file(config) {
+            command.envs(env_file::read_env_file(&path)?);
+        }
+        
         // Set environment variables
         for env in &config.environment {
             command.env(&env.key, &env.value);
         }
         
-        // Set working directory
+        let credentials = Credentials::resolve(&config.program)?;
+        
+        // Set working directory, creating it first if asked
         if let Some(working_dir) = &config.working_directory {
+            if !working_dir.exists() && config.create_working_directory {
+                Self::create_working_directory(working_dir, credentials.as_ref())?;
+            }
+            
             if working_dir.exists() {
                 command.current_dir(working_dir);
             } else {
@@ -41,49 +103,266 @@ impl ProcessSpawner {
             }
         }
         
-        // Setup stdio
-        // TODO: Implement proper logging to files/journal
+        // Lead a new process group, so stopping the job reaches its descendants too
+        if config.supervision.kill_process_group {
+            command.process_group(0);
+        }
+        
+        // Apply resource limits (before dropping privileges, so hard limits can be raised)
+        Self::apply_limits(&mut command, &config.limits);
+        
+        // Set the file mode creation mask
+        if let Some(umask) = &config.program.umask {
+            Self::apply_umask(&mut command, umask)?;
+        }
+        
+        // Set CPU priority, OOM score and CPU affinity
+        Self::apply_process(&mut command, &config.process)?;
+        
+        // Drop privileges to the configured user/group
+        if let Some(credentials) = credentials {
+            Self::apply_credentials(&mut command, credentials, config)?;
+        }
+        
+        // Hand over activation sockets; this hook execs, so it must be installed last
+        if !listen_fds.is_empty() {
+            Self::apply_sockets(&mut command, config, listen_fds)?;
+        }
+        
+        // Setup stdio: the configured stdin; log files if configured, otherwise piped to the log target
+        let stdin = output::stdin_for(config)?;
+        let (stdout, stdout_output) = output::stdio_for(config, OutputStream::Stdout)?;
+        let (stderr, stderr_output) = output::stdio_for(config, OutputStream::Stderr)?;
         command
-            .stdin(std::process::Stdio::null())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped());
+            .stdin(stdin)
+            .stdout(stdout)
+            .stderr(stderr);
         
-        // Spawn the process
-        let mut child = command.spawn()
-            .map_err(|e| {
-                NusaError::Process(format!("Failed to spawn process '{}': {}", 
-                    config.program.path.display(), e))
-            })?;
+        // Spawn the process, retrying transient failures with backoff
+        let mut child = spawn_with_retries(config, || command.spawn()).await?;
         
         let pid = child.id()
             .ok_or_else(|| NusaError::Process("Failed to get PID".into()))?;
         
         info!("Process spawned [PID: {}] for job: {}", pid, config.label);
         
+        if let Some(stdout) = child.stdout.take() {
+            output::forward(stdout, config.label.clone(), pid, OutputStream::Stdout, stdout_output);
+        }
+        if let Some(stderr) = child.stderr.take() {
+            output::forward(stderr, config.label.clone(), pid, OutputStream::Stderr, stderr_output);
+        }
+        
         // Create monitor task
         let label = config.label.clone();
-        let config_clone = config.clone();
         let event_dispatcher = self.event_dispatcher.clone();
+        let exit_tx = self.exit_tx.clone();
         
         let handle = tokio::spawn(async move {
             Self::monitor_process(
                 label,
-                config_clone,
+                pid,
                 child,
-                event_dispatcher
+                event_dispatcher,
+                exit_tx
             ).await;
         });
         
         Ok((pid, handle))
     }
     
+    /// Set the child's umask between fork and exec
+    fn apply_umask(command: &mut Command, umask: &str) -> Result<()> {
+        let mask = parse_umask(umask)
+            .ok_or_else(|| ProcessError::Spawn(format!("Invalid umask '{}'", umask)))?;
+        
+        // SAFETY: umask is async-signal-safe and cannot fail
+        unsafe {
+            command.pre_exec(move || {
+                libc::umask(mask as libc::mode_t);
+                Ok(())
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Set nice, oom_score_adj and CPU affinity in the child before exec (and before dropping privileges)
+    fn apply_process(command: &mut Command, process: &ProcessConfig) -> Result<()> {
+        let nice = process.nice;
+        
+        // Format the value now: nothing may be allocated after fork
+        let oom_score_adj = process.oom_score_adj.map(|adj| format!("{}\n", adj).into_bytes());
+        let cpu_set = process.cpu_affinity.as_ref().map(cpu_set).transpose()?;
+        
+        if nice.is_none() && oom_score_adj.is_none() && cpu_set.is_none() {
+            return Ok(());
+        }
+        
+        // SAFETY: setpriority, open, write, close and sched_setaffinity are async-signal-safe
+        unsafe {
+            command.pre_exec(move || {
+                if let Some(nice) = nice {
+                    if libc::setpriority(libc::PRIO_PROCESS as _, 0, nice) != 0 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                }
+                
+                if let Some(set) = &cpu_set {
+                    if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), set) != 0 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                }
+                
+                if let Some(value) = &oom_score_adj {
+                    let fd = libc::open(b"/proc/self/oom_score_adj\0".as_ptr().cast(), libc::O_WRONLY | libc::O_CLOEXEC);
+                    if fd < 0 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                    let written = libc::write(fd, value.as_ptr().cast(), value.len());
+                    let error = std::io::Error::last_os_error();
+                    libc::close(fd);
+                    if written < 0 {
+                        return Err(error);
+                    }
+                }
+                
+                Ok(())
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Set resource limits in the child before exec
+    fn apply_limits(command: &mut Command, limits: &LimitsConfig) {
+        use nix::sys::resource::Resource;
+        
+        let limits: Vec<(Resource, ResourceLimit)> = [
+            (Resource::RLIMIT_NOFILE, limits.nofile),
+            (Resource::RLIMIT_NPROC, limits.nproc),
+            (Resource::RLIMIT_AS, limits.as_bytes),
+            (Resource::RLIMIT_CORE, limits.core),
+            (Resource::RLIMIT_CPU, limits.cpu_seconds),
+        ]
+        .into_iter()
+        .filter_map(|(resource, limit)| limit.map(|limit| (resource, limit)))
+        .collect();
+        
+        if limits.is_empty() {
+            return;
+        }
+        
+        // SAFETY: setrlimit is async-signal-safe and nothing is allocated after fork
+        unsafe {
+            command.pre_exec(move || {
+                for (resource, limit) in &limits {
+                    nix::sys::resource::setrlimit(*resource, limit.soft, limit.hard)?;
+                }
+                Ok(())
+            });
+        }
+    }
+    
+    /// Create a working directory and any missing parents, owned by the job's user/group
+    fn create_working_directory(path: &Path, credentials: Option<&Credentials>) -> Result<()> {
+        let existing = path.ancestors()
+            .find(|ancestor| ancestor.exists())
+            .map(Path::to_path_buf);
+        
+        std::fs::create_dir_all(path).map_err(|e| {
+            ProcessError::Spawn(format!("Failed to create working directory {}: {}", path.display(), e))
+        })?;
+        info!("Created working directory {}", path.display());
+        
+        let credentials = match credentials {
+            Some(credentials) if credentials.needs_switch() => credentials,
+            _ => return Ok(()),
+        };
+        
+        // Only the directories created here change owner
+        let created = path.ancestors()
+            .take_while(|ancestor| Some(*ancestor) != existing.as_deref());
+        for dir in created {
+            std::os::unix::fs::chown(dir, credentials.uid.map(|uid| uid.as_raw()), Some(credentials.gid.as_raw()))
+                .map_err(|e| {
+                    ProcessError::Spawn(format!("Failed to set owner of {}: {}", dir.display(), e))
+                })?;
+        }
+        
+        Ok(())
+    }
+    
+    /// Switch the child to the given credentials before exec
+    ///
+    /// This is done in `pre_exec` rather than with `Command::uid`/`gid`, because
+    /// std switches users before running `pre_exec` hooks, after which
+    /// supplementary groups can no longer be set.
+    fn apply_credentials(command: &mut Command, credentials: Credentials, config: &JobConfig) -> Result<()> {
+        if !credentials.needs_switch() {
+            // Already running as the requested identity
+            return Ok(());
+        }
+        
+        if !nix::unistd::geteuid().is_root() {
+            return Err(ProcessError::Spawn(format!(
+                "Job '{}' requests user {} / group {} but the daemon is not running as root",
+                config.label,
+                config.program.user.as_deref().unwrap_or("(unchanged)"),
+                config.program.group.as_deref().unwrap_or("(default)"),
+            )).into());
+        }
+        
+        if let Some(user) = &credentials.user_name {
+            command.env("USER", user).env("LOGNAME", user);
+        }
+        
+        // SAFETY: only async-signal-safe syscalls run between fork and exec
+        unsafe {
+            command.pre_exec(move || {
+                nix::unistd::setgroups(&credentials.groups)?;
+                nix::unistd::setgid(credentials.gid)?;
+                if let Some(uid) = credentials.uid {
+                    nix::unistd::setuid(uid)?;
+                }
+                Ok(())
+            });
+        }
+        
+        Ok(())
+    }
+    
+    /// Pass sockets at fd 3 onward, without FD_CLOEXEC, with `LISTEN_FDS`, `LISTEN_PID`
+    /// and `LISTEN_FDNAMES` set exactly as systemd's `sd_listen_fds_with_names` expects
+    fn apply_sockets(command: &mut Command, config: &JobConfig, listen_fds: &[ListenFd]) -> Result<()> {
+        let mut exec = ActivationExec::prepare(
+            command.as_std(),
+            config.process_title(),
+            config.program.clear_environment,
+            listen_fds,
+        ).map_err(|e| ProcessError::Spawn(format!("Failed to prepare socket activation: {}", e)))?;
+        
+        // SAFETY: ActivationExec::exec only makes async-signal-safe calls
+        unsafe {
+            command.pre_exec(move || Err(exec.exec()));
+        }
+        
+        Ok(())
+    }
+    
+    /// Whether a spawn error is worth retrying (resource pressure rather than a bad config)
+    pub fn is_transient_spawn_error(error: &std::io::Error) -> bool {
+        matches!(error.raw_os_error(), Some(libc::EAGAIN) | Some(libc::ENOMEM))
+    }
+    
     /// Monitor a running process and handle its exit
-    #[instrument(skip(child, event_dispatcher), fields(job = %label))]
+    #[instrument(skip(child, event_dispatcher, exit_tx), fields(job = %label))]
     async fn monitor_process(
         label: String,
-        config: JobConfig,
+        pid: u32,
         mut child: Child,
         event_dispatcher: EventDispatcher,
+        exit_tx: mpsc::Sender<ProcessExit>,
     ) {
         debug!("Starting process monitor");
         
@@ -94,32 +373,13 @@ impl ProcessSpawner {
                 
                 debug!("Process exited: code={}, signal={:?}", exit_code, signal);
                 
-                // Determine if restart is needed
-                let restart_needed = if config.supervision.keep_alive {
-                    match config.supervision.restart_policy {
-                        RestartPolicy::Always => true,
-                        RestartPolicy::Never => false,
-                        RestartPolicy::OnFailure => exit_code != 0,
-                        RestartPolicy::OnCrash => signal.is_some(),
-                    }
-                } else {
-                    false
-                };
-                
-                // Send exit event
-                let _ = event_dispatcher.send(crate::job::manager::JobEvent::JobExited(
-                    label.clone(),
+                // Let the manager record the exit and decide on a restart
+                let _ = exit_tx.send(ProcessExit {
+                    label,
+                    pid,
                     exit_code,
                     signal,
-                    0, // restart_count will be updated by manager
-                )).await;
-                
-                // If restart needed, signal the manager
-                if restart_needed {
-                    let _ = event_dispatcher.send(
-                        crate::job::manager::JobEvent::JobReadyForRestart(label)
-                    ).await;
-                }
+                }).await;
             }
             Err(e) => {
                 warn!("Error monitoring process for job '{}': {}", label, e);
@@ -161,10 +421,76 @@ impl ProcessSpawner {
     }
 }
 
+impl Spawner for ProcessSpawner {
+    fn spawn<'a>(&'a self, config: &'a JobConfig, listen_fds: &'a [ListenFd]) -> SpawnFuture<'a> {
+        Box::pin(ProcessSpawner::spawn(self, config, listen_fds))
+    }
+    
+    fn signal(&self, pid: u32, signal: Signal, group: bool) -> nix::Result<()> {
+        // A job spawned before `kill_process_group` was switched on is not a group
+        // leader, so only its own PID is signalled
+        let pid = nix::unistd::Pid::from_raw(pid as i32);
+        if group && nix::unistd::getpgid(Some(pid)) == Ok(pid) {
+            nix::sys::signal::killpg(pid, signal)
+        } else {
+            nix::sys::signal::kill(pid, signal)
+        }
+    }
+}
+
 impl Clone for ProcessSpawner {
     fn clone(&self) -> Self {
         Self {
             event_dispatcher: self.event_dispatcher.clone(),
+            exit_tx: self.exit_tx.clone(),
+        }
+    }
+}
+
+/// Run `spawn` until it succeeds, retrying transient failures up to `spawn_retries` times with backoff
+pub(crate) async fn spawn_with_retries<T>(
+    config: &JobConfig,
+    mut spawn: impl FnMut() -> std::io::Result<T>,
+) -> Result<T> {
+    let mut attempt = 0;
+    loop {
+        match spawn() {
+            Ok(spawned) => return Ok(spawned),
+            Err(e) if attempt < config.supervision.spawn_retries
+                && ProcessSpawner::is_transient_spawn_error(&e) => {
+                let delay = SPAWN_RETRY_BASE_DELAY * 2u32.pow(attempt);
+                attempt += 1;
+                warn!(
+                    "Transient spawn failure for job '{}': {} (retry {}/{} in {:?})",
+                    config.label, e, attempt, config.supervision.spawn_retries, delay
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => {
+                return Err(ProcessError::Spawn(format!("Failed to spawn process '{}': {}",
+                    config.program.path.display(), e)).into());
+            }
+        }
+    }
+}
+
+/// Number of CPUs currently online, the bound for `cpu_affinity` core indices
+pub fn online_cpus() -> usize {
+    usize::try_from(unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) }).unwrap_or(1).max(1)
+}
+
+fn cpu_set(affinity: &CpuAffinity) -> Result<libc::cpu_set_t> {
+    let cores = affinity.cores()
+        .filter(|cores| !cores.is_empty())
+        .ok_or_else(|| ProcessError::Spawn(format!("Invalid cpu_affinity {:?}", affinity)))?;
+    
+    // SAFETY: an all-zero cpu_set_t is the empty set
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for core in cores {
+        if core >= libc::CPU_SETSIZE as usize {
+            return Err(ProcessError::Spawn(format!("cpu_affinity core {} is out of range", core)).into());
         }
+        unsafe { libc::CPU_SET(core, &mut set) };
     }
+    Ok(set)
 }
\ No newline at end of file
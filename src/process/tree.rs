@@ -0,0 +1,79 @@
+//! Process trees read from `/proc`, for `job status --tree`
+
+use std::path::Path;
+
+/// A process as listed in `/proc/<pid>/status`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub ppid: u32,
+    pub name: String,
+}
+
+/// A process with the processes it started
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessTree {
+    pub process: ProcessInfo,
+    pub children: Vec<ProcessTree>,
+}
+
+/// Take `Name` and `PPid` from the contents of `/proc/<pid>/status`
+pub fn parse_status(pid: u32, status: &str) -> Option<ProcessInfo> {
+    let mut name = None;
+    let mut ppid = None;
+    
+    for line in status.lines() {
+        if let Some(value) = line.strip_prefix("Name:") {
+            name = Some(value.trim().to_string());
+        } else if let Some(value) = line.strip_prefix("PPid:") {
+            ppid = value.trim().parse().ok();
+        }
+    }
+    
+    Some(ProcessInfo { pid, ppid: ppid?, name: name? })
+}
+
+/// Every process under `proc_root` (normally `/proc`); processes that exit while reading are skipped
+pub fn read_processes(proc_root: &Path) -> Vec<ProcessInfo> {
+    let entries = match std::fs::read_dir(proc_root) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    
+    let mut processes: Vec<ProcessInfo> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let status = std::fs::read_to_string(entry.path().join("status")).ok()?;
+            parse_status(pid, &status)
+        })
+        .collect();
+    processes.sort_by_key(|process| process.pid);
+    processes
+}
+
+/// The tree rooted at `pid`, or `None` if there is no such process
+pub fn build_tree(processes: &[ProcessInfo], pid: u32) -> Option<ProcessTree> {
+    let process = processes.iter().find(|process| process.pid == pid)?.clone();
+    
+    let children = processes.iter()
+        .filter(|child| child.ppid == pid && child.pid != pid)
+        .filter_map(|child| build_tree(processes, child.pid))
+        .collect();
+    
+    Some(ProcessTree { process, children })
+}
+
+/// One `PID name` line per process, children indented below their parent
+pub fn render_tree(tree: &ProcessTree) -> String {
+    let mut out = String::new();
+    render_into(tree, 0, &mut out);
+    out
+}
+
+fn render_into(tree: &ProcessTree, depth: usize, out: &mut String) {
+    out.push_str(&format!("{}{} {}\n", "  ".repeat(depth), tree.process.pid, tree.process.name));
+    for child in &tree.children {
+        render_into(child, depth + 1, out);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+//! Memory and CPU use and start time of a running process, read from `/proc`
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// CPU time a process had used by some instant
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CpuSample {
+    pub pid: u32,
+    /// User plus system time, in clock ticks
+    pub ticks: u64,
+    pub taken_at: Instant,
+}
+
+impl CpuSample {
+    /// Share of one CPU used between `previous` and this sample, in percent
+    ///
+    /// `None` for samples of different processes or without time between them.
+    pub fn percent_since(&self, previous: &CpuSample, ticks_per_sec: u64) -> Option<f64> {
+        if self.pid != previous.pid || ticks_per_sec == 0 {
+            return None;
+        }
+        
+        let elapsed = self.taken_at.checked_duration_since(previous.taken_at)?;
+        if elapsed.is_zero() {
+            return None;
+        }
+        
+        let busy_secs = self.ticks.saturating_sub(previous.ticks) as f64 / ticks_per_sec as f64;
+        Some(busy_secs / elapsed.as_secs_f64() * 100.0)
+    }
+}
+
+/// `utime + stime` from the contents of `/proc/<pid>/stat`
+pub fn parse_cpu_ticks(stat: &str) -> Option<u64> {
+    let fields = stat_fields(stat)?;
+    
+    // Counting from `state` (field 3), utime and stime are fields 14 and 15
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some(utime + stime)
+}
+
+/// `starttime` (clock ticks after boot) from the contents of `/proc/<pid>/stat`
+pub fn parse_start_ticks(stat: &str) -> Option<u64> {
+    // Field 22, counting from `state` as field 3
+    stat_fields(stat)?.get(19)?.parse().ok()
+}
+
+/// The fields after the command name, starting with `state`
+fn stat_fields(stat: &str) -> Option<Vec<&str>> {
+    // The command name may contain spaces and parentheses; fields resume after the last `)`
+    Some(stat.get(stat.rfind(')')? + 1..)?.split_whitespace().collect())
+}
+
+/// Seconds since boot from the contents of `/proc/uptime`
+pub fn parse_system_uptime(uptime: &str) -> Option<f64> {
+    uptime.split_whitespace().next()?.parse().ok()
+}
+
+/// Resident pages from the contents of `/proc/<pid>/statm`
+pub fn parse_resident_pages(statm: &str) -> Option<u64> {
+    statm.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// Resident memory of `pid` under `proc_root` (normally `/proc`), in bytes
+pub fn read_rss_bytes(proc_root: &Path, pid: u32) -> Option<u64> {
+    let statm = std::fs::read_to_string(proc_root.join(pid.to_string()).join("statm")).ok()?;
+    Some(parse_resident_pages(&statm)? * page_size())
+}
+
+/// CPU time `pid` under `proc_root` (normally `/proc`) has used so far
+pub fn read_cpu_sample(proc_root: &Path, pid: u32) -> Option<CpuSample> {
+    let stat = std::fs::read_to_string(proc_root.join(pid.to_string()).join("stat")).ok()?;
+    Some(CpuSample {
+        pid,
+        ticks: parse_cpu_ticks(&stat)?,
+        taken_at: Instant::now(),
+    })
+}
+
+/// When `pid` under `proc_root` (normally `/proc`) started, in clock ticks after boot
+pub fn read_start_ticks(proc_root: &Path, pid: u32) -> Option<u64> {
+    let stat = std::fs::read_to_string(proc_root.join(pid.to_string()).join("stat")).ok()?;
+    parse_start_ticks(&stat)
+}
+
+/// How long a process that started `start_ticks` after boot has been running
+pub fn running_for(proc_root: &Path, start_ticks: u64) -> Option<Duration> {
+    let uptime = parse_system_uptime(&std::fs::read_to_string(proc_root.join("uptime")).ok()?)?;
+    let started = start_ticks as f64 / ticks_per_sec() as f64;
+    Some(Duration::from_secs_f64((uptime - started).max(0.0)))
+}
+
+/// Clock ticks per second, the unit of CPU times in `/proc`
+pub fn ticks_per_sec() -> u64 {
+    u64::try_from(unsafe { libc::sysconf(libc::_SC_CLK_TCK) }).unwrap_or(100)
+}
+
+fn page_size() -> u64 {
+    u64::try_from(unsafe { libc::sysconf(libc::_SC_PAGESIZE) }).unwrap_or(4096)
+}
\ No newline at end of file
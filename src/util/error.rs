@@ -0,0 +1,113 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum NusaError {
+    #[error("Config error: {0}")]
+    Config(#[from] ConfigError),
+    
+    #[error("Process error: {0}")]
+    Process(#[from] ProcessError),
+    
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    
+    #[error("Job '{0}' not found")]
+    JobNotFound(String),
+    
+    #[error("Job '{0}' already exists")]
+    JobExists(String),
+    
+    #[error("Job '{0}' is blocked: {1}")]
+    Blocked(String, String),
+    
+    #[error("Job limit reached ({0} jobs loaded)")]
+    JobLimitExceeded(usize),
+    
+    #[error("System error: {0}")]
+    System(String),
+    
+    #[error("Daemon error: {0}")]
+    Control(String),
+}
+
+impl NusaError {
+    /// Stable identifier of the error kind, sent as `code` in control socket errors
+    ///
+    /// Clients match on these, so they must never change once released.
+    pub fn code(&self) -> &'static str {
+        match self {
+            NusaError::Config(_) => "config_error",
+            NusaError::Process(_) => "process_error",
+            NusaError::Io(_) => "io_error",
+            NusaError::JobNotFound(_) => "job_not_found",
+            NusaError::JobExists(_) => "job_exists",
+            NusaError::Blocked(..) => "job_blocked",
+            NusaError::JobLimitExceeded(_) => "job_limit_exceeded",
+            NusaError::System(_) => "system_error",
+            NusaError::Control(_) => "control_error",
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to parse config: {0}")]
+    Parse(String),
+    
+    #[error("Invalid config: {0}")]
+    Validation(String),
+    
+    #[error("File '{0}' not found")]
+    FileNotFound(PathBuf),
+    
+    #[error("Unsupported config format")]
+    UnsupportedFormat,
+    
+    #[error("TOML parsing error: {0}")]
+    Toml(#[from] toml::de::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum ProcessError {
+    #[error("Failed to spawn process: {0}")]
+    Spawn(String),
+    
+    #[error("Process exited with code {0}")]
+    Exit(i32),
+    
+    #[error("Process terminated by {}", describe_signal(*.0))]
+    Signal(i32),
+    
+    #[error("Process timeout")]
+    Timeout,
+    
+    #[error("Process error: {0}")]
+    Other(String),
+}
+
+impl ProcessError {
+    /// How a process ended, as reported by the exit monitor: by `signal`, else with `exit_code`
+    pub fn from_exit(exit_code: i32, signal: Option<i32>) -> Self {
+        match signal {
+            Some(signal) => ProcessError::Signal(signal),
+            None => ProcessError::Exit(exit_code),
+        }
+    }
+}
+
+/// A signal number with its name, such as `SIGSEGV (11)`, or `signal 99` if it has none
+pub fn describe_signal(signal: i32) -> String {
+    match nix::sys::signal::Signal::try_from(signal) {
+        Ok(name) => format!("{} ({})", name, signal),
+        Err(_) => format!("signal {}", signal),
+    }
+}
+
+impl From<String> for ProcessError {
+    fn from(s: String) -> Self {
+        ProcessError::Other(s)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, NusaError>;
\ No newline at end of file
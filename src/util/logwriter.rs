@@ -0,0 +1,112 @@
+//! Rotating log file writer
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+
+/// Buffered line writer with size-based rotation (`<name>.1` .. `<name>.<max_files>`)
+///
+/// Rotation happens between lines, never inside one.
+pub struct RotatingWriter {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    mode: u32,
+    writer: BufWriter<File>,
+    written: u64,
+}
+
+impl RotatingWriter {
+    /// Open (or create) a log file for appending
+    ///
+    /// A `max_bytes` of 0 disables rotation.
+    pub fn open<P: AsRef<Path>>(path: P, max_bytes: u64, max_files: usize) -> io::Result<Self> {
+        Self::open_with_mode(path, max_bytes, max_files, 0o666)
+    }
+    
+    /// Like `open`, creating files with `mode` (before the umask)
+    pub fn open_with_mode<P: AsRef<Path>>(
+        path: P,
+        max_bytes: u64,
+        max_files: usize,
+        mode: u32,
+    ) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+        
+        let file = Self::open_append(&path, mode)?;
+        let written = file.metadata()?.len();
+        
+        Ok(Self {
+            path,
+            max_bytes,
+            max_files,
+            mode,
+            writer: BufWriter::new(file),
+            written,
+        })
+    }
+    
+    /// Write a single line, rotating first if it would exceed the size limit
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let len = line.len() as u64 + 1;
+        
+        if self.max_bytes > 0 && self.written > 0 && self.written + len > self.max_bytes {
+            self.rotate()?;
+        }
+        
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.write_all(b"\n")?;
+        self.written += len;
+        
+        Ok(())
+    }
+    
+    /// Flush buffered lines to disk
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+    
+    /// Path of the active log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    
+    fn rotate(&mut self) -> io::Result<()> {
+        self.writer.flush()?;
+        
+        if self.max_files == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            // Shift <name>.N-1 -> <name>.N, dropping the oldest
+            for index in (1..self.max_files).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    fs::rename(&from, self.rotated_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+        }
+        
+        self.writer = BufWriter::new(Self::open_append(&self.path, self.mode)?);
+        self.written = 0;
+        
+        Ok(())
+    }
+    
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+    
+    fn open_append(path: &Path, mode: u32) -> io::Result<File> {
+        OpenOptions::new().create(true).append(true).mode(mode).open(path)
+    }
+}
\ No newline at end of file
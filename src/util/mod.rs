@@ -1,6 +1,7 @@
 //! Utility modules for NusaLaunchd
 
 pub mod error;
+pub mod logwriter;
 
 // Re-export error types
 pub use error::{NusaError, ConfigError, ProcessError, Result};
\ No newline at end of file
@@ -0,0 +1,123 @@
+use nusalaunchd::control::ControlServer;
+use nusalaunchd::job::{JobManager, JobState};
+use std::path::Path;
+use std::process::Output;
+use tempfile::TempDir;
+use tokio::process::Command;
+
+const NUSALAUNCHD: &str = env!("CARGO_BIN_EXE_nusalaunchd");
+
+/// A daemon serving `sleeper` and `other` (both stopped) on a socket in `temp_dir`
+async fn serve(temp_dir: &TempDir) -> JobManager {
+    let (mut job_manager, _event_rx) = JobManager::new().await.unwrap();
+    job_manager.set_auto_start(false);
+    for label in ["sleeper", "other"] {
+        job_manager.load_job(toml::from_str(&format!(
+            "label = \"{}\"\n[program]\npath = \"/bin/sleep\"\narguments = [\"30\"]\n[supervision]\nkeep_alive = false\n",
+            label
+        )).unwrap()).await.unwrap();
+    }
+    
+    let server = ControlServer::bind(socket(temp_dir.path()), job_manager.clone(), None).unwrap();
+    tokio::spawn(server.run());
+    job_manager
+}
+
+fn socket(dir: &Path) -> std::path::PathBuf {
+    dir.join("control.sock")
+}
+
+/// Run `nusalaunchd job <args>` against the test daemon
+async fn job_command(temp_dir: &TempDir, args: &[&str]) -> (Output, String) {
+    let output = Command::new(NUSALAUNCHD)
+        .arg("--control-socket").arg(socket(temp_dir.path()))
+        .arg("job")
+        .args(args)
+        .output()
+        .await
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    (output, stdout)
+}
+
+async fn state(job_manager: &JobManager, label: &str) -> JobState {
+    job_manager.get_job_status(label).await.unwrap().state
+}
+
+#[tokio::test]
+async fn test_start_wait_reports_running_job() {
+    let temp_dir = TempDir::new().unwrap();
+    let job_manager = serve(&temp_dir).await;
+    
+    let (output, stdout) = job_command(&temp_dir, &["start", "--wait", "sleeper"]).await;
+    assert!(output.status.success(), "{:?}", output);
+    
+    let pid = job_manager.get_job_status("sleeper").await.unwrap().pid.unwrap();
+    assert!(stdout.contains(&format!("✓ sleeper: running [PID: {}]", pid)), "{}", stdout);
+    
+    job_manager.kill_job("sleeper").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_failing_label_exits_non_zero() {
+    let temp_dir = TempDir::new().unwrap();
+    let job_manager = serve(&temp_dir).await;
+    
+    // The other labels are still handled
+    let (output, stdout) = job_command(&temp_dir, &["start", "sleeper", "missing", "other"]).await;
+    assert!(!output.status.success(), "{:?}", output);
+    assert!(stdout.contains("✓ sleeper: started [PID: "), "{}", stdout);
+    assert!(stdout.contains("✗ missing: "), "{}", stdout);
+    assert!(stdout.contains("✓ other: started [PID: "), "{}", stdout);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed: missing"), "{:?}", output);
+    
+    let (output, stdout) = job_command(&temp_dir, &["stop", "missing"]).await;
+    assert!(!output.status.success(), "{:?}", output);
+    assert!(stdout.contains("✗ missing: "), "{}", stdout);
+    
+    for label in ["sleeper", "other"] {
+        job_manager.kill_job(label).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_stop_and_force_stop() {
+    let temp_dir = TempDir::new().unwrap();
+    let job_manager = serve(&temp_dir).await;
+    job_manager.start_job("sleeper").await.unwrap();
+    job_manager.start_job("other").await.unwrap();
+    
+    let (output, stdout) = job_command(&temp_dir, &["stop", "sleeper"]).await;
+    assert!(output.status.success(), "{:?}", output);
+    assert!(stdout.contains("✓ sleeper: stopped"), "{}", stdout);
+    assert_eq!(state(&job_manager, "sleeper").await, JobState::Stopped);
+    
+    let (output, stdout) = job_command(&temp_dir, &["stop", "--force", "other"]).await;
+    assert!(output.status.success(), "{:?}", output);
+    assert!(stdout.contains("✓ other: killed"), "{}", stdout);
+    assert_eq!(job_manager.get_job_status("other").await.unwrap().pid, None);
+}
+
+#[tokio::test]
+async fn test_restart_replaces_process() {
+    let temp_dir = TempDir::new().unwrap();
+    let job_manager = serve(&temp_dir).await;
+    job_manager.start_job("sleeper").await.unwrap();
+    let before = job_manager.get_job_status("sleeper").await.unwrap().pid.unwrap();
+    
+    let (output, stdout) = job_command(&temp_dir, &["restart", "sleeper"]).await;
+    assert!(output.status.success(), "{:?}", output);
+    assert!(stdout.contains("✓ sleeper: restarted"), "{}", stdout);
+    
+    let after = job_manager.get_job_status("sleeper").await.unwrap();
+    assert_eq!(after.state, JobState::Running);
+    assert_ne!(after.pid, Some(before));
+    
+    // A stopped job is left alone with --skip-if-stopped
+    let (output, stdout) = job_command(&temp_dir, &["restart", "--skip-if-stopped", "other"]).await;
+    assert!(output.status.success(), "{:?}", output);
+    assert!(stdout.contains("✓ other: skipped (not running)"), "{}", stdout);
+    assert_eq!(state(&job_manager, "other").await, JobState::Stopped);
+    
+    job_manager.kill_job("sleeper").await.unwrap();
+}
\ No newline at end of file
@@ -20,15 +20,30 @@ async fn test_job_lifecycle() {
         program: ProgramConfig {
             path: PathBuf::from("/bin/sleep"),
             arguments: vec!["5".to_string()], // Sleep for 5 seconds
+            ..Default::default()
         },
         supervision: SupervisionConfig {
             keep_alive: false,
             restart_policy: RestartPolicy::Never,
             restart_delay_sec: 1,
             max_restarts: 0,
+            ..Default::default()
         },
         environment: vec![],
+        env_file: None,
+        expand_env: false,
         working_directory: None,
+        create_working_directory: false,
+        limits: Default::default(),
+        process: Default::default(),
+        logging: Default::default(),
+        schedule: None,
+        after: Vec::new(),
+        requires: Vec::new(),
+        health: None,
+        sockets: Vec::new(),
+        hooks: Default::default(),
+        conditions: Default::default(),
     };
     
     // Test: Load job
@@ -62,15 +77,30 @@ async fn test_job_restart_policy() {
         program: ProgramConfig {
             path: PathBuf::from("/bin/true"),
             arguments: vec![],
+            ..Default::default()
         },
         supervision: SupervisionConfig {
             keep_alive: true,
             restart_policy: RestartPolicy::OnFailure,
             restart_delay_sec: 1,
             max_restarts: 3,
+            ..Default::default()
         },
         environment: vec![],
+        env_file: None,
+        expand_env: false,
         working_directory: None,
+        create_working_directory: false,
+        limits: Default::default(),
+        process: Default::default(),
+        logging: Default::default(),
+        schedule: None,
+        after: Vec::new(),
+        requires: Vec::new(),
+        health: None,
+        sockets: Vec::new(),
+        hooks: Default::default(),
+        conditions: Default::default(),
     };
     
     manager.load_job(config).await.expect("Failed to load job");
@@ -119,6 +149,7 @@ async fn test_backoff_calculation() {
         restart_policy: RestartPolicy::Always,
         restart_delay_sec: 2,
         max_restarts: 5,
+        ..Default::default()
     };
     
     // Test exponential backoff
@@ -134,4 +165,514 @@ async fn test_backoff_calculation() {
     // Test cap at 300 seconds (5 minutes)
     let backoff_large = supervisor.calculate_backoff(&config, 10);
     assert!(backoff_large.as_secs() <= 300);
+}
+
+#[tokio::test]
+async fn test_fixed_and_linear_backoff() {
+    use nusalaunchd::job::BackoffStrategy;
+    use nusalaunchd::job::supervisor::JobSupervisor;
+    let supervisor = JobSupervisor::new();
+    
+    let fixed = SupervisionConfig {
+        restart_delay_sec: 3,
+        backoff_strategy: BackoffStrategy::Fixed,
+        ..Default::default()
+    };
+    assert_eq!(supervisor.calculate_backoff(&fixed, 0).as_secs(), 3);
+    assert_eq!(supervisor.calculate_backoff(&fixed, 7).as_secs(), 3);
+    
+    let linear = SupervisionConfig {
+        backoff_strategy: BackoffStrategy::Linear,
+        ..fixed
+    };
+    assert_eq!(supervisor.calculate_backoff(&linear, 0).as_secs(), 3); // 3 * 1
+    assert_eq!(supervisor.calculate_backoff(&linear, 3).as_secs(), 12); // 3 * 4
+    
+    // Exponential stays the default
+    assert_eq!(SupervisionConfig::default().backoff_strategy, BackoffStrategy::Exponential);
+}
+
+#[test]
+fn test_success_reset_window() {
+    let config: SupervisionConfig = toml::from_str("keep_alive = true").unwrap();
+    assert_eq!(config.success_reset_sec, 300);
+    
+    assert!(!config.resets_restart_count(Duration::from_secs(299)));
+    assert!(config.resets_restart_count(Duration::from_secs(300)));
+    
+    let disabled = SupervisionConfig { success_reset_sec: 0, ..config };
+    assert!(!disabled.resets_restart_count(Duration::from_secs(86400)));
+}
+
+#[tokio::test]
+async fn test_crashing_job_restarts_after_backoff() {
+    let (manager, mut event_rx) = JobManager::new().await.unwrap();
+    
+    let config: JobConfig = toml::from_str(r#"
+        label = "crasher"
+        
+        [program]
+        path = "/bin/sh"
+        arguments = ["-c", "sleep 0.2; exit 1"]
+        
+        [supervision]
+        keep_alive = true
+        restart_policy = "on-failure"
+        restart_delay_sec = 1
+        max_restarts = 0
+    "#).unwrap();
+    
+    manager.load_job(config).await.unwrap();
+    
+    // Loading starts the job; wait for the supervisor to bring it back after it crashes
+    let mut pids = Vec::new();
+    let result = tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(event) = event_rx.recv().await {
+            if let nusalaunchd::job::JobEvent::JobStarted(_, pid, _) = event {
+                pids.push(pid);
+                if pids.len() == 2 {
+                    break;
+                }
+            }
+        }
+    }).await;
+    
+    assert!(result.is_ok(), "job was not restarted, started PIDs: {:?}", pids);
+    assert_ne!(pids[0], pids[1]);
+    
+    let status = manager.get_job_status("crasher").await.unwrap();
+    assert_eq!(status.restart_count, 1);
+    
+    manager.stop_job("crasher").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_status_records_exit_code() {
+    let (manager, mut event_rx) = JobManager::new().await.unwrap();
+    
+    let config: JobConfig = toml::from_str(r#"
+        label = "exits"
+        
+        [program]
+        path = "/bin/sh"
+        arguments = ["-c", "exit 3"]
+        
+        [supervision]
+        keep_alive = false
+    "#).unwrap();
+    
+    manager.load_job(config).await.unwrap();
+    manager.start_job("exits").await.unwrap();
+    
+    let result = tokio::time::timeout(Duration::from_secs(5), async {
+        while let Some(event) = event_rx.recv().await {
+            if let nusalaunchd::job::JobEvent::JobExited(label, code, signal, _) = event {
+                return (label, code, signal);
+            }
+        }
+        panic!("event channel closed");
+    }).await;
+    
+    assert_eq!(result.unwrap(), ("exits".to_string(), 3, None));
+    
+    let status = manager.get_job_status("exits").await.unwrap();
+    assert_eq!(status.state, nusalaunchd::job::JobState::Stopped);
+    assert_eq!(status.exit_code, Some(3));
+    assert_eq!(status.exit_signal, None);
+    assert_eq!(status.pid, None);
+}
+
+#[test]
+fn test_restart_policy_decision() {
+    let config = SupervisionConfig {
+        keep_alive: true,
+        restart_policy: RestartPolicy::OnFailure,
+        ..Default::default()
+    };
+    assert!(config.wants_restart(1, None));
+    assert!(!config.wants_restart(0, None));
+    
+    let on_crash = SupervisionConfig { restart_policy: RestartPolicy::OnCrash, ..config.clone() };
+    assert!(on_crash.wants_restart(-1, Some(9)));
+    assert!(!on_crash.wants_restart(1, None));
+    
+    let not_supervised = SupervisionConfig { keep_alive: false, restart_policy: RestartPolicy::Always, ..config };
+    assert!(!not_supervised.wants_restart(1, None));
+}
+
+#[tokio::test]
+async fn test_restart_exit_codes() {
+    let config = SupervisionConfig {
+        keep_alive: true,
+        restart_policy: RestartPolicy::OnFailure,
+        restart_on_exit_codes: vec![0, 75],
+        no_restart_exit_codes: vec![75, 78],
+        ..Default::default()
+    };
+    
+    // A no-restart code wins over the policy and over the restart list
+    assert!(!config.wants_restart(78, None));
+    assert!(!config.wants_restart(75, None));
+    assert!(config.wants_restart(0, None));
+    
+    // Codes in neither list follow the policy; signals match no code
+    assert!(config.wants_restart(1, None));
+    assert!(config.wants_restart(78, Some(9)));
+    
+    let never = SupervisionConfig { restart_policy: RestartPolicy::Never, ..config.clone() };
+    assert!(never.wants_restart(0, None));
+    assert!(!never.wants_restart(1, None));
+    
+    let out_of_range: JobConfig = toml::from_str(
+        "label = \"codes\"\n[program]\npath = \"/bin/true\"\n[supervision]\nno_restart_exit_codes = [256]\n"
+    ).unwrap();
+    let err = out_of_range.validate().await.unwrap_err();
+    assert!(err.to_string().contains("256"), "{}", err);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_load_and_start_spawns_once() {
+    for round in 0..20 {
+        let (manager, mut event_rx) = JobManager::new().await.unwrap();
+        
+        let config: JobConfig = toml::from_str(&format!(r#"
+            label = "racer-{}"
+            
+            [program]
+            path = "/bin/sleep"
+            arguments = ["30"]
+            
+            [supervision]
+            keep_alive = true
+        "#, round)).unwrap();
+        let label = config.label.clone();
+        
+        // Hammer start_job while the job is being loaded (and auto-started)
+        let starters: Vec<_> = (0..8).map(|_| {
+            let manager = manager.clone();
+            let label = label.clone();
+            tokio::spawn(async move {
+                for _ in 0..20 {
+                    let _ = manager.start_job(&label).await;
+                    tokio::task::yield_now().await;
+                }
+            })
+        }).collect();
+        
+        manager.load_job(config).await.unwrap();
+        for starter in starters {
+            starter.await.unwrap();
+        }
+        
+        // Wait for the auto-start to finish spawning
+        for _ in 0..100 {
+            if manager.get_job_status(&label).await.unwrap().pid.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        
+        let mut started = 0;
+        while let Ok(event) = event_rx.try_recv() {
+            if let nusalaunchd::job::JobEvent::JobStarted(..) = event {
+                started += 1;
+            }
+        }
+        assert_eq!(started, 1, "round {}", round);
+        
+        manager.stop_job(&label).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_load_rejects_jobs_over_limit() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.set_max_jobs(3);
+    
+    let job = |index: usize| -> JobConfig {
+        toml::from_str(&format!(
+            "label = \"job-{}\"\n[program]\npath = \"/bin/true\"\n[supervision]\nkeep_alive = false\n",
+            index
+        )).unwrap()
+    };
+    
+    for index in 0..3 {
+        manager.load_job(job(index)).await.unwrap();
+    }
+    
+    let err = manager.load_job(job(3)).await.unwrap_err();
+    assert!(matches!(err, nusalaunchd::util::error::NusaError::JobLimitExceeded(3)), "{}", err);
+    assert!(manager.get_job_status("job-3").await.is_none());
+    assert_eq!(manager.list_jobs().await.len(), 3);
+}
+
+#[tokio::test]
+async fn test_daemon_config_without_max_jobs_keeps_limit() {
+    use nusalaunchd::daemon::{DaemonConfig, DaemonConfigHandle};
+    use tracing_subscriber::{filter::LevelFilter, reload, Registry};
+    
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("daemon.toml");
+    std::fs::write(&path, "log_level = \"debug\"\n").unwrap();
+    
+    let config = DaemonConfig::from_file(&path).await.unwrap();
+    assert_eq!(config.max_jobs, None);
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.set_max_jobs(3);
+    
+    let (_layer, log_handle) = reload::Layer::<LevelFilter, Registry>::new(LevelFilter::INFO);
+    let handle = DaemonConfigHandle::new(path.clone(), config, log_handle, manager.clone());
+    handle.apply().await.unwrap();
+    assert_eq!(manager.max_jobs(), 3);
+    
+    // Setting it in the file does take over
+    std::fs::write(&path, "log_level = \"debug\"\nmax_jobs = 8\n").unwrap();
+    let report = handle.reload().await.unwrap();
+    assert_eq!(report.applied, vec!["max_jobs: unset -> 8".to_string()]);
+    assert_eq!(manager.max_jobs(), 8);
+    
+    // Removing it again restores the command line's limit
+    std::fs::write(&path, "log_level = \"debug\"\n").unwrap();
+    let report = handle.reload().await.unwrap();
+    assert_eq!(report.applied, vec!["max_jobs: 8 -> unset".to_string()]);
+    assert_eq!(manager.max_jobs(), 3);
+}
+
+#[tokio::test]
+async fn test_daemon_config_without_log_level_keeps_command_line_level() {
+    use nusalaunchd::daemon::{DaemonConfig, DaemonConfigHandle};
+    use tracing_subscriber::{filter::LevelFilter, reload, Registry};
+    
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("daemon.toml");
+    std::fs::write(&path, "max_jobs = 4\n").unwrap();
+    
+    let config = DaemonConfig::from_file(&path).await.unwrap();
+    assert_eq!(config.log_level, None);
+    
+    // As if started with -q
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    let (_layer, log_handle) = reload::Layer::<LevelFilter, Registry>::new(LevelFilter::ERROR);
+    let handle = DaemonConfigHandle::new(path.clone(), config, log_handle.clone(), manager);
+    handle.apply().await.unwrap();
+    assert_eq!(log_handle.clone_current(), Some(LevelFilter::ERROR));
+    
+    std::fs::write(&path, "max_jobs = 4\nlog_level = \"debug\"\n").unwrap();
+    handle.reload().await.unwrap();
+    assert_eq!(log_handle.clone_current(), Some(LevelFilter::DEBUG));
+    
+    std::fs::write(&path, "max_jobs = 4\n").unwrap();
+    let report = handle.reload().await.unwrap();
+    assert_eq!(report.applied, vec!["log_level: debug -> unset".to_string()]);
+    assert_eq!(log_handle.clone_current(), Some(LevelFilter::ERROR));
+}
+
+#[tokio::test]
+async fn test_restart_rate_limit_fails_job() {
+    let (manager, mut event_rx) = JobManager::new().await.unwrap();
+    
+    let config: JobConfig = toml::from_str(r#"
+        label = "flapper"
+        
+        [program]
+        path = "/bin/sh"
+        arguments = ["-c", "exit 1"]
+        
+        [supervision]
+        keep_alive = true
+        restart_policy = "on-failure"
+        restart_delay_sec = 0
+        max_restarts = 0
+        restart_limit_interval_sec = 60
+        restart_limit_burst = 2
+    "#).unwrap();
+    
+    manager.load_job(config).await.unwrap();
+    
+    // Two restarts fit the burst; the third exit within the window fails the job
+    let mut starts = 0;
+    let result = tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(event) = event_rx.recv().await {
+            match event {
+                nusalaunchd::job::JobEvent::JobStarted(..) => starts += 1,
+                nusalaunchd::job::JobEvent::JobFailed(_, state) => return state,
+                _ => {}
+            }
+        }
+        unreachable!("event channel closed");
+    }).await;
+    
+    let state = result.expect("job was never failed");
+    assert_eq!(starts, 3);
+    match state {
+        nusalaunchd::job::JobState::Failed(reason) => assert!(reason.contains("rate limit"), "{}", reason),
+        other => panic!("unexpected state {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_load_rejects_zero_restart_limit_burst() {
+    let config: JobConfig = toml::from_str(r#"
+        label = "flapper"
+        
+        [program]
+        path = "/bin/true"
+        
+        [supervision]
+        restart_limit_interval_sec = 60
+        restart_limit_burst = 0
+    "#).unwrap();
+    
+    let err = config.validate().await.unwrap_err();
+    assert!(err.to_string().contains("restart_limit_burst"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_max_runtime_kills_and_fails_job() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    
+    let config: JobConfig = toml::from_str(r#"
+        label = "overrun"
+        
+        [program]
+        path = "/bin/sleep"
+        arguments = ["100"]
+        
+        [supervision]
+        keep_alive = false
+        max_runtime_sec = 1
+    "#).unwrap();
+    
+    manager.load_job(config).await.unwrap();
+    manager.start_job("overrun").await.unwrap();
+    let pid = manager.get_job_status("overrun").await.unwrap().pid.unwrap();
+    
+    tokio::time::sleep(Duration::from_millis(2500)).await;
+    
+    let status = manager.get_job_status("overrun").await.unwrap();
+    match status.state {
+        nusalaunchd::job::JobState::Failed(reason) => assert!(reason.contains("max runtime"), "{}", reason),
+        other => panic!("unexpected state {:?}", other),
+    }
+    assert!(status.pid.is_none());
+    assert!(nix::sys::signal::kill(nix::unistd::Pid::from_raw(pid as i32), None).is_err());
+}
+
+#[tokio::test]
+async fn test_spawn_failure_retried_until_program_appears() {
+    use std::os::unix::fs::PermissionsExt;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let program = temp_dir.path().join("late-service");
+    
+    let (mut manager, mut event_rx) = JobManager::new().await.unwrap();
+    manager.set_auto_start(false);
+    
+    let config: JobConfig = toml::from_str(&format!(r#"
+        label = "late"
+        
+        [program]
+        path = "{}"
+        
+        [supervision]
+        keep_alive = true
+        restart_delay_sec = 1
+        max_restarts = 0
+        retry_on_spawn_failure = true
+    "#, program.display())).unwrap();
+    
+    manager.load_job(config).await.unwrap();
+    assert!(manager.start_job("late").await.is_err());
+    assert_eq!(manager.get_job_status("late").await.unwrap().state, nusalaunchd::job::JobState::Backoff);
+    
+    // The program is deployed while the job backs off
+    std::fs::write(&program, "#!/bin/sh\nexec sleep 30\n").unwrap();
+    std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+    
+    let mut spawn_failed = false;
+    let started = tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(event) = event_rx.recv().await {
+            match event {
+                nusalaunchd::job::JobEvent::SpawnFailed(..) => spawn_failed = true,
+                nusalaunchd::job::JobEvent::JobStarted(..) => return true,
+                _ => {}
+            }
+        }
+        false
+    }).await;
+    
+    assert!(spawn_failed);
+    assert_eq!(started, Ok(true));
+    assert_eq!(manager.get_job_status("late").await.unwrap().state, nusalaunchd::job::JobState::Running);
+    
+    manager.kill_job("late").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_spawn_failure_fails_without_retry() {
+    let (mut manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.set_auto_start(false);
+    
+    let config: JobConfig = toml::from_str(r#"
+        label = "missing"
+        
+        [program]
+        path = "/nonexistent/service"
+        
+        [supervision]
+        keep_alive = true
+    "#).unwrap();
+    
+    manager.load_job(config).await.unwrap();
+    assert!(manager.start_job("missing").await.is_err());
+    match manager.get_job_status("missing").await.unwrap().state {
+        nusalaunchd::job::JobState::Failed(reason) => assert!(reason.contains("Failed to start"), "{}", reason),
+        other => panic!("unexpected state {:?}", other),
+    }
+}
+
+fn delayed_job(label: &str) -> JobConfig {
+    toml::from_str(&format!(r#"
+        label = "{}"
+        
+        [program]
+        path = "/bin/sleep"
+        arguments = ["30"]
+        
+        [supervision]
+        keep_alive = true
+        start_delay_sec = 1
+    "#, label)).unwrap()
+}
+
+#[tokio::test]
+async fn test_start_delay_defers_spawn() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(delayed_job("delayed")).await.unwrap();
+    
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    let waiting = manager.get_job_status("delayed").await.unwrap();
+    assert_eq!(waiting.state, nusalaunchd::job::JobState::Waiting);
+    assert!(waiting.pid.is_none());
+    
+    tokio::time::sleep(Duration::from_millis(1000)).await;
+    let running = manager.get_job_status("delayed").await.unwrap();
+    assert_eq!(running.state, nusalaunchd::job::JobState::Running);
+    assert!(running.pid.is_some());
+    
+    manager.kill_job("delayed").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_stop_cancels_start_delay() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(delayed_job("cancelled")).await.unwrap();
+    
+    manager.stop_job("cancelled").await.unwrap();
+    
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    let status = manager.get_job_status("cancelled").await.unwrap();
+    assert_eq!(status.state, nusalaunchd::job::JobState::Stopped);
+    assert!(status.pid.is_none());
 }
\ No newline at end of file
@@ -0,0 +1,28 @@
+use std::process::Command;
+use tempfile::TempDir;
+
+const NUSALAUNCHD: &str = env!("CARGO_BIN_EXE_nusalaunchd");
+
+#[test]
+fn test_json_log_format_emits_json_lines() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let output = Command::new(NUSALAUNCHD)
+        .arg("--config-dir").arg(temp_dir.path())
+        .args(["--log-format", "json", "daemon", "--validate-only"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    
+    // Logs go to stderr, one JSON object per line
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let lines: Vec<&str> = stderr.lines().filter(|line| !line.trim().is_empty()).collect();
+    assert!(!lines.is_empty(), "no log output");
+    
+    for line in lines {
+        let record: serde_json::Value = serde_json::from_str(line)
+            .unwrap_or_else(|e| panic!("not JSON ({}): {}", e, line));
+        assert!(record["level"].is_string(), "{}", line);
+        assert!(record["message"].is_string(), "{}", line);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,86 @@
+use nusalaunchd::job::{JobConfig, JobManager};
+use std::io::{BufRead, Write};
+use std::os::fd::FromRawFd;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+
+/// Set when this test binary is re-executed as the activated job
+const LISTEN_ECHO_ENV: &str = "NUSA_LISTEN_ECHO";
+
+/// The activated job, not a test of its own: checks LISTEN_PID, accepts on fd 3 and
+/// echoes a line with LISTEN_FDS / LISTEN_FDNAMES
+#[test]
+fn listen_echo() {
+    if std::env::var_os(LISTEN_ECHO_ENV).is_none() {
+        return;
+    }
+    
+    let listen_pid = std::env::var("LISTEN_PID").ok().and_then(|pid| pid.parse::<u32>().ok());
+    assert_eq!(listen_pid, Some(std::process::id()));
+    let fds = std::env::var("LISTEN_FDS").unwrap();
+    let names = std::env::var("LISTEN_FDNAMES").unwrap_or_default();
+    
+    // SAFETY: accept only writes through null pointers it is told to ignore
+    let fd = unsafe { libc::accept(3, std::ptr::null_mut(), std::ptr::null_mut()) };
+    assert!(fd >= 0, "accept on fd 3: {}", std::io::Error::last_os_error());
+    
+    // SAFETY: accept returned a new descriptor that nothing else owns
+    let stream = unsafe { std::fs::File::from_raw_fd(fd) };
+    let mut line = String::new();
+    std::io::BufReader::new(&stream).read_line(&mut line).unwrap();
+    writeln!(&stream, "{} {} {}", fds, names, line.trim_end()).unwrap();
+}
+
+#[tokio::test]
+async fn test_sd_listen_fds_protocol() {
+    let temp_dir = TempDir::new().unwrap();
+    let first = temp_dir.path().join("first.sock");
+    let second = temp_dir.path().join("second.sock");
+    
+    // The first socket is named, the second falls back to the job label
+    let config: JobConfig = toml::from_str(&format!(
+        r#"
+        label = "echo"
+        
+        [program]
+        path = "{}"
+        arguments = ["--exact", "listen_echo", "--nocapture"]
+        
+        [supervision]
+        keep_alive = false
+        
+        [[sockets]]
+        listen = "{}"
+        name = "control"
+        
+        [[sockets]]
+        listen = "{}"
+        
+        [[environment]]
+        key = "{}"
+        value = "1"
+        "#,
+        std::env::current_exe().unwrap().display(),
+        first.display(),
+        second.display(),
+        LISTEN_ECHO_ENV
+    )).unwrap();
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(config).await.expect("Failed to load job");
+    
+    let stream = UnixStream::connect(&first).await.unwrap();
+    let (reader, mut writer) = stream.into_split();
+    writer.write_all(b"hello\n").await.unwrap();
+    
+    let mut reply = String::new();
+    tokio::time::timeout(Duration::from_secs(5), BufReader::new(reader).read_line(&mut reply))
+        .await
+        .expect("No reply from the activated job")
+        .unwrap();
+    
+    assert_eq!(reply, "2 control:echo hello\n");
+    assert_eq!(manager.list_sockets().await[0].activations, 1);
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+const NUSALAUNCHD: &str = env!("CARGO_BIN_EXE_nusalaunchd");
+
+/// Run `daemon --validate-only` with every file the daemon could write pointed into `root`
+fn validate_only(root: &Path) -> Output {
+    Command::new(NUSALAUNCHD)
+        .arg("--config-dir").arg(root.join("jobs"))
+        .args(["daemon", "--validate-only"])
+        .arg("--pid-file").arg(root.join("nusalaunchd.pid"))
+        .arg("--log-file").arg(root.join("nusalaunchd.log"))
+        .arg("--state-dir").arg(root.join("state"))
+        .arg("--runtime-dir").arg(root.join("run"))
+        .arg("--event-log").arg(root.join("events.jsonl"))
+        .arg("--daemon-config").arg(root.join("daemon.toml"))
+        .output()
+        .unwrap()
+}
+
+fn entries(root: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(root).unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+fn setup(jobs: &[(&str, &str)]) -> TempDir {
+    let root = TempDir::new().unwrap();
+    std::fs::create_dir(root.path().join("jobs")).unwrap();
+    for (name, contents) in jobs {
+        std::fs::write(root.path().join("jobs").join(name), contents).unwrap();
+    }
+    
+    // Applied, this would reject the second job
+    std::fs::write(root.path().join("daemon.toml"), "max_jobs = 1\n").unwrap();
+    root
+}
+
+#[test]
+fn test_validate_only_accepts_good_configs_without_writing_files() {
+    let root = setup(&[
+        ("web.toml", "label = \"web\"\n[program]\npath = \"/bin/true\"\n"),
+        ("db.toml", "label = \"db\"\n[program]\npath = \"/bin/true\"\n"),
+    ]);
+    
+    let output = validate_only(root.path());
+    assert!(output.status.success(), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Validated 2 jobs (0 failed)"), "{:?}", output);
+    assert_eq!(entries(root.path()), ["daemon.toml", "jobs"]);
+}
+
+#[test]
+fn test_validate_only_fails_on_bad_configs_without_writing_files() {
+    let root = setup(&[
+        ("web.toml", "label = \"web\"\n[program]\npath = \"/bin/true\"\n"),
+        ("broken.toml", "label = \"broken\"\n[program\n"),
+    ]);
+    
+    let output = validate_only(root.path());
+    assert!(!output.status.success(), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("(1 failed)"), "{:?}", output);
+    assert_eq!(entries(root.path()), ["daemon.toml", "jobs"]);
+}
\ No newline at end of file
@@ -1,8 +1,8 @@
 use nusalaunchd::job::config::JobConfig;
 use tempfile::NamedTempFile;
 
-#[test]
-fn test_basic_config_parsing() {
+#[tokio::test]
+async fn test_basic_config_parsing() {
     let toml_content = r#"
         label = "test-service"
         
@@ -17,15 +17,15 @@ fn test_basic_config_parsing() {
     let mut file = NamedTempFile::new().unwrap();
     std::io::Write::write_all(&mut file, toml_content.as_bytes()).unwrap();
     
-    let config = JobConfig::from_file(file.path()).unwrap();
+    let config = JobConfig::from_file(file.path()).await.unwrap();
     
     assert_eq!(config.label, "test-service");
     assert_eq!(config.program.path, std::path::PathBuf::from("/bin/true"));
     assert_eq!(config.supervision.keep_alive, true);
 }
 
-#[test]
-fn test_environment_vars() {
+#[tokio::test]
+async fn test_environment_vars() {
     let toml_content = r#"
         label = "env-test"
         
@@ -44,7 +44,7 @@ fn test_environment_vars() {
     let mut file = NamedTempFile::new().unwrap();
     std::io::Write::write_all(&mut file, toml_content.as_bytes()).unwrap();
     
-    let config = JobConfig::from_file(file.path()).unwrap();
+    let config = JobConfig::from_file(file.path()).await.unwrap();
     
     assert_eq!(config.environment.len(), 2);
     assert_eq!(config.environment[0].key, "HOME");
@@ -0,0 +1,80 @@
+use clap::{CommandFactory, Parser};
+use nusalaunchd::cli::args::LogLevel;
+use nusalaunchd::cli::CliArgs;
+
+fn log_level(args: &[&str]) -> LogLevel {
+    CliArgs::parse_from(std::iter::once("nusalaunchd").chain(args.iter().copied())).effective_log_level()
+}
+
+#[test]
+fn test_verbosity_flags_override_log_level() {
+    assert_eq!(log_level(&["status"]), LogLevel::Info);
+    assert_eq!(log_level(&["--log-level", "warn", "status"]), LogLevel::Warn);
+    
+    assert_eq!(log_level(&["-v", "status"]), LogLevel::Debug);
+    assert_eq!(log_level(&["status", "-vv"]), LogLevel::Trace);
+    assert_eq!(log_level(&["-vvv", "status"]), LogLevel::Trace);
+    assert_eq!(log_level(&["--log-level", "error", "-v", "status"]), LogLevel::Debug);
+    
+    assert_eq!(log_level(&["-q", "status"]), LogLevel::Error);
+    assert_eq!(log_level(&["--log-level", "trace", "-qq", "status"]), LogLevel::Error);
+    
+    assert!(CliArgs::try_parse_from(["nusalaunchd", "-v", "-q", "status"]).is_err());
+}
+
+#[test]
+fn test_timeout_is_global() {
+    let timeout = |args: &[&str]| CliArgs::parse_from(std::iter::once("nusalaunchd").chain(args.iter().copied())).timeout;
+    
+    assert_eq!(timeout(&["status"]), None);
+    assert_eq!(timeout(&["--timeout", "3", "status"]), Some(3));
+    assert_eq!(timeout(&["job", "start", "web", "--wait", "--timeout", "20"]), Some(20));
+}
+
+#[tokio::test]
+async fn test_daemon_paths_made_absolute() {
+    use nusalaunchd::cli::Commands;
+    use nusalaunchd::daemon::DaemonConfig;
+    
+    let mut args = CliArgs::parse_from([
+        "nusalaunchd", "daemon", "--pid-file", "run/nusalaunchd.pid", "--state-dir", "state",
+        "--runtime-dir", "/run/nusalaunchd", "--daemon-config", "daemon.toml",
+    ]);
+    let Some(Commands::Daemon { daemon_opts }) = &mut args.command else {
+        panic!("not a daemon command");
+    };
+    daemon_opts.make_absolute().unwrap();
+    
+    let cwd = std::env::current_dir().unwrap();
+    assert_eq!(daemon_opts.pid_file, cwd.join("run/nusalaunchd.pid"));
+    assert_eq!(daemon_opts.state_dir, cwd.join("state"));
+    assert_eq!(daemon_opts.runtime_dir, std::path::PathBuf::from("/run/nusalaunchd"));
+    assert_eq!(daemon_opts.daemon_config, Some(cwd.join("daemon.toml")));
+    
+    // A relative control socket in the daemon config is next to the file
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("daemon.toml");
+    std::fs::write(&path, "control_socket = \"control.sock\"\n").unwrap();
+    let config = DaemonConfig::from_file(&path).await.unwrap();
+    assert_eq!(config.control_socket, Some(temp_dir.path().join("control.sock")));
+    
+    // Without one the daemon keeps its socket in the runtime dir
+    std::fs::write(&path, "max_jobs = 4\n").unwrap();
+    assert_eq!(DaemonConfig::from_file(&path).await.unwrap().control_socket, None);
+}
+
+#[test]
+fn test_subcommand_flags_do_not_clash_with_global_ones() {
+    // clap checks every subcommand's flags against the global ones here
+    CliArgs::command().debug_assert();
+    
+    let args = CliArgs::parse_from(["nusalaunchd", "job", "stop", "--force", "web"]);
+    assert!(!args.foreground);
+    assert!(matches!(
+        args.command,
+        Some(nusalaunchd::cli::args::Commands::Job {
+            job_command: nusalaunchd::cli::args::JobCommands::Stop { force: true, .. },
+        })
+    ));
+    assert!(CliArgs::parse_from(["nusalaunchd", "daemon", "-f"]).foreground);
+}
\ No newline at end of file
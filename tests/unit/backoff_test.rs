@@ -0,0 +1,48 @@
+use nusalaunchd::job::config::{BackoffStrategy, SupervisionConfig};
+use std::time::Duration;
+
+fn supervision(restart_delay_sec: u64, backoff_strategy: BackoffStrategy) -> SupervisionConfig {
+    SupervisionConfig {
+        keep_alive: true,
+        restart_delay_sec,
+        backoff_strategy,
+        ..Default::default()
+    }
+}
+
+fn backoffs(config: &SupervisionConfig) -> Vec<u64> {
+    (0..=10).map(|attempt| config.restart_backoff(attempt).as_secs()).collect()
+}
+
+#[test]
+fn test_exponential_backoff_attempts() {
+    assert_eq!(
+        backoffs(&supervision(1, BackoffStrategy::Exponential)),
+        vec![1, 2, 4, 8, 16, 32, 64, 64, 64, 64, 64]
+    );
+    assert_eq!(
+        backoffs(&supervision(5, BackoffStrategy::Exponential)),
+        vec![5, 10, 20, 40, 80, 160, 300, 300, 300, 300, 300]
+    );
+}
+
+#[test]
+fn test_linear_backoff_attempts() {
+    assert_eq!(
+        backoffs(&supervision(30, BackoffStrategy::Linear)),
+        vec![30, 60, 90, 120, 150, 180, 210, 240, 270, 300, 300]
+    );
+}
+
+#[test]
+fn test_fixed_backoff_attempts() {
+    assert_eq!(backoffs(&supervision(7, BackoffStrategy::Fixed)), vec![7; 11]);
+}
+
+#[test]
+fn test_backoff_never_below_configured_delay() {
+    // The ceiling limits growth, not an explicitly long delay
+    let config = supervision(600, BackoffStrategy::Exponential);
+    assert_eq!(config.restart_backoff(0), Duration::from_secs(600));
+    assert_eq!(config.restart_backoff(10), Duration::from_secs(600));
+}
\ No newline at end of file
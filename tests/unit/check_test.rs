@@ -0,0 +1,73 @@
+mod common;
+
+use nusalaunchd::cli::args::OutputFormat;
+use nusalaunchd::job::config::JobConfig;
+use nusalaunchd::job::{JobManager, JobState};
+use nusalaunchd::process::check::check_spawn;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn job(dir: &Path, program: &Path, extra: &str) -> JobConfig {
+    common::job_config(&format!(
+        "label = \"web\"\nworking_directory = \"{}\"\n{}\n[program]\npath = \"{}\"\narguments = [\"-c\", \"true\"]\n\
+         [supervision]\nkeep_alive = false\n[logging]\nstdout_path = \"out/web.log\"\n",
+        dir.display(), extra, program.display()
+    ))
+}
+
+#[test]
+fn test_check_resolves_without_running() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("web.env"), "PORT=8080\n").unwrap();
+    
+    let config = job(
+        temp_dir.path(),
+        Path::new("/bin/sh"),
+        "env_file = \"web.env\"\n[[environment]]\nkey = \"MODE\"\nvalue = \"prod\"",
+    );
+    let check = check_spawn(&config);
+    
+    assert!(check.ok(), "{:?}", check.problems);
+    assert_eq!(check.uid, nix::unistd::geteuid().as_raw());
+    assert_eq!(check.environment, vec!["PORT", "MODE"]);
+    assert_eq!(check.stdout, temp_dir.path().join("out/web.log").display().to_string());
+    assert_eq!(check.stderr, "daemon log");
+    
+    // Nothing was created on the way
+    assert!(!temp_dir.path().join("out").exists());
+    
+    let text = nusalaunchd::cli::check::render(&[check], &OutputFormat::Table).unwrap();
+    assert!(text.starts_with("✓ web: would start\n"));
+    assert!(text.contains("  command:     /bin/sh -c true\n"));
+}
+
+#[test]
+fn test_check_reports_every_problem() {
+    let temp_dir = TempDir::new().unwrap();
+    let script = temp_dir.path().join("script.sh");
+    std::fs::write(&script, "#!/bin/sh\n").unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o644)).unwrap();
+    
+    let mut config = job(temp_dir.path(), &script, "env_file = \"missing.env\"");
+    config.working_directory = Some(temp_dir.path().join("gone"));
+    let check = check_spawn(&config);
+    
+    assert!(!check.ok());
+    assert_eq!(check.problems.len(), 3, "{:?}", check.problems);
+    assert!(check.problems.iter().any(|problem| problem.contains("missing.env")));
+    assert!(check.problems.iter().any(|problem| problem.contains("gone")));
+    assert!(check.problems.iter().any(|problem| problem.contains("not executable")));
+}
+
+#[tokio::test]
+async fn test_check_job_leaves_job_stopped() {
+    let temp_dir = TempDir::new().unwrap();
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(job(temp_dir.path(), Path::new("/bin/sh"), "")).await.unwrap();
+    
+    assert!(manager.check_job("web").await.unwrap().ok());
+    assert_eq!(manager.get_job_status("web").await.unwrap().state, JobState::Stopped);
+    
+    assert!(manager.check_job("nope").await.is_err());
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+//! Fixtures shared by the unit test binaries
+
+use nusalaunchd::job::JobConfig;
+
+/// Parse a job config from TOML and validate it as loading a config file would, so a
+/// fixture can't describe a job the daemon would refuse
+///
+/// Validation runs on a runtime of its own, which lets sync and async tests share this.
+pub fn job_config(toml: &str) -> JobConfig {
+    let config: JobConfig = toml::from_str(toml)
+        .unwrap_or_else(|e| panic!("fixture does not parse: {}\n{}", e, toml));
+    
+    let checked = config.clone();
+    let validated = std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(checked.validate())
+    }).join().unwrap();
+    validated.unwrap_or_else(|e| panic!("fixture is invalid: {}\n{}", e, toml));
+    
+    config
+}
\ No newline at end of file
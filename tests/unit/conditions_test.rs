@@ -0,0 +1,82 @@
+mod common;
+
+use nusalaunchd::job::config::JobConfig;
+use nusalaunchd::job::{JobEvent, JobManager, JobState};
+use tempfile::TempDir;
+
+fn job(conditions: &str) -> JobConfig {
+    common::job_config(&format!(
+        "label = \"guarded\"\n[program]\npath = \"/bin/sleep\"\narguments = [\"30\"]\n\
+         [supervision]\nkeep_alive = false\n[conditions]\n{}\n",
+        conditions
+    ))
+}
+
+#[tokio::test]
+async fn test_conditions_validated() {
+    assert!(job("path_exists = [\"/etc\"]\nexec = \"true\"").validate().await.is_ok());
+    
+    let mut config = job("");
+    config.conditions.path_absent = vec!["relative/flag".into()];
+    let err = config.validate().await.unwrap_err();
+    assert!(err.to_string().contains("relative/flag"), "{}", err);
+    
+    let mut config = job("");
+    config.conditions.exec = Some(" ".to_string());
+    assert!(config.validate().await.is_err());
+    assert!(toml::from_str::<JobConfig>(
+        "label = \"x\"\n[program]\npath = \"/bin/true\"\n[conditions]\npath_exist = [\"/etc\"]\n"
+    ).is_err());
+}
+
+#[tokio::test]
+async fn test_missing_path_skips_until_it_exists() {
+    let temp_dir = TempDir::new().unwrap();
+    let flag = temp_dir.path().join("ready");
+    
+    let (mut manager, mut event_rx) = JobManager::new().await.unwrap();
+    manager.set_auto_start(false);
+    manager.load_job(job(&format!("path_exists = [\"{}\"]", flag.display()))).await.unwrap();
+    
+    assert!(manager.start_job_detailed("guarded").await.unwrap().is_none());
+    match manager.get_job_status("guarded").await.unwrap().state {
+        JobState::Skipped(reason) => assert!(reason.contains("does not exist"), "{}", reason),
+        state => panic!("unexpected state {:?}", state),
+    }
+    
+    let mut skipped = false;
+    while let Ok(event) = event_rx.try_recv() {
+        skipped |= matches!(event, JobEvent::JobSkipped(ref label, _) if label == "guarded");
+    }
+    assert!(skipped);
+    
+    // Re-evaluated on the next start
+    std::fs::write(&flag, "").unwrap();
+    assert!(manager.start_job_detailed("guarded").await.unwrap().is_some());
+    assert_eq!(manager.get_job_status("guarded").await.unwrap().state, JobState::Running);
+    
+    manager.kill_job("guarded").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_exec_condition() {
+    let temp_dir = TempDir::new().unwrap();
+    let flag = temp_dir.path().join("maintenance");
+    std::fs::write(&flag, "").unwrap();
+    
+    let (mut manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.set_auto_start(false);
+    manager.load_job(job(&format!("exec = \"test ! -e {}\"", flag.display()))).await.unwrap();
+    
+    manager.start_job("guarded").await.unwrap();
+    match manager.get_job_status("guarded").await.unwrap().state {
+        JobState::Skipped(reason) => assert!(reason.contains("exec condition"), "{}", reason),
+        state => panic!("unexpected state {:?}", state),
+    }
+    
+    std::fs::remove_file(&flag).unwrap();
+    manager.start_job("guarded").await.unwrap();
+    assert_eq!(manager.get_job_status("guarded").await.unwrap().state, JobState::Running);
+    
+    manager.kill_job("guarded").await.unwrap();
+}
\ No newline at end of file
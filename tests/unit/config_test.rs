@@ -1,15 +1,17 @@
-use nusalaunchd::job::config::JobConfig;
-use tempfile::NamedTempFile;
+use nusalaunchd::job::config::{JobConfig, JobConfigFile};
+use nusalaunchd::util::error::{ConfigError, NusaError};
+use tempfile::{NamedTempFile, TempDir};
 
-#[test]
-fn test_config_parsing() {
+#[tokio::test]
+async fn test_config_parsing() {
     let toml_content = r#"
         label = "web-server"
         description = "Nginx web server"
+        working_directory = "/var/www"
         
         [program]
         path = "/usr/sbin/nginx"
-        arguments = [")g", "daemon off;"]
+        arguments = ["-g", "daemon off;"]
         
         [supervision]
         keep_alive = true
@@ -24,8 +26,6 @@ fn test_config_parsing() {
         [[environment]]
         key = "RUST_LOG"
         value = "info"
-        
-        working_directory = "/var/www"
     "#;
     
     let mut file = NamedTempFile::new().unwrap();
@@ -44,8 +44,8 @@ fn test_config_parsing() {
     assert_eq!(config.working_directory.unwrap(), std::path::PathBuf::from("/var/www"));
 }
 
-#[test]
-fn test_config_validation() {
+#[tokio::test]
+async fn test_config_validation() {
     // Test empty label
     let toml_content = r#"
         label = ""
@@ -60,8 +60,8 @@ fn test_config_validation() {
     assert!(result.is_err());
 }
 
-#[test]
-fn test_environment_parsing() {
+#[tokio::test]
+async fn test_environment_parsing() {
     let toml_content = r#"
         label = "env-test"
         
@@ -100,4 +100,178 @@ fn test_environment_parsing() {
     assert_eq!(env_map.get("HOME"), Some(&"/tmp/test".to_string()));
     assert_eq!(env_map.get("PATH"), Some(&"/usr/bin:/bin".to_string()));
     assert_eq!(env_map.get("DEBUG"), Some(&"1".to_string()));
+}
+
+#[test]
+fn test_expand_vars() {
+    use nusalaunchd::job::config::expand_vars;
+    
+    std::env::set_var("NUSA_TEST_CACHE", "/srv/cache");
+    std::env::remove_var("NUSA_TEST_UNSET");
+    
+    assert_eq!(expand_vars("${NUSA_TEST_CACHE}/web", false).unwrap(), "/srv/cache/web");
+    assert_eq!(expand_vars("$NUSA_TEST_CACHE/web", false).unwrap(), "/srv/cache/web");
+    assert_eq!(expand_vars("cost: $$5 $", false).unwrap(), "cost: $5 $");
+    assert_eq!(expand_vars("a${NUSA_TEST_UNSET}b", false).unwrap(), "ab");
+    
+    assert!(expand_vars("$NUSA_TEST_UNSET", true).is_err());
+    assert!(expand_vars("${NUSA_TEST_CACHE", false).is_err());
+    assert!(expand_vars("${1BAD}", false).is_err());
+}
+
+#[tokio::test]
+async fn test_from_file_expands_environment() {
+    std::env::set_var("NUSA_TEST_HOME", "/home/nusa");
+    
+    let toml_content = r#"
+        label = "cache"
+        working_directory = "${NUSA_TEST_HOME}/work"
+        expand_env = true
+        
+        [program]
+        path = "/bin/true"
+        arguments = ["--dir", "$NUSA_TEST_HOME/cache", "$${NUSA_TEST_HOME}"]
+        
+        [[environment]]
+        key = "CACHE_DIR"
+        value = "${NUSA_TEST_HOME}/cache"
+    "#;
+    
+    let mut file = NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut file, toml_content.as_bytes()).unwrap();
+    
+    let config = JobConfig::from_file(&file).await.unwrap();
+    
+    assert_eq!(config.program.arguments, vec!["--dir", "/home/nusa/cache", "${NUSA_TEST_HOME}"]);
+    assert_eq!(config.environment[0].value, "/home/nusa/cache");
+    assert_eq!(config.working_directory, Some(std::path::PathBuf::from("/home/nusa/work")));
+}
+
+#[tokio::test]
+async fn test_from_file_leaves_variables_without_expand_env() {
+    std::env::set_var("LISTEN_FDS", "3");
+    
+    let toml_content = r#"
+        label = "shell"
+        
+        [program]
+        path = "/bin/sh"
+        arguments = ["-c", "echo $LISTEN_FDS", "${HOME}"]
+        
+        [[environment]]
+        key = "CACHE_DIR"
+        value = "${HOME}/cache"
+    "#;
+    
+    let mut file = NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut file, toml_content.as_bytes()).unwrap();
+    
+    let config = JobConfig::from_file(&file).await.unwrap();
+    
+    assert_eq!(config.program.arguments, vec!["-c", "echo $LISTEN_FDS", "${HOME}"]);
+    assert_eq!(config.environment[0].value, "${HOME}/cache");
+}
+
+#[tokio::test]
+async fn test_job_array_file() {
+    let toml_content = r#"
+        [[job]]
+        label = "worker-a"
+        [job.program]
+        path = "/bin/true"
+        
+        [[job]]
+        label = "worker-b"
+        [job.program]
+        path = "/bin/true"
+        [job.supervision]
+        keep_alive = false
+    "#;
+    
+    let mut file = NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut file, toml_content.as_bytes()).unwrap();
+    
+    let jobs = JobConfigFile::from_file(&file).await.unwrap().into_jobs();
+    let labels: Vec<&str> = jobs.iter().map(|job| job.label.as_str()).collect();
+    assert_eq!(labels, vec!["worker-a", "worker-b"]);
+    assert!(jobs[0].supervision.keep_alive);
+    assert!(!jobs[1].supervision.keep_alive);
+    
+    // A single-job load refuses to pick one of several
+    let err = JobConfig::from_file(&file).await.unwrap_err();
+    assert!(err.to_string().contains("defines 2 jobs"), "{}", err);
+    
+    // A plain file is one job either way
+    let mut single = NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut single, b"label = \"solo\"\n[program]\npath = \"/bin/true\"\n").unwrap();
+    assert!(matches!(JobConfigFile::from_file(&single).await.unwrap(), JobConfigFile::Single(config) if config.label == "solo"));
+}
+
+#[tokio::test]
+async fn test_job_array_rejects_duplicate_labels() {
+    let toml_content = r#"
+        [[job]]
+        label = "worker"
+        [job.program]
+        path = "/bin/true"
+        
+        [[job]]
+        label = "worker"
+        [job.program]
+        path = "/bin/false"
+    "#;
+    
+    let mut file = NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut file, toml_content.as_bytes()).unwrap();
+    
+    let err = JobConfigFile::from_file(&file).await.unwrap_err();
+    assert!(err.to_string().contains("Duplicate label 'worker'"), "{}", err);
+    
+    // Top-level job fields next to the array are not allowed
+    let mut mixed = NamedTempFile::new().unwrap();
+    std::io::Write::write_all(
+        &mut mixed,
+        b"label = \"x\"\n[[job]]\nlabel = \"y\"\n[job.program]\npath = \"/bin/true\"\n",
+    ).unwrap();
+    assert!(JobConfigFile::from_file(&mixed).await.is_err());
+}
+
+#[tokio::test]
+async fn test_json_and_yaml_configs() {
+    let dir = TempDir::new().unwrap();
+    
+    let json = dir.path().join("web.json");
+    std::fs::write(&json, r#"{
+        "label": "web",
+        "program": { "path": "/usr/sbin/nginx", "arguments": ["-g", "daemon off;"] },
+        "supervision": { "keep_alive": false, "max_restarts": 2 }
+    }"#).unwrap();
+    let config = JobConfig::from_file(&json).await.unwrap();
+    assert_eq!(config.label, "web");
+    assert_eq!(config.program.arguments, vec!["-g", "daemon off;"]);
+    assert!(!config.supervision.keep_alive);
+    assert_eq!(config.supervision.max_restarts, 2);
+    
+    for name in ["worker.yaml", "worker.yml"] {
+        let yaml = dir.path().join(name);
+        std::fs::write(&yaml, "label: worker\nprogram:\n  path: /bin/true\nsupervision:\n  max_restarts: 7\n").unwrap();
+        let config = JobConfig::from_file(&yaml).await.unwrap();
+        assert_eq!(config.label, "worker");
+        assert_eq!(config.supervision.max_restarts, 7);
+    }
+    
+    // Each format reports its own syntax errors
+    std::fs::write(&json, "{ \"label\": ").unwrap();
+    let err = JobConfig::from_file(&json).await.unwrap_err();
+    assert!(err.to_string().contains("Invalid JSON"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_unknown_extension_is_unsupported() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("worker.ini");
+    std::fs::write(&path, "label = \"worker\"\n[program]\npath = \"/bin/true\"\n").unwrap();
+    
+    let err = JobConfig::from_file(&path).await.unwrap_err();
+    assert!(matches!(err, NusaError::Config(ConfigError::UnsupportedFormat)), "{}", err);
 }
\ No newline at end of file
@@ -0,0 +1,235 @@
+use nusalaunchd::control::protocol::INVALID_REQUEST;
+use nusalaunchd::control::{ControlResponse, ControlServer};
+use nusalaunchd::job::JobManager;
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_bind_replaces_stale_socket() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("control.sock");
+    
+    // A socket file left behind by a daemon that is no longer running
+    drop(UnixListener::bind(&path).unwrap());
+    assert!(path.exists());
+    
+    let (job_manager, _event_rx) = JobManager::new().await.unwrap();
+    let server = ControlServer::bind(path.clone(), job_manager, None).unwrap();
+    
+    assert!(UnixStream::connect(&path).is_ok());
+    
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+    assert_eq!(mode, 0o660);
+    
+    // Handing it to our own group always succeeds
+    server.set_group(&nix::unistd::getegid().to_string()).unwrap();
+    assert!(server.set_group("no-such-group-nusalaunchd").is_err());
+    
+    // The socket file is removed with the server
+    drop(server);
+    assert!(!path.exists());
+}
+
+#[tokio::test]
+async fn test_bind_refuses_live_socket() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("control.sock");
+    
+    let _listener = UnixListener::bind(&path).unwrap();
+    
+    let (job_manager, _event_rx) = JobManager::new().await.unwrap();
+    assert!(ControlServer::bind(path.clone(), job_manager, None).is_err());
+    assert!(path.exists());
+}
+
+#[tokio::test]
+async fn test_connect_explains_unreachable_daemon() {
+    use nusalaunchd::control::ControlClient;
+    use std::time::Duration;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("control.sock");
+    
+    let err = ControlClient::connect(&path, Duration::ZERO).await.err().unwrap();
+    assert!(err.to_string().contains("does not exist"), "{}", err);
+    
+    drop(UnixListener::bind(&path).unwrap());
+    let err = ControlClient::connect(&path, Duration::from_millis(300)).await.err().unwrap();
+    assert!(err.to_string().contains("stale"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_connect_waits_for_starting_daemon() {
+    use nusalaunchd::control::ControlClient;
+    use std::time::Duration;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("control.sock");
+    
+    let (job_manager, _event_rx) = JobManager::new().await.unwrap();
+    let server_path = path.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        let server = ControlServer::bind(server_path, job_manager, None).unwrap();
+        server.run().await;
+    });
+    
+    // The socket does not exist yet when the client starts trying
+    assert!(ControlClient::connect(&path, Duration::from_secs(5)).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_request_times_out_without_answer() {
+    use nusalaunchd::control::{ControlClient, ControlRequest};
+    use std::time::{Duration, Instant};
+    
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("control.sock");
+    
+    // Accepts connections but never answers
+    let listener = tokio::net::UnixListener::bind(&path).unwrap();
+    tokio::spawn(async move {
+        let (_stream, _) = listener.accept().await.unwrap();
+        std::future::pending::<()>().await;
+    });
+    
+    let mut client = ControlClient::connect(&path, Duration::from_secs(1)).await.unwrap();
+    client.set_timeout(Some(Duration::from_millis(300)));
+    
+    let started = Instant::now();
+    let err = client.call::<serde_json::Value>(&ControlRequest::Summary).await.unwrap_err();
+    assert!(err.to_string().contains("Timed out"), "{}", err);
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_events_streamed_until_viewer_leaves() {
+    use nusalaunchd::control::{ControlClient, ControlRequest};
+    use std::time::Duration;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("control.sock");
+    
+    let (mut job_manager, _event_rx) = JobManager::new().await.unwrap();
+    job_manager.set_auto_start(false);
+    tokio::spawn(ControlServer::bind(path.clone(), job_manager.clone(), None).unwrap().run());
+    
+    let mut viewer = ControlClient::connect(&path, Duration::from_secs(1)).await.unwrap();
+    viewer.subscribe_events().await.unwrap();
+    job_manager.load_job(toml::from_str("label = \"watched\"\n[program]\npath = \"/bin/true\"\n").unwrap()).await.unwrap();
+    
+    let record = tokio::time::timeout(Duration::from_secs(5), viewer.next_event()).await.unwrap().unwrap().unwrap();
+    assert_eq!(record["event"], "job-loaded");
+    assert_eq!(record["data"], "watched");
+    assert_eq!(record["message"], "Job loaded: watched");
+    
+    // A viewer that goes away leaves the daemon serving everyone else
+    drop(viewer);
+    job_manager.unload_job("watched").await.unwrap();
+    
+    let mut client = ControlClient::connect(&path, Duration::from_secs(1)).await.unwrap();
+    let summary: nusalaunchd::job::DaemonSummary = client.call(&ControlRequest::Summary).await.unwrap();
+    assert_eq!(summary.total, 0);
+}
+
+#[test]
+fn test_failure_carries_code_and_message() {
+    let response = ControlResponse::failure(nusalaunchd::util::NusaError::JobNotFound("web".into()));
+    assert!(!response.ok);
+    assert_eq!(response.code.as_deref(), Some("job_not_found"));
+    assert_eq!(response.error.as_deref(), Some("Job 'web' not found"));
+    
+    let response = ControlResponse::failure_with_code(INVALID_REQUEST, "Invalid request: eof");
+    assert_eq!(response.code.as_deref(), Some("invalid_request"));
+}
+
+#[tokio::test]
+async fn test_start_reports_process_start_time() {
+    use nusalaunchd::control::protocol::StartResult;
+    use nusalaunchd::control::{ControlClient, ControlRequest};
+    use std::time::{Duration, SystemTime};
+    
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("control.sock");
+    
+    let (mut job_manager, _event_rx) = JobManager::new().await.unwrap();
+    job_manager.set_auto_start(false);
+    job_manager.load_job(toml::from_str(
+        "label = \"sleeper\"\n[program]\npath = \"/bin/sleep\"\narguments = [\"30\"]\n[supervision]\nkeep_alive = false\n"
+    ).unwrap()).await.unwrap();
+    tokio::spawn(ControlServer::bind(path.clone(), job_manager.clone(), None).unwrap().run());
+    
+    let mut client = ControlClient::connect(&path, Duration::from_secs(1)).await.unwrap();
+    let before = SystemTime::now();
+    let started: StartResult = client.call(&ControlRequest::Start { label: "sleeper".into() }).await.unwrap();
+    let start_time = started.start_time.unwrap();
+    assert!(started.pid.is_some());
+    assert!(start_time >= before && start_time <= SystemTime::now(), "{:?}", start_time);
+    
+    // Starting a running job reports the same process and when it started
+    tokio::time::sleep(Duration::from_millis(1100)).await;
+    let again: StartResult = client.call(&ControlRequest::Start { label: "sleeper".into() }).await.unwrap();
+    assert_eq!(again.pid, started.pid);
+    let drift = again.start_time.unwrap().duration_since(start_time)
+        .unwrap_or_else(|e| e.duration());
+    assert!(drift < Duration::from_millis(100), "{:?}", drift);
+    
+    job_manager.kill_job("sleeper").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_diff_only_reads_the_config_dir() {
+    use nusalaunchd::control::{ControlClient, ControlRequest};
+    use std::time::Duration;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("control.sock");
+    let config_dir = temp_dir.path().join("jobs");
+    let other_dir = temp_dir.path().join("other");
+    std::fs::create_dir(&config_dir).unwrap();
+    std::fs::create_dir(&other_dir).unwrap();
+    std::fs::write(config_dir.join("web.toml"), "label = \"web\"\n[program]\npath = \"/bin/true\"\n").unwrap();
+    std::fs::write(other_dir.join("secret.toml"), "password = hunter2\n").unwrap();
+    
+    let (mut job_manager, _event_rx) = JobManager::new().await.unwrap();
+    job_manager.set_config_dir(config_dir.clone());
+    tokio::spawn(ControlServer::bind(path.clone(), job_manager, None).unwrap().run());
+    let mut client = ControlClient::connect(&path, Duration::from_secs(1)).await.unwrap();
+    
+    let response = client.request(&ControlRequest::Diff { path: Some(other_dir) }).await.unwrap();
+    assert!(!response.ok);
+    let error = response.error.unwrap();
+    assert!(error.contains("config directory") && !error.contains("hunter2"), "{}", error);
+    
+    for path in [None, Some(config_dir)] {
+        let response = client.request(&ControlRequest::Diff { path }).await.unwrap();
+        assert!(response.ok, "{:?}", response.error);
+        assert_eq!(response.data.unwrap()["plan"]["added"][0]["label"], "web");
+    }
+}
+
+#[tokio::test]
+async fn test_diff_reads_subdirectories_of_recursive_config() {
+    use nusalaunchd::control::{ControlClient, ControlRequest};
+    use std::time::Duration;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("control.sock");
+    let config_dir = temp_dir.path().join("jobs");
+    std::fs::create_dir_all(config_dir.join("multi-user")).unwrap();
+    std::fs::write(config_dir.join("multi-user/web.toml"), "label = \"web\"\n[program]\npath = \"/bin/true\"\n").unwrap();
+    
+    let (mut job_manager, _event_rx) = JobManager::new().await.unwrap();
+    job_manager.set_auto_start(false);
+    job_manager.set_config_dir(config_dir.clone());
+    job_manager.set_recursive_config(true);
+    job_manager.load_directory(&config_dir).await;
+    tokio::spawn(ControlServer::bind(path.clone(), job_manager, None).unwrap().run());
+    let mut client = ControlClient::connect(&path, Duration::from_secs(1)).await.unwrap();
+    
+    // The job in the subdirectory is neither added again nor reported as removed
+    let response = client.request(&ControlRequest::Diff { path: None }).await.unwrap();
+    assert!(response.ok, "{:?}", response.error);
+    assert_eq!(response.data.unwrap()["rendered"], "No changes");
+}
\ No newline at end of file
@@ -0,0 +1,64 @@
+use nusalaunchd::job::config::{JobConfig, ProgramConfig};
+use nusalaunchd::process::Credentials;
+use nix::unistd::{Gid, Uid};
+use std::path::PathBuf;
+use tempfile::NamedTempFile;
+
+fn program(user: Option<&str>, group: Option<&str>) -> ProgramConfig {
+    ProgramConfig {
+        path: PathBuf::from("/bin/true"),
+        user: user.map(str::to_string),
+        group: group.map(str::to_string),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_no_user_or_group() {
+    let credentials = Credentials::resolve(&program(None, None)).unwrap();
+    assert!(credentials.is_none());
+}
+
+#[test]
+fn test_group_without_user() {
+    let credentials = Credentials::resolve(&program(None, Some("0"))).unwrap().unwrap();
+    
+    // The daemon's user is kept, only the group changes
+    assert_eq!(credentials.uid, None);
+    assert_eq!(credentials.gid, Gid::from_raw(0));
+    assert_eq!(credentials.groups, vec![Gid::from_raw(0)]);
+}
+
+#[test]
+fn test_user_by_name_and_uid() {
+    let by_name = Credentials::resolve(&program(Some("root"), None)).unwrap().unwrap();
+    assert_eq!(by_name.uid, Some(Uid::from_raw(0)));
+    assert_eq!(by_name.gid, Gid::from_raw(0));
+    assert_eq!(by_name.user_name.as_deref(), Some("root"));
+    
+    let by_uid = Credentials::resolve(&program(Some("0"), None)).unwrap().unwrap();
+    assert_eq!(by_uid, by_name);
+}
+
+#[test]
+fn test_unknown_user_and_group() {
+    assert!(Credentials::resolve(&program(Some("nusalaunchd-no-such-user"), None)).is_err());
+    assert!(Credentials::resolve(&program(None, Some("nusalaunchd-no-such-group"))).is_err());
+}
+
+#[tokio::test]
+async fn test_unknown_user_rejected_at_load() {
+    let toml_content = r#"
+        label = "bad-user"
+        
+        [program]
+        path = "/bin/true"
+        user = "nusalaunchd-no-such-user"
+    "#;
+    
+    let mut file = NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut file, toml_content.as_bytes()).unwrap();
+    
+    let result = JobConfig::from_file(file.path()).await;
+    assert!(result.is_err());
+}
\ No newline at end of file
@@ -0,0 +1,208 @@
+mod common;
+
+use nusalaunchd::job::config::JobConfig;
+use nusalaunchd::job::dependency::{dependents, find_cycle, start_order};
+use nusalaunchd::job::{JobEvent, JobManager, JobState};
+
+fn job(label: &str, program: &str, after: &[&str], requires: &[&str]) -> JobConfig {
+    common::job_config(&format!(
+        "label = \"{}\"\nafter = {:?}\nrequires = {:?}\n[program]\npath = \"{}\"\n[supervision]\nkeep_alive = false\n",
+        label, after, requires, program
+    ))
+}
+
+#[test]
+fn test_start_order_puts_dependencies_first() {
+    let configs = vec![
+        job("web", "/bin/true", &["cache"], &["db"]),
+        job("db", "/bin/true", &[], &[]),
+        job("cache", "/bin/true", &[], &["db"]),
+        job("unrelated", "/bin/true", &["not-loaded"], &[]),
+    ];
+    
+    let order = start_order(&configs).unwrap();
+    let position = |label: &str| order.iter().position(|l| l == label).unwrap();
+    
+    assert_eq!(order.len(), 4);
+    assert!(position("db") < position("cache"));
+    assert!(position("cache") < position("web"));
+}
+
+#[test]
+fn test_cycle_is_named() {
+    let configs = vec![
+        job("a", "/bin/true", &[], &["b"]),
+        job("b", "/bin/true", &["c"], &[]),
+        job("c", "/bin/true", &[], &["a"]),
+    ];
+    
+    let cycle = find_cycle(&configs).unwrap();
+    assert_eq!(cycle.first(), cycle.last());
+    assert_eq!(cycle.len(), 4);
+    
+    let err = start_order(&configs).unwrap_err().to_string();
+    assert!(err.contains("Dependency cycle"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_self_dependency_is_a_cycle() {
+    // Past validation, which refuses this on its own
+    let mut config = job("a", "/bin/true", &[], &[]);
+    config.requires = vec!["a".to_string()];
+    assert_eq!(find_cycle(&[config]), Some(vec!["a".to_string(), "a".to_string()]));
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    let mut config = job("a", "/bin/true", &[], &[]);
+    config.after = vec!["a".to_string()];
+    let err = manager.load_job(config).await.unwrap_err();
+    assert!(err.to_string().contains("a -> a"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_load_rejects_cycle() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    
+    manager.load_job(job("a", "/bin/true", &[], &["b"])).await.unwrap();
+    let err = manager.load_job(job("b", "/bin/true", &["a"], &[])).await.unwrap_err();
+    
+    assert!(err.to_string().contains("a -> b -> a") || err.to_string().contains("b -> a -> b"), "{}", err);
+    assert!(manager.get_job_status("b").await.is_none());
+}
+
+#[tokio::test]
+async fn test_failed_requirement_blocks_dependent() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    
+    manager.load_job(job("db", "/nonexistent/postgres", &[], &[])).await.unwrap();
+    manager.load_job(job("web", "/bin/true", &[], &["db"])).await.unwrap();
+    
+    assert!(manager.start_job("db").await.is_err());
+    assert!(matches!(manager.get_job_status("db").await.unwrap().state, JobState::Failed(_)));
+    
+    assert!(manager.start_job("web").await.is_err());
+    assert!(matches!(manager.get_job_status("web").await.unwrap().state, JobState::Blocked(_)));
+}
+
+#[tokio::test]
+async fn test_blocked_dependent_starts_with_requirement() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    
+    for config in [job("db", "/bin/sleep", &[], &[]), job("web", "/bin/sleep", &[], &["db"])] {
+        let mut config = config;
+        config.program.arguments = vec!["30".to_string()];
+        manager.load_job(config).await.unwrap();
+    }
+    
+    // A stopped requirement blocks too, and the reason says why
+    assert!(manager.start_job("web").await.is_err());
+    assert_eq!(
+        manager.get_job_status("web").await.unwrap().state,
+        JobState::Blocked("required job 'db' is stopped".to_string())
+    );
+    
+    manager.start_job("db").await.unwrap();
+    
+    for _ in 0..50 {
+        if manager.get_job_status("web").await.unwrap().state == JobState::Running {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    assert_eq!(manager.get_job_status("web").await.unwrap().state, JobState::Running);
+    
+    manager.stop_job("web").await.unwrap();
+    manager.stop_job("db").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_shutdown_stops_dependents_first() {
+    let (manager, mut event_rx) = JobManager::new().await.unwrap();
+    
+    for config in [job("db", "/bin/sleep", &[], &[]), job("web", "/bin/sleep", &[], &["db"])] {
+        let mut config = config;
+        config.program.arguments = vec!["30".to_string()];
+        manager.load_job(config).await.unwrap();
+    }
+    
+    manager.start_job("db").await.unwrap();
+    manager.start_job("web").await.unwrap();
+    
+    manager.shutdown_all(Some(std::time::Duration::from_secs(5))).await;
+    
+    let mut stopped = Vec::new();
+    while let Ok(event) = event_rx.try_recv() {
+        if let JobEvent::JobStopped(label, _) = event {
+            stopped.push(label);
+        }
+    }
+    
+    assert_eq!(stopped, vec!["web", "db"]);
+    assert_eq!(manager.get_job_status("db").await.unwrap().state, JobState::Stopped);
+}
+
+#[tokio::test]
+async fn test_start_all_and_stop_all_report_each_job() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    
+    for config in [
+        job("web", "/bin/sleep", &[], &["db"]),
+        job("db", "/bin/sleep", &[], &[]),
+        job("broken", "/nonexistent/daemon", &[], &[]),
+    ] {
+        let mut config = config;
+        config.program.arguments = vec!["30".to_string()];
+        config.supervision.keep_alive = false;
+        manager.load_job(config).await.unwrap();
+    }
+    
+    let started = manager.start_all().await;
+    let outcomes: Vec<(&str, bool)> = started.iter()
+        .map(|(label, result)| (label.as_str(), result.is_ok()))
+        .collect();
+    assert_eq!(outcomes.len(), 3);
+    assert!(outcomes.contains(&("broken", false)));
+    
+    let position = |name: &str| outcomes.iter().position(|(label, _)| *label == name).unwrap();
+    assert!(position("db") < position("web"));
+    assert!(outcomes[position("db")].1 && outcomes[position("web")].1);
+    
+    // Only running jobs are stopped, dependents first
+    let stopped: Vec<String> = manager.stop_all().await.into_iter()
+        .map(|(label, result)| {
+            assert!(result.is_ok(), "{}", label);
+            label
+        })
+        .collect();
+    assert_eq!(stopped, vec!["web", "db"]);
+    assert_eq!(manager.get_job_status("web").await.unwrap().state, JobState::Stopped);
+}
+
+#[tokio::test]
+async fn test_remove_job_refused_while_depended_on() {
+    let (mut manager, mut event_rx) = JobManager::new().await.unwrap();
+    manager.set_auto_start(false);
+    manager.load_job(job("db", "/bin/true", &[], &[])).await.unwrap();
+    manager.load_job(job("web", "/bin/true", &[], &["db"])).await.unwrap();
+    manager.load_job(job("worker", "/bin/true", &["db"], &[])).await.unwrap();
+    
+    let configs = vec![job("db", "/bin/true", &[], &[]), job("web", "/bin/true", &[], &["db"])];
+    assert_eq!(dependents("db", &configs), vec!["web"]);
+    
+    let err = manager.remove_job("db").await.unwrap_err();
+    assert!(err.to_string().contains("web, worker"), "{}", err);
+    assert!(manager.get_job_status("db").await.is_some());
+    
+    manager.remove_job("web").await.unwrap();
+    manager.remove_job("worker").await.unwrap();
+    manager.remove_job("db").await.unwrap();
+    assert!(manager.list_jobs().await.is_empty());
+    assert!(manager.remove_job("db").await.is_err());
+    
+    let mut unloaded = Vec::new();
+    while let Ok(event) = event_rx.try_recv() {
+        if let JobEvent::JobUnloaded(label) = event {
+            unloaded.push(label);
+        }
+    }
+    assert_eq!(unloaded, vec!["web", "worker", "db"]);
+}
\ No newline at end of file
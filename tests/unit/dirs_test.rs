@@ -0,0 +1,41 @@
+use nusalaunchd::daemon::dirs::{ensure_dir, RUNTIME_DIR_MODE, STATE_DIR_MODE};
+use std::os::unix::fs::PermissionsExt;
+use tempfile::TempDir;
+
+fn mode(path: &std::path::Path) -> u32 {
+    std::fs::metadata(path).unwrap().permissions().mode() & 0o7777
+}
+
+#[test]
+fn test_creates_directories_with_mode() {
+    let temp_dir = TempDir::new().unwrap();
+    let runtime_dir = temp_dir.path().join("run/nusalaunchd");
+    let state_dir = temp_dir.path().join("state");
+    
+    ensure_dir(&runtime_dir, RUNTIME_DIR_MODE).unwrap();
+    ensure_dir(&state_dir, STATE_DIR_MODE).unwrap();
+    
+    assert_eq!(mode(&runtime_dir), 0o755);
+    assert_eq!(mode(&state_dir), 0o700);
+}
+
+#[test]
+fn test_existing_directory_keeps_its_mode() {
+    let temp_dir = TempDir::new().unwrap();
+    let state_dir = temp_dir.path().join("state");
+    std::fs::create_dir(&state_dir).unwrap();
+    std::fs::set_permissions(&state_dir, std::fs::Permissions::from_mode(0o750)).unwrap();
+    
+    ensure_dir(&state_dir, STATE_DIR_MODE).unwrap();
+    assert_eq!(mode(&state_dir), 0o750);
+}
+
+#[test]
+fn test_refuses_file_in_place_of_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("run");
+    std::fs::write(&path, "").unwrap();
+    
+    let err = ensure_dir(&path, RUNTIME_DIR_MODE).unwrap_err();
+    assert!(err.to_string().contains("not a directory"), "{}", err);
+}
\ No newline at end of file
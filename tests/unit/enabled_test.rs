@@ -0,0 +1,51 @@
+use nusalaunchd::job::config::JobConfig;
+use nusalaunchd::job::enabled::{disable, enable, enabled_labels, target_dir};
+use nusalaunchd::job::{JobManager, JobState};
+use tempfile::TempDir;
+
+#[test]
+fn test_enable_and_disable_markers() {
+    let temp_dir = TempDir::new().unwrap();
+    let state_dir = temp_dir.path();
+    
+    assert_eq!(enabled_labels(state_dir, "multi-user").unwrap(), None);
+    
+    assert!(enable(state_dir, "multi-user", "web").unwrap());
+    assert!(!enable(state_dir, "multi-user", "web").unwrap());
+    assert!(enable(state_dir, "multi-user", "db").unwrap());
+    assert!(enable(state_dir, "graphical", "web").unwrap());
+    assert!(target_dir(state_dir, "multi-user").join("web").exists());
+    
+    assert_eq!(enabled_labels(state_dir, "multi-user").unwrap(), Some(vec!["db".to_string(), "web".to_string()]));
+    
+    // Disabling removes the job from every target
+    assert_eq!(disable(state_dir, "web").unwrap(), vec!["graphical", "multi-user"]);
+    assert!(disable(state_dir, "web").unwrap().is_empty());
+    assert_eq!(enabled_labels(state_dir, "multi-user").unwrap(), Some(vec!["db".to_string()]));
+    assert_eq!(enabled_labels(state_dir, "graphical").unwrap(), Some(Vec::new()));
+    
+    assert!(enable(state_dir, "multi-user", "../escape").is_err());
+    assert!(enable(state_dir, "..", "web").is_err());
+}
+
+#[tokio::test]
+async fn test_start_enabled_pulls_in_requirements() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    
+    for (label, requires) in [("db", ""), ("web", "requires = [\"db\"]\n"), ("other", "")] {
+        let config: JobConfig = toml::from_str(&format!(
+            "label = \"{}\"\n{}[program]\npath = \"/bin/sleep\"\narguments = [\"30\"]\n[supervision]\nkeep_alive = false\n",
+            label, requires
+        )).unwrap();
+        manager.load_job(config).await.unwrap();
+    }
+    
+    manager.start_enabled(&["web".to_string(), "missing".to_string()]).await.unwrap();
+    
+    assert_eq!(manager.get_job_status("web").await.unwrap().state, JobState::Running);
+    assert_eq!(manager.get_job_status("db").await.unwrap().state, JobState::Running);
+    assert_ne!(manager.get_job_status("other").await.unwrap().state, JobState::Running);
+    
+    manager.stop_job("web").await.unwrap();
+    manager.stop_job("db").await.unwrap();
+}
\ No newline at end of file
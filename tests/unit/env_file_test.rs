@@ -0,0 +1,124 @@
+use nusalaunchd::job::config::JobConfig;
+use nusalaunchd::job::JobManager;
+use nusalaunchd::process::env_file::{parse_env_file, resolve_env_file};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[test]
+fn test_parse_env_file() {
+    let vars = parse_env_file(
+        "# database\nDB_HOST=localhost\n\n  DB_PORT = 5432\nexport DB_NAME=\"app data\"\nEMPTY=\nURL=postgres://a?b=c\nQUOTED='x'\n"
+    ).unwrap();
+    
+    assert_eq!(vars, vec![
+        ("DB_HOST".to_string(), "localhost".to_string()),
+        ("DB_PORT".to_string(), "5432".to_string()),
+        ("DB_NAME".to_string(), "app data".to_string()),
+        ("EMPTY".to_string(), String::new()),
+        ("URL".to_string(), "postgres://a?b=c".to_string()),
+        ("QUOTED".to_string(), "x".to_string()),
+    ]);
+    
+    assert!(parse_env_file("GOOD=1\nno equals sign\n").unwrap_err().contains("line 2"));
+    assert!(parse_env_file("1BAD=x\n").is_err());
+}
+
+#[test]
+fn test_env_file_resolves_against_working_directory() {
+    let mut config: JobConfig = toml::from_str(
+        "label = \"web\"\nworking_directory = \"/srv/web\"\nenv_file = \".env\"\n[program]\npath = \"/bin/true\"\n"
+    ).unwrap();
+    assert_eq!(resolve_env_file(&config), Some(PathBuf::from("/srv/web/.env")));
+    
+    config.env_file = Some(PathBuf::from("/etc/web.env"));
+    assert_eq!(resolve_env_file(&config), Some(PathBuf::from("/etc/web.env")));
+    
+    config.env_file = None;
+    assert_eq!(resolve_env_file(&config), None);
+}
+
+#[tokio::test]
+async fn test_spawn_merges_env_file_under_inline_environment() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("job.env"), "FROM_FILE=file\nSHARED=file\n").unwrap();
+    
+    let config: JobConfig = toml::from_str(&format!(
+        r#"
+        label = "env-file"
+        working_directory = "{}"
+        env_file = "job.env"
+        
+        [program]
+        path = "/bin/sh"
+        arguments = ["-c", "echo $FROM_FILE $SHARED"]
+        
+        [[environment]]
+        key = "SHARED"
+        value = "inline"
+        
+        [logging]
+        stdout_path = "out.log"
+        "#,
+        temp_dir.path().display()
+    )).unwrap();
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(config).await.unwrap();
+    manager.start_job("env-file").await.unwrap();
+    
+    assert_eq!(wait_for_output(&temp_dir.path().join("out.log")).await, "file inline\n");
+}
+
+#[tokio::test]
+async fn test_clear_environment_with_default_path() {
+    let temp_dir = TempDir::new().unwrap();
+    std::env::set_var("NUSA_TEST_LEAK", "leaked");
+    
+    for (label, default_path, expected) in [
+        ("cleared", false, "MARKER=1\n"),
+        ("cleared-path", true, "MARKER=1\nPATH=/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin\n"),
+    ] {
+        let config: JobConfig = toml::from_str(&format!(
+            r#"
+            label = "{}"
+            working_directory = "{}"
+            
+            [program]
+            path = "/usr/bin/env"
+            clear_environment = true
+            default_path = {}
+            
+            [[environment]]
+            key = "MARKER"
+            value = "1"
+            
+            [logging]
+            stdout_path = "{}.log"
+            "#,
+            label,
+            temp_dir.path().display(),
+            default_path,
+            label
+        )).unwrap();
+        
+        let (manager, _event_rx) = JobManager::new().await.unwrap();
+        manager.load_job(config).await.unwrap();
+        manager.start_job(label).await.unwrap();
+        
+        let out = temp_dir.path().join(format!("{}.log", label));
+        assert_eq!(wait_for_output(&out).await, expected);
+    }
+}
+
+/// Contents of a job's output file once its first line is complete
+async fn wait_for_output(path: &Path) -> String {
+    for _ in 0..50 {
+        if std::fs::read_to_string(path).map_or(false, |content| content.ends_with('\n')) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    
+    std::fs::read_to_string(path).unwrap()
+}
\ No newline at end of file
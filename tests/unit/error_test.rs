@@ -0,0 +1,55 @@
+use nusalaunchd::util::error::{describe_signal, ConfigError, NusaError, ProcessError, Result};
+use std::path::PathBuf;
+
+fn fails_with_config_error() -> Result<()> {
+    Err(ConfigError::Validation("label is empty".into()))?
+}
+
+#[test]
+fn test_errors_convert_into_nusa_error() {
+    let err = fails_with_config_error().unwrap_err();
+    assert!(matches!(err, NusaError::Config(ConfigError::Validation(_))));
+    assert_eq!(err.to_string(), "Config error: Invalid config: label is empty");
+    
+    let err: NusaError = ProcessError::from("no such file".to_string()).into();
+    assert_eq!(err.to_string(), "Process error: Process error: no such file");
+    
+    let err: NusaError = ConfigError::FileNotFound(PathBuf::from("/etc/web.toml")).into();
+    assert_eq!(err.to_string(), "Config error: File '/etc/web.toml' not found");
+}
+
+#[test]
+fn test_reexported_from_util() {
+    let err = nusalaunchd::util::NusaError::JobNotFound("web".into());
+    assert_eq!(err.to_string(), "Job 'web' not found");
+}
+
+#[test]
+fn test_error_codes_are_stable() {
+    // Control socket clients match on these; changing one is a protocol break
+    let cases: Vec<(NusaError, &str)> = vec![
+        (ConfigError::Parse("x".into()).into(), "config_error"),
+        (ProcessError::Timeout.into(), "process_error"),
+        (std::io::Error::from(std::io::ErrorKind::NotFound).into(), "io_error"),
+        (NusaError::JobNotFound("web".into()), "job_not_found"),
+        (NusaError::JobExists("web".into()), "job_exists"),
+        (NusaError::Blocked("web".into(), "db failed".into()), "job_blocked"),
+        (NusaError::JobLimitExceeded(512), "job_limit_exceeded"),
+        (NusaError::System("x".into()), "system_error"),
+        (NusaError::Control("x".into()), "control_error"),
+    ];
+    
+    for (err, code) in cases {
+        assert_eq!(err.code(), code, "{:?}", err);
+    }
+}
+
+#[test]
+fn test_process_exit_names_signal() {
+    assert!(matches!(ProcessError::from_exit(3, None), ProcessError::Exit(3)));
+    assert_eq!(ProcessError::from_exit(3, None).to_string(), "Process exited with code 3");
+    assert_eq!(ProcessError::from_exit(-1, Some(11)).to_string(), "Process terminated by SIGSEGV (11)");
+    
+    assert_eq!(describe_signal(9), "SIGKILL (9)");
+    assert_eq!(describe_signal(99), "signal 99");
+}
\ No newline at end of file
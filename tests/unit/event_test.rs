@@ -0,0 +1,113 @@
+use nusalaunchd::event::{EventDispatcher, EventOverflow};
+use nusalaunchd::job::{JobEvent, JobManager};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+
+#[tokio::test]
+async fn test_subscribers_see_events_alongside_processor() {
+    let (manager, mut event_rx) = JobManager::new().await.unwrap();
+    let mut first = manager.subscribe();
+    let mut second = manager.subscribe();
+    
+    let config = toml::from_str("label = \"noop\"\n[program]\npath = \"/bin/true\"\n").unwrap();
+    manager.load_job(config).await.unwrap();
+    
+    // The mpsc receiver still gets every event
+    assert!(matches!(event_rx.recv().await, Some(JobEvent::JobLoaded(label)) if label == "noop"));
+    
+    for subscriber in [&mut first, &mut second] {
+        assert!(matches!(subscriber.recv().await, Ok(JobEvent::JobLoaded(label)) if label == "noop"));
+    }
+}
+
+#[tokio::test]
+async fn test_slow_subscriber_lags() {
+    let (tx, _rx) = mpsc::channel(1024);
+    let dispatcher = EventDispatcher::new(tx);
+    let mut subscriber = dispatcher.subscribe();
+    
+    for i in 0..300 {
+        dispatcher.send(JobEvent::JobLoaded(format!("job-{}", i))).await.unwrap();
+    }
+    
+    assert!(matches!(subscriber.recv().await, Err(RecvError::Lagged(44))));
+    assert!(matches!(subscriber.recv().await, Ok(JobEvent::JobLoaded(label)) if label == "job-44"));
+}
+
+#[tokio::test]
+async fn test_drop_oldest_never_blocks() {
+    let (tx, mut rx) = mpsc::channel(2);
+    let dispatcher = EventDispatcher::with_overflow(tx, 2, EventOverflow::DropOldest);
+    
+    // Nothing drains the channel meanwhile; blocking here would hang the test
+    for i in 0..10 {
+        dispatcher.send(JobEvent::JobLoaded(format!("job-{}", i))).await.unwrap();
+    }
+    assert_eq!(dispatcher.dropped(), 8);
+    
+    // The newest events survive, in order
+    assert!(matches!(rx.recv().await, Some(JobEvent::JobLoaded(label)) if label == "job-8"));
+    assert!(matches!(rx.recv().await, Some(JobEvent::JobLoaded(label)) if label == "job-9"));
+}
+
+#[tokio::test]
+async fn test_event_log_is_json_lines() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("events.log");
+    let log = nusalaunchd::util::logwriter::RotatingWriter::open(&path, 0, 1).unwrap();
+    
+    let (tx, rx) = mpsc::channel(16);
+    let dispatcher = EventDispatcher::new(tx);
+    let processor = tokio::spawn(EventDispatcher::process_events(rx, Some(log)));
+    
+    dispatcher.send(JobEvent::JobLoaded("web".to_string())).await.unwrap();
+    dispatcher.send(JobEvent::JobStarted("web".to_string(), 42, std::time::SystemTime::now())).await.unwrap();
+    drop(dispatcher);
+    processor.await.unwrap();
+    
+    let content = std::fs::read_to_string(&path).unwrap();
+    let records: Vec<serde_json::Value> = content.lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0]["event"], "job-loaded");
+    assert_eq!(records[0]["data"], "web");
+    assert_eq!(records[1]["event"], "job-started");
+    assert_eq!(records[1]["data"][1], 42);
+    assert!(records[1]["timestamp"].as_str().unwrap().contains('T'));
+}
+
+#[tokio::test]
+async fn test_event_log_rotates_and_keeps_max_files() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("events.log");
+    let log = nusalaunchd::util::logwriter::RotatingWriter::open(&path, 300, 2).unwrap();
+    
+    let (tx, rx) = mpsc::channel(64);
+    let dispatcher = EventDispatcher::new(tx);
+    let processor = tokio::spawn(EventDispatcher::process_events(rx, Some(log)));
+    
+    for i in 0..50 {
+        dispatcher.send(JobEvent::JobLoaded(format!("job-{}", i))).await.unwrap();
+    }
+    drop(dispatcher);
+    processor.await.unwrap();
+    
+    // The active file and two rotated ones; older events are gone
+    let mut names: Vec<String> = std::fs::read_dir(temp_dir.path()).unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    assert_eq!(names, ["events.log", "events.log.1", "events.log.2"]);
+    
+    for name in &names {
+        let size = std::fs::metadata(temp_dir.path().join(name)).unwrap().len();
+        assert!(size > 0 && size <= 300, "{} is {} bytes", name, size);
+    }
+    
+    let newest = std::fs::read_to_string(&path).unwrap();
+    assert!(newest.lines().last().unwrap().contains("\"job-49\""), "{}", newest);
+    let oldest = std::fs::read_to_string(temp_dir.path().join("events.log.2")).unwrap();
+    assert!(!oldest.contains("\"job-0\""), "{}", oldest);
+}
\ No newline at end of file
@@ -0,0 +1,62 @@
+use nusalaunchd::cli::args::OutputFormat;
+use nusalaunchd::cli::export::{render, render_file};
+use nusalaunchd::job::config::{JobConfig, JobConfigFile};
+
+const WEB_SERVER: &str = include_str!("../../configs/examples/web_server.toml");
+
+#[test]
+fn test_export_fills_in_defaults() {
+    let config: JobConfig = toml::from_str(WEB_SERVER).unwrap();
+    let exported = render(&config, &OutputFormat::Table).unwrap();
+    
+    // Set in the file
+    assert!(exported.contains("restart_delay_sec = 3"), "{}", exported);
+    // Only defaulted
+    assert!(exported.contains("spawn_retries = 3"), "{}", exported);
+    assert!(exported.contains("success_reset_sec = "), "{}", exported);
+    assert!(exported.contains("notify_ready = false"), "{}", exported);
+}
+
+#[test]
+fn test_export_round_trips() {
+    let config: JobConfig = toml::from_str(WEB_SERVER).unwrap();
+    let exported = render(&config, &OutputFormat::Plain).unwrap();
+    
+    let reloaded: JobConfig = toml::from_str(&exported).unwrap();
+    assert_eq!(render(&reloaded, &OutputFormat::Plain).unwrap(), exported);
+}
+
+#[tokio::test]
+async fn test_export_file_keeps_jobs_as_written() {
+    std::env::set_var("NUSA_TEST_EXPORT_HOME", "/home/nusa");
+    
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("workers.toml");
+    std::fs::write(&path, r#"
+        [[job]]
+        label = "worker-a"
+        expand_env = true
+        [job.program]
+        path = "/bin/true"
+        arguments = ["${NUSA_TEST_EXPORT_HOME}/a"]
+        
+        [[job]]
+        label = "worker-b"
+        [job.program]
+        path = "/bin/true"
+    "#).unwrap();
+    
+    let file = JobConfigFile::read(&path).await.unwrap();
+    let exported = render_file(&file, &OutputFormat::Table).unwrap();
+    
+    // Variables are left for the daemon to expand
+    assert!(exported.contains("${NUSA_TEST_EXPORT_HOME}/a"), "{}", exported);
+    assert!(!exported.contains("/home/nusa"), "{}", exported);
+    
+    // Both jobs are exported, and load back as they were
+    assert_eq!(exported.matches("[[job]]").count(), 2, "{}", exported);
+    std::fs::write(&path, &exported).unwrap();
+    let labels: Vec<String> = JobConfigFile::read(&path).await.unwrap().into_jobs()
+        .into_iter().map(|job| job.label).collect();
+    assert_eq!(labels, vec!["worker-a", "worker-b"]);
+}
\ No newline at end of file
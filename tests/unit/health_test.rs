@@ -0,0 +1,94 @@
+use nusalaunchd::job::config::{HealthConfig, JobConfig};
+use nusalaunchd::job::health::{probe, validate_health};
+use nusalaunchd::job::{JobEvent, JobManager, JobState};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+fn health() -> HealthConfig {
+    HealthConfig {
+        command: None,
+        tcp_port: None,
+        http_url: None,
+        interval_sec: 10,
+        timeout_sec: 2,
+        failure_threshold: 3,
+    }
+}
+
+#[test]
+fn test_exactly_one_probe() {
+    assert!(validate_health(&health()).is_err());
+    assert!(validate_health(&HealthConfig { tcp_port: Some(80), command: Some("true".into()), ..health() }).is_err());
+    assert!(validate_health(&HealthConfig { tcp_port: Some(80), ..health() }).is_ok());
+    assert!(validate_health(&HealthConfig { http_url: Some("https://example.com".into()), ..health() }).is_err());
+    assert!(validate_health(&HealthConfig { tcp_port: Some(80), failure_threshold: 0, ..health() }).is_err());
+}
+
+#[tokio::test]
+async fn test_command_probe() {
+    assert!(probe(&HealthConfig { command: Some("true".into()), ..health() }).await.is_ok());
+    assert!(probe(&HealthConfig { command: Some("exit 3".into()), ..health() }).await.is_err());
+}
+
+#[tokio::test]
+async fn test_command_probe_timeout() {
+    let result = probe(&HealthConfig { command: Some("sleep 5".into()), timeout_sec: 1, ..health() }).await;
+    assert!(result.unwrap_err().contains("timed out"));
+}
+
+#[tokio::test]
+async fn test_tcp_probe() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    
+    assert!(probe(&HealthConfig { tcp_port: Some(port), ..health() }).await.is_ok());
+    
+    drop(listener);
+    assert!(probe(&HealthConfig { tcp_port: Some(port), ..health() }).await.is_err());
+}
+
+#[tokio::test]
+async fn test_http_probe_status() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    
+    tokio::spawn(async move {
+        for status in ["200 OK", "503 Service Unavailable"] {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 256];
+            let _ = stream.read(&mut buf).await;
+            let response = format!("HTTP/1.0 {}\r\n\r\n", status);
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+    
+    let url = format!("http://127.0.0.1:{}/health", port);
+    assert!(probe(&HealthConfig { http_url: Some(url.clone()), ..health() }).await.is_ok());
+    assert!(probe(&HealthConfig { http_url: Some(url), ..health() }).await.is_err());
+}
+
+#[tokio::test]
+async fn test_unready_job_fails_after_start_timeout() {
+    let (manager, mut event_rx) = JobManager::new().await.unwrap();
+    
+    // The health check never passes, so the job never becomes ready
+    let config: JobConfig = toml::from_str(
+        "label = \"stuck\"\n[program]\npath = \"/bin/sleep\"\narguments = [\"30\"]\n[supervision]\nkeep_alive = false\nstart_timeout_sec = 1\n[health]\ncommand = \"false\"\ninterval_sec = 5\n"
+    ).unwrap();
+    manager.load_job(config).await.unwrap();
+    manager.start_job("stuck").await.unwrap();
+    assert_eq!(manager.get_job_status("stuck").await.unwrap().state, JobState::Running);
+    
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    
+    let status = manager.get_job_status("stuck").await.unwrap();
+    assert!(matches!(status.state, JobState::Failed(_)), "{}", status.state);
+    assert_eq!(status.pid, None);
+    
+    let mut timed_out = false;
+    while let Ok(event) = event_rx.try_recv() {
+        timed_out |= matches!(event, JobEvent::JobStartTimedOut(ref label, _) if label == "stuck");
+    }
+    assert!(timed_out);
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+use nusalaunchd::job::config::{HooksConfig, JobConfig};
+use nusalaunchd::job::hooks::{run_hook, validate_hooks};
+use nusalaunchd::job::JobManager;
+use std::path::Path;
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// Wait up to two seconds for a hook to write `path`
+async fn read_when_written(path: &Path) -> String {
+    for _ in 0..40 {
+        if let Ok(contents) = std::fs::read_to_string(path) {
+            if contents.ends_with('\n') {
+                return contents;
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("{} was not written", path.display());
+}
+
+#[test]
+fn test_hook_timeout_must_be_positive() {
+    assert!(validate_hooks(&HooksConfig::default()).is_ok());
+    assert!(validate_hooks(&HooksConfig { timeout_sec: 0, ..HooksConfig::default() }).is_err());
+}
+
+#[tokio::test]
+async fn test_run_hook_env_and_timeout() {
+    let temp_dir = TempDir::new().unwrap();
+    let out = temp_dir.path().join("out");
+    
+    let command = format!("echo \"$NUSA_LABEL $NUSA_EXIT_CODE\" > {}", out.display());
+    run_hook(&command, "web", Some(3), Duration::from_secs(5)).await.unwrap();
+    assert_eq!(std::fs::read_to_string(&out).unwrap(), "web 3\n");
+    
+    assert!(run_hook("exit 1", "web", None, Duration::from_secs(5)).await.is_err());
+    
+    let result = run_hook("sleep 5", "web", None, Duration::from_secs(1)).await;
+    assert!(result.unwrap_err().contains("timed out"));
+}
+
+#[tokio::test]
+async fn test_hooks_run_on_job_events() {
+    let temp_dir = TempDir::new().unwrap();
+    let started = temp_dir.path().join("started");
+    let exited = temp_dir.path().join("exited");
+    let failed = temp_dir.path().join("failed");
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    
+    let config: JobConfig = toml::from_str(&format!(
+        "label = \"once\"\n[program]\npath = \"/bin/sh\"\narguments = [\"-c\", \"exit 4\"]\n\
+         [supervision]\nkeep_alive = false\n\
+         [hooks]\non_start = \"echo $NUSA_LABEL > {}\"\non_exit = \"echo $NUSA_EXIT_CODE > {}\"\n",
+        started.display(), exited.display()
+    )).unwrap();
+    manager.load_job(config).await.unwrap();
+    manager.start_job("once").await.unwrap();
+    
+    assert_eq!(read_when_written(&started).await, "once\n");
+    assert_eq!(read_when_written(&exited).await, "4\n");
+    
+    // Every exit counts toward max_restarts, so the first crash fails the job
+    let config: JobConfig = toml::from_str(&format!(
+        "label = \"crashy\"\n[program]\npath = \"/bin/sh\"\narguments = [\"-c\", \"exit 2\"]\n\
+         [supervision]\nkeep_alive = true\nrestart_policy = \"always\"\nmax_restarts = 1\n\
+         [hooks]\non_failure = \"echo $NUSA_LABEL $NUSA_EXIT_CODE > {}\"\n",
+        failed.display()
+    )).unwrap();
+    manager.load_job(config).await.unwrap();
+    manager.start_job("crashy").await.unwrap();
+    
+    assert_eq!(read_when_written(&failed).await, "crashy 2\n");
+}
\ No newline at end of file
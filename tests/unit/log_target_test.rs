@@ -0,0 +1,100 @@
+mod common;
+
+use nusalaunchd::job::config::{JobConfig, LogTarget};
+use nusalaunchd::process::output::{forward, journald_message, syslog_message, LogSink, OutputStream, PipedOutput};
+use nusalaunchd::util::logwriter::RotatingWriter;
+use std::time::Duration;
+use tempfile::TempDir;
+use tokio::net::UnixDatagram;
+
+fn config(logging: &str) -> JobConfig {
+    common::job_config(&format!(
+        "label = \"web\"\n[program]\npath = \"/bin/true\"\n[logging]\n{}",
+        logging
+    ))
+}
+
+async fn recv(socket: &UnixDatagram) -> String {
+    let mut buf = [0u8; 1024];
+    let len = tokio::time::timeout(Duration::from_secs(2), socket.recv(&mut buf)).await
+        .expect("no datagram received")
+        .unwrap();
+    String::from_utf8_lossy(&buf[..len]).into_owned()
+}
+
+#[tokio::test]
+async fn test_log_target_parsing() {
+    assert_eq!(config("").logging.log_target, LogTarget::Daemon);
+    assert_eq!(config("log_target = \"journald\"").logging.log_target, LogTarget::Journald);
+    assert!(toml::from_str::<JobConfig>(
+        "label = \"web\"\n[program]\npath = \"/bin/true\"\n[logging]\nlog_target = \"kafka\"\n"
+    ).is_err());
+    
+    // Writing only to files needs a file
+    let mut file_only = config("");
+    file_only.logging.log_target = LogTarget::File;
+    assert!(file_only.validate().await.is_err());
+    assert!(config("log_target = \"file\"\nstdout_path = \"/tmp/web.log\"").validate().await.is_ok());
+}
+
+#[test]
+fn test_message_formats() {
+    assert_eq!(syslog_message("web", 42, "listening"), "<30>web[42]: listening");
+    assert_eq!(
+        journald_message("web", 42, "listening"),
+        "SYSLOG_IDENTIFIER=web\nSYSLOG_PID=42\nPRIORITY=6\nMESSAGE=listening\n"
+    );
+}
+
+#[tokio::test]
+async fn test_forward_to_sockets_and_copy_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let syslog_path = temp_dir.path().join("log");
+    let journal_path = temp_dir.path().join("journal");
+    let copy_path = temp_dir.path().join("out.log");
+    let syslog = UnixDatagram::bind(&syslog_path).unwrap();
+    let journal = UnixDatagram::bind(&journal_path).unwrap();
+    
+    let output = PipedOutput {
+        file: Some(RotatingWriter::open(&copy_path, 0, 1).unwrap()),
+        sink: Some(LogSink::Syslog(syslog_path)),
+    };
+    forward(&b"one\ntwo\n"[..], "web".into(), 7, OutputStream::Stdout, output);
+    assert_eq!(recv(&syslog).await, "<30>web[7]: one");
+    assert_eq!(recv(&syslog).await, "<30>web[7]: two");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(std::fs::read_to_string(&copy_path).unwrap(), "one\ntwo\n");
+    
+    let output = PipedOutput { file: None, sink: Some(LogSink::Journald(journal_path)) };
+    forward(&b"oops\n"[..], "web".into(), 7, OutputStream::Stderr, output);
+    assert!(recv(&journal).await.contains("SYSLOG_IDENTIFIER=web\n"));
+}
+
+#[tokio::test]
+async fn test_forward_rotates_log_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("out.log");
+    let config = config(&format!("stdout_path = \"{}\"\nlog_max_bytes = 20\nlog_keep = 2", path.display()));
+    assert!(config.validate().await.is_ok());
+    
+    let (_, output) = nusalaunchd::process::output::stdio_for(&config, OutputStream::Stdout).unwrap();
+    assert!(output.file.is_some() && output.sink.is_none());
+    
+    // Nine characters and a newline per line: two lines fit before each rotation
+    let input: String = (1..=7).map(|i| format!("line-{:04}\n", i)).collect();
+    forward(std::io::Cursor::new(input.into_bytes()), "web".into(), 7, OutputStream::Stdout, output);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    
+    let size = |name: &str| std::fs::metadata(temp_dir.path().join(name)).map(|m| m.len()).ok();
+    assert_eq!(size("out.log"), Some(10));
+    assert_eq!(size("out.log.1"), Some(20));
+    assert_eq!(size("out.log.2"), Some(20));
+    assert_eq!(size("out.log.3"), None);
+    
+    // Whole lines only, newest in the active file
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "line-0007\n");
+    assert_eq!(
+        std::fs::read_to_string(temp_dir.path().join("out.log.2")).unwrap(),
+        "line-0003\nline-0004\n"
+    );
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+use nusalaunchd::cli::logs::{line_timestamp, log_paths, parse_timestamp, tail_lines, TimeFilter};
+use nusalaunchd::job::JobConfig;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+#[test]
+fn test_tail_reads_last_lines_across_chunks() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("out.log");
+    
+    let content: String = (0..5000).map(|i| format!("line {}\n", i)).collect();
+    std::fs::write(&path, &content).unwrap();
+    
+    let (lines, offset) = tail_lines(&path, 3).unwrap();
+    assert_eq!(lines, vec!["line 4997", "line 4998", "line 4999"]);
+    assert_eq!(offset, content.len() as u64);
+    
+    let (lines, _) = tail_lines(&path, 2000).unwrap();
+    assert_eq!(lines.len(), 2000);
+    assert_eq!(lines[0], "line 3000");
+    
+    // Asking for more lines than the file has returns all of them
+    std::fs::write(&path, "only\nthree\nlines").unwrap();
+    assert_eq!(tail_lines(&path, 50).unwrap().0, vec!["only", "three", "lines"]);
+}
+
+#[test]
+fn test_time_filter_keeps_continuation_lines() {
+    let since = parse_timestamp("2024-05-01T12:00:00Z").unwrap();
+    let until = parse_timestamp("2024-05-01T13:00:00+00:00").unwrap();
+    let mut filter = TimeFilter::new(Some(since), Some(until));
+    
+    assert!(!filter.accept("2024-05-01T11:59:59Z too early"));
+    assert!(!filter.accept("    continuation of an early line"));
+    assert!(filter.accept("[2024-05-01T12:30:00Z] in range"));
+    assert!(filter.accept("    continuation of a line in range"));
+    assert!(!filter.accept("2024-05-01T13:00:01Z too late"));
+    
+    assert!(parse_timestamp("yesterday").is_err());
+    assert!(line_timestamp("no timestamp here").is_none());
+}
+
+#[test]
+fn test_log_paths_resolve_and_dedup() {
+    let mut config: JobConfig = toml::from_str(
+        "label = \"web\"\nworking_directory = \"/srv/web\"\n[program]\npath = \"/bin/true\"\n[logging]\nstdout_path = \"web.log\"\nstderr_path = \"web.log\"\n"
+    ).unwrap();
+    assert_eq!(log_paths(&config), vec![PathBuf::from("/srv/web/web.log")]);
+    
+    config.logging.stderr_path = Some(PathBuf::from("/var/log/web.err"));
+    assert_eq!(log_paths(&config), vec![PathBuf::from("/srv/web/web.log"), PathBuf::from("/var/log/web.err")]);
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+mod common;
+
+use nusalaunchd::daemon::metrics::{render, Counters, MetricsServer};
+use nusalaunchd::job::{JobConfig, JobEvent, JobManager};
+use std::time::{Duration, SystemTime};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+fn job(label: &str) -> JobConfig {
+    common::job_config(&format!(
+        "label = \"{}\"\n[program]\npath = \"/bin/sh\"\narguments = [\"-c\", \"exit 2\"]\n[supervision]\nkeep_alive = false\n",
+        label
+    ))
+}
+
+#[tokio::test]
+async fn test_render_gauges_and_counters() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(job("web")).await.unwrap();
+    
+    let mut counters = Counters::default();
+    counters.record(&JobEvent::JobStarted("web".to_string(), 42, SystemTime::now()));
+    counters.record(&JobEvent::JobStarted("web".to_string(), 43, SystemTime::now()));
+    counters.record(&JobEvent::JobRestartScheduled("web".to_string(), Duration::from_secs(1), 1));
+    
+    let text = render(&manager, &counters).await;
+    
+    assert!(text.contains("nusalaunchd_jobs 1\n"), "{}", text);
+    assert!(text.contains("nusalaunchd_job_state{job=\"web\",state=\"stopped\"} 1\n"), "{}", text);
+    assert!(text.contains("nusalaunchd_job_state{job=\"web\",state=\"running\"} 0\n"), "{}", text);
+    assert!(text.contains("nusalaunchd_job_restart_count{job=\"web\"} 0\n"), "{}", text);
+    assert!(text.contains("nusalaunchd_job_uptime_seconds{job=\"web\"} 0.000\n"), "{}", text);
+    assert!(text.contains("nusalaunchd_job_starts_total{job=\"web\"} 2\n"), "{}", text);
+    assert!(text.contains("nusalaunchd_job_restarts_total{job=\"web\"} 1\n"), "{}", text);
+    assert!(text.contains("nusalaunchd_events_dropped_total 0\n"), "{}", text);
+    assert!(text.contains("nusalaunchd_job_exits_total{job=\"web\"} 0\n"), "{}", text);
+    assert!(!text.contains("nusalaunchd_job_last_exit_code{"), "{}", text);
+}
+
+async fn get(addr: std::net::SocketAddr, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    stream.write_all(format!("GET {} HTTP/1.1\r\nHost: test\r\n\r\n", path).as_bytes()).await.unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+#[tokio::test]
+async fn test_metrics_endpoint_serves_exit_codes() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(job("batch")).await.unwrap();
+    
+    let server = MetricsServer::bind("127.0.0.1:0".parse().unwrap(), manager.clone()).unwrap();
+    let addr = server.local_addr().unwrap();
+    tokio::spawn(server.run());
+    
+    manager.start_job("batch").await.unwrap();
+    for _ in 0..100 {
+        if manager.get_job_status("batch").await.unwrap().exit_code.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    // Let the event counter catch up
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    
+    let response = get(addr, "/metrics").await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+    assert!(response.contains("nusalaunchd_job_last_exit_code{job=\"batch\"} 2\n"), "{}", response);
+    assert!(response.contains("nusalaunchd_job_starts_total{job=\"batch\"} 1\n"), "{}", response);
+    assert!(response.contains("nusalaunchd_job_exits_total{job=\"batch\"} 1\n"), "{}", response);
+    
+    assert!(get(addr, "/other").await.starts_with("HTTP/1.1 404 Not Found\r\n"));
+}
\ No newline at end of file
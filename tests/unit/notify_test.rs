@@ -0,0 +1,148 @@
+mod common;
+
+use nusalaunchd::job::config::JobConfig;
+use nusalaunchd::job::notify::is_ready_message;
+use nusalaunchd::job::{JobManager, JobState};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::UnixDatagram;
+use std::path::Path;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn job(label: &str, supervision: &str, requires: &str) -> JobConfig {
+    common::job_config(&format!(
+        "label = \"{}\"\n{}[program]\npath = \"/bin/sleep\"\narguments = [\"30\"]\n[supervision]\nkeep_alive = false\n{}\n",
+        label, requires, supervision
+    ))
+}
+
+/// `NOTIFY_SOCKET` as passed to a process, once it has exec'd
+async fn notify_socket(pid: u32) -> String {
+    for _ in 0..50 {
+        let environ = std::fs::read(format!("/proc/{}/environ", pid)).unwrap();
+        let path = environ.split(|byte| *byte == 0)
+            .find_map(|entry| entry.strip_prefix(b"NOTIFY_SOCKET="))
+            .map(|path| String::from_utf8(path.to_vec()).unwrap());
+        if let Some(path) = path {
+            return path;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("NOTIFY_SOCKET not set");
+}
+
+async fn wait_for_state(manager: &JobManager, label: &str, state: JobState) {
+    for _ in 0..50 {
+        if manager.get_job_status(label).await.unwrap().state == state {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(manager.get_job_status(label).await.unwrap().state, state);
+}
+
+#[test]
+fn test_ready_message() {
+    assert!(is_ready_message(b"READY=1"));
+    assert!(is_ready_message(b"STATUS=warming up\nREADY=1\n"));
+    assert!(!is_ready_message(b"STATUS=READY=1"));
+    assert!(!is_ready_message(b"READY=0"));
+}
+
+#[tokio::test]
+async fn test_job_running_after_ready_notification() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(job("db", "notify_ready = true", "")).await.unwrap();
+    manager.load_job(job("web", "", "requires = [\"db\"]\n")).await.unwrap();
+    
+    manager.start_job("db").await.unwrap();
+    let pid = manager.get_job_status("db").await.unwrap().pid.unwrap();
+    assert_eq!(manager.get_job_status("db").await.unwrap().state, JobState::Starting);
+    
+    // Dependents wait for readiness, not just for the process
+    assert!(manager.start_job("web").await.is_err());
+    
+    let socket = notify_socket(pid).await;
+    let sender = UnixDatagram::unbound().unwrap();
+    sender.send_to(b"STATUS=starting", &socket).unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(manager.get_job_status("db").await.unwrap().state, JobState::Starting);
+    
+    sender.send_to(b"READY=1\n", &socket).unwrap();
+    wait_for_state(&manager, "db", JobState::Running).await;
+    wait_for_state(&manager, "web", JobState::Running).await;
+    
+    manager.stop_job("web").await.unwrap();
+    manager.stop_job("db").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_silent_job_hits_start_timeout() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(job("silent", "notify_ready = true\nstart_timeout_sec = 1", "")).await.unwrap();
+    
+    manager.start_job("silent").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    
+    let status = manager.get_job_status("silent").await.unwrap();
+    assert!(matches!(status.state, JobState::Failed(_)), "{}", status.state);
+    assert_eq!(status.pid, None);
+}
+
+#[tokio::test]
+async fn test_long_label_fits_private_socket_dir() {
+    let runtime_dir = TempDir::new().unwrap();
+    let label = "l".repeat(200);
+    
+    let (mut manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.set_runtime_dir(runtime_dir.path().to_path_buf());
+    manager.load_job(job(&label, "notify_ready = true", "")).await.unwrap();
+    
+    manager.start_job(&label).await.unwrap();
+    let pid = manager.get_job_status(&label).await.unwrap().pid.unwrap();
+    let socket = notify_socket(pid).await;
+    assert!(socket.len() <= 107, "{}", socket);
+    
+    let dir = Path::new(&socket).parent().unwrap();
+    assert_eq!(dir.parent().unwrap(), runtime_dir.path().join("notify"));
+    assert_eq!(std::fs::metadata(dir).unwrap().permissions().mode() & 0o777, 0o700);
+    
+    UnixDatagram::unbound().unwrap().send_to(b"READY=1", &socket).unwrap();
+    wait_for_state(&manager, &label, JobState::Running).await;
+    
+    manager.stop_job(&label).await.unwrap();
+    assert!(!dir.exists());
+}
+
+#[tokio::test]
+async fn test_restarted_job_keeps_socket_under_runtime_dir() {
+    let runtime_dir = TempDir::new().unwrap();
+    let config: JobConfig = toml::from_str(
+        "label = \"restarted\"\n[program]\npath = \"/bin/sleep\"\narguments = [\"30\"]\n\
+         [supervision]\nkeep_alive = true\nrestart_delay_sec = 0\nnotify_ready = true\n"
+    ).unwrap();
+    
+    // Set after the manager started its restart task, as the daemon does
+    let (mut manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.set_runtime_dir(runtime_dir.path().to_path_buf());
+    manager.set_auto_start(false);
+    manager.load_job(config).await.unwrap();
+    
+    manager.start_job("restarted").await.unwrap();
+    let first = manager.get_job_status("restarted").await.unwrap().pid.unwrap();
+    nix::sys::signal::kill(nix::unistd::Pid::from_raw(first as i32), nix::sys::signal::Signal::SIGKILL).unwrap();
+    
+    let mut restarted = None;
+    for _ in 0..100 {
+        restarted = manager.get_job_status("restarted").await.unwrap().pid.filter(|pid| *pid != first);
+        if restarted.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    
+    let socket = notify_socket(restarted.expect("job was not restarted")).await;
+    assert!(Path::new(&socket).starts_with(runtime_dir.path().join("notify")), "{}", socket);
+    
+    manager.stop_job("restarted").await.unwrap();
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+use nusalaunchd::cli::pattern::{expand_labels, is_pattern, matches};
+
+fn labels(list: &[&str]) -> Vec<String> {
+    list.iter().map(|label| label.to_string()).collect()
+}
+
+#[test]
+fn test_glob_matching() {
+    assert!(matches("web-*", "web-frontend"));
+    assert!(matches("web-*", "web-"));
+    assert!(!matches("web-*", "api-web"));
+    assert!(matches("db?", "db1"));
+    assert!(!matches("db?", "db10"));
+    assert!(matches("[ab]-worker", "b-worker"));
+    assert!(matches("worker-[0-9]", "worker-7"));
+    assert!(!matches("worker-[!0-9]", "worker-7"));
+    assert!(matches("web\\*", "web*"));
+    assert!(!matches("web\\*", "web-frontend"));
+}
+
+#[test]
+fn test_is_pattern() {
+    assert!(is_pattern("web-*"));
+    assert!(is_pattern("[ab]"));
+    assert!(!is_pattern("web"));
+    assert!(!is_pattern("web\\*"));
+}
+
+#[test]
+fn test_expand_labels() {
+    let loaded = labels(&["web-b", "db", "web-a", "web*"]);
+    
+    assert_eq!(expand_labels(&labels(&["web-*", "db"]), &loaded).unwrap(), ["web-a", "web-b", "db"]);
+    assert_eq!(expand_labels(&labels(&["db", "d?"]), &loaded).unwrap(), ["db"]);
+    
+    // Literal labels match exactly, escapes included, and are passed on even if unknown
+    assert_eq!(expand_labels(&labels(&["web\\*"]), &loaded).unwrap(), ["web*"]);
+    assert_eq!(expand_labels(&labels(&["cache"]), &loaded).unwrap(), ["cache"]);
+    
+    let err = expand_labels(&labels(&["api-*"]), &loaded).unwrap_err();
+    assert!(err.to_string().contains("'api-*'"), "{}", err);
+}
\ No newline at end of file
@@ -0,0 +1,54 @@
+use nusalaunchd::daemon::pidfile::{read_pid, PidFile};
+use std::os::unix::io::AsRawFd;
+use tempfile::TempDir;
+
+// Far above any pid_max, so never a running process
+const DEAD_PID: &str = "2147483000\n";
+
+#[test]
+fn test_pid_file_written_and_removed() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("run/nusalaunchd.pid");
+    
+    let pid_file = PidFile::acquire(&path).unwrap();
+    assert_eq!(read_pid(&path), Some(std::process::id()));
+    
+    drop(pid_file);
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_second_instance_refused() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("nusalaunchd.pid");
+    
+    let _held = PidFile::acquire(&path).unwrap();
+    let err = PidFile::acquire(&path).unwrap_err();
+    assert!(err.to_string().contains("already running"), "{}", err);
+    assert_eq!(read_pid(&path), Some(std::process::id()));
+}
+
+#[test]
+fn test_stale_pid_file_reclaimed() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("nusalaunchd.pid");
+    std::fs::write(&path, DEAD_PID).unwrap();
+    
+    let _pid_file = PidFile::acquire(&path).unwrap();
+    assert_eq!(read_pid(&path), Some(std::process::id()));
+}
+
+#[test]
+fn test_lock_held_without_live_pid_refused() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("nusalaunchd.pid");
+    std::fs::write(&path, DEAD_PID).unwrap();
+    
+    // Like a daemon that has taken the lock but not written its PID yet
+    let holder = std::fs::File::open(&path).unwrap();
+    assert_eq!(unsafe { libc::flock(holder.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) }, 0);
+    
+    let err = PidFile::acquire(&path).unwrap_err();
+    assert!(err.to_string().contains("locked by another process"), "{}", err);
+    assert_eq!(read_pid(&path), Some(2147483000));
+}
\ No newline at end of file
@@ -0,0 +1,191 @@
+mod common;
+
+use nusalaunchd::job::config::{parse_cpu_list, parse_umask, CpuAffinity, JobConfig, StdinSource};
+use nusalaunchd::job::validator::ConfigValidator;
+use nusalaunchd::job::JobManager;
+use std::path::Path;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn job(dir: &Path, label: &str, command: &str, program: &str) -> JobConfig {
+    common::job_config(&format!(
+        "label = \"{}\"\nworking_directory = \"{}\"\n[program]\npath = \"/bin/sh\"\narguments = [\"-c\", \"{}\"]\n{}\n[supervision]\nkeep_alive = false\n[logging]\nstdout_path = \"{}.log\"\n",
+        label,
+        dir.display(),
+        command,
+        program,
+        label
+    ))
+}
+
+/// Run a job to completion and return what it printed
+async fn run(config: JobConfig) -> String {
+    let path = config.working_directory.clone().unwrap().join(format!("{}.log", config.label));
+    let label = config.label.clone();
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(config).await.unwrap();
+    manager.start_job(&label).await.unwrap();
+    
+    for _ in 0..50 {
+        if std::fs::read_to_string(&path).map_or(false, |content| content.ends_with('\n')) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    
+    std::fs::read_to_string(&path).unwrap()
+}
+
+#[test]
+fn test_parse_umask() {
+    assert_eq!(parse_umask("0027"), Some(0o027));
+    assert_eq!(parse_umask("077"), Some(0o077));
+    assert_eq!(parse_umask("0"), Some(0));
+    assert_eq!(parse_umask("0089"), None);
+    assert_eq!(parse_umask("01777"), None);
+    assert_eq!(parse_umask(""), None);
+}
+
+#[tokio::test]
+async fn test_validator_rejects_bad_umask() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    assert!(ConfigValidator::validate(&job(temp_dir.path(), "ok", "true", "umask = \"0027\""), false).await.is_ok());
+    
+    let mut bad = job(temp_dir.path(), "bad", "true", "");
+    bad.program.umask = Some("0999".to_string());
+    let err = ConfigValidator::validate(&bad, false).await.unwrap_err();
+    assert!(err.to_string().contains("0999"), "{}", err);
+    
+    // Loading a config checks it too
+    let err = bad.validate().await.unwrap_err();
+    assert!(err.to_string().contains("0999"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_spawn_applies_umask() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let output = run(job(temp_dir.path(), "umask", "umask", "umask = \"0027\"")).await;
+    assert_eq!(output.trim(), "0027");
+}
+
+#[tokio::test]
+async fn test_process_ranges_rejected_at_load() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    for (section, valid) in [
+        ("nice = 19\noom_score_adj = -1000", true),
+        ("nice = -21", false),
+        ("nice = 20", false),
+        ("oom_score_adj = 1001", false),
+        ("cpu_affinity = [0]", true),
+        ("cpu_affinity = \"0\"", true),
+        ("cpu_affinity = []", false),
+        ("cpu_affinity = \"1-0\"", false),
+        ("cpu_affinity = [100000]", false),
+    ] {
+        let path = temp_dir.path().join("job.toml");
+        std::fs::write(&path, format!(
+            "label = \"ranges\"\n[program]\npath = \"/bin/true\"\n[supervision]\nkeep_alive = false\n[process]\n{}\n",
+            section
+        )).unwrap();
+        
+        assert_eq!(JobConfig::from_file(&path).await.is_ok(), valid, "{}", section);
+        
+        let config: JobConfig = toml::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(ConfigValidator::validate(&config, false).await.is_ok(), valid, "{}", section);
+    }
+}
+
+#[tokio::test]
+async fn test_spawn_applies_nice_and_oom_score() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let mut config = job(temp_dir.path(), "priority", "echo $(nice) $(cat /proc/self/oom_score_adj)", "");
+    config.process.nice = Some(7);
+    config.process.oom_score_adj = Some(500);
+    
+    assert_eq!(run(config).await.trim(), "7 500");
+}
+
+#[test]
+fn test_parse_cpu_list() {
+    assert_eq!(parse_cpu_list("0-3,6"), Some(vec![0, 1, 2, 3, 6]));
+    assert_eq!(parse_cpu_list(" 2 , 1 "), Some(vec![2, 1]));
+    assert_eq!(parse_cpu_list("3-1"), None);
+    assert_eq!(parse_cpu_list("0,,1"), None);
+    assert_eq!(CpuAffinity::List("2,0-2".into()).cores(), Some(vec![0, 1, 2]));
+}
+
+#[tokio::test]
+async fn test_spawn_applies_cpu_affinity() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    let mut config = job(temp_dir.path(), "pinned", "grep Cpus_allowed_list /proc/self/status", "");
+    config.process.cpu_affinity = Some(CpuAffinity::Cores(vec![0]));
+    
+    assert_eq!(run(config).await.split_whitespace().last(), Some("0"));
+}
+
+#[tokio::test]
+async fn test_spawn_creates_working_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    let working_dir = temp_dir.path().join("srv/web");
+    
+    let mut config = job(&working_dir, "mkdir", "pwd", "");
+    config.create_working_directory = true;
+    assert!(ConfigValidator::validate(&config, true).await.is_ok());
+    
+    assert_eq!(run(config).await.trim(), working_dir.display().to_string());
+}
+
+#[tokio::test]
+async fn test_spawn_reads_stdin_file() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("input.txt"), "from stdin\n").unwrap();
+    
+    let config = job(temp_dir.path(), "reader", "cat", "stdin = \"input.txt\"");
+    assert_eq!(run(config).await, "from stdin\n");
+}
+
+#[tokio::test]
+async fn test_stdin_fifo_without_writer_does_not_block_spawn() {
+    let temp_dir = TempDir::new().unwrap();
+    let fifo = temp_dir.path().join("input.fifo");
+    assert!(std::process::Command::new("mkfifo").arg(&fifo).status().unwrap().success());
+    
+    let config = job(temp_dir.path(), "fifo", "echo started", &format!("stdin = \"{}\"", fifo.display()));
+    let output = tokio::time::timeout(Duration::from_secs(5), run(config)).await.unwrap();
+    assert_eq!(output, "started\n");
+}
+
+#[tokio::test]
+async fn test_stdin_source_parsed_and_validated() {
+    let temp_dir = TempDir::new().unwrap();
+    
+    assert_eq!(job(temp_dir.path(), "a", "true", "").program.stdin, StdinSource::Null);
+    assert_eq!(job(temp_dir.path(), "a", "true", "stdin = \"inherit\"").program.stdin, StdinSource::Inherit);
+    
+    let missing = job(temp_dir.path(), "a", "true", "stdin = \"missing.txt\"");
+    assert!(ConfigValidator::validate(&missing, false).await.is_ok());
+    let err = ConfigValidator::validate(&missing, true).await.unwrap_err();
+    assert!(err.to_string().contains("missing.txt"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_spawn_sets_process_title() {
+    let temp_dir = TempDir::new().unwrap();
+    let argv0 = |output: String| output.split('\0').next().unwrap().to_string();
+    
+    // The shell prints its own command line, whose argv[0] is the title
+    let output = run(job(temp_dir.path(), "titled", "cat /proc/$$/cmdline; echo", "proc_title = \"nusa: titled worker\"")).await;
+    assert_eq!(argv0(output), "nusa: titled worker");
+    
+    let output = run(job(temp_dir.path(), "by-label", "cat /proc/$$/cmdline; echo", "label_as_proc_title = true")).await;
+    assert_eq!(argv0(output), "by-label");
+    
+    let output = run(job(temp_dir.path(), "untitled", "cat /proc/$$/cmdline; echo", "")).await;
+    assert_eq!(argv0(output), "/bin/sh");
+}
\ No newline at end of file
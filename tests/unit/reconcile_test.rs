@@ -0,0 +1,102 @@
+mod common;
+
+use nusalaunchd::job::config::JobConfig;
+use nusalaunchd::job::reconcile::{diff_configs, FieldChange, ReconcilePlan};
+use std::path::{Path, PathBuf};
+
+fn job(label: &str, extra: &str) -> JobConfig {
+    common::job_config(&format!(
+        "label = \"{}\"\n[program]\npath = \"/bin/true\"\n[supervision]\n{}\n",
+        label, extra
+    ))
+}
+
+fn on_disk(config: JobConfig) -> (PathBuf, JobConfig) {
+    (PathBuf::from(format!("/etc/nusa/{}.toml", config.label)), config)
+}
+
+fn loaded(config: JobConfig) -> (Option<PathBuf>, JobConfig) {
+    let (source, config) = on_disk(config);
+    (Some(source), config)
+}
+
+#[test]
+fn test_diff_configs() {
+    let old = job("worker", "max_restarts = 3");
+    assert!(diff_configs(&old, &job("worker", "max_restarts = 3")).is_empty());
+    
+    assert_eq!(
+        diff_configs(&old, &job("worker", "max_restarts = 5")),
+        vec![FieldChange {
+            field: "supervision.max_restarts".to_string(),
+            old: Some("3".to_string()),
+            new: Some("5".to_string()),
+        }]
+    );
+    
+    // Optional fields appear and disappear as unset
+    let mut described = job("worker", "max_restarts = 3");
+    described.description = Some("Background worker".to_string());
+    let changes = diff_configs(&old, &described);
+    assert_eq!(changes, vec![FieldChange {
+        field: "description".to_string(),
+        old: None,
+        new: Some("\"Background worker\"".to_string()),
+    }]);
+    let changes = diff_configs(&described, &old);
+    assert_eq!(changes[0].old.as_deref(), Some("\"Background worker\""));
+    assert_eq!(changes[0].new, None);
+}
+
+#[test]
+fn test_reconcile_plan() {
+    let loaded = vec![
+        loaded(job("unchanged", "")),
+        loaded(job("changed", "max_restarts = 3")),
+        loaded(job("removed", "")),
+    ];
+    let disk = vec![
+        on_disk(job("changed", "max_restarts = 5")),
+        on_disk(job("unchanged", "")),
+        on_disk(job("added", "")),
+    ];
+    
+    let plan = ReconcilePlan::compute(&loaded, &disk, Path::new("/etc/nusa"), false);
+    assert!(!plan.is_empty());
+    
+    assert_eq!(plan.added.len(), 1);
+    assert_eq!(plan.added[0].label, "added");
+    assert_eq!(plan.added[0].source, PathBuf::from("/etc/nusa/added.toml"));
+    
+    assert_eq!(plan.removed, vec!["removed"]);
+    
+    assert_eq!(plan.updated.len(), 1);
+    assert_eq!(plan.updated[0].label, "changed");
+    assert_eq!(plan.updated[0].changes.len(), 1);
+    assert_eq!(plan.updated[0].changes[0].field, "supervision.max_restarts");
+}
+
+#[test]
+fn test_reconcile_plan_without_changes() {
+    let loaded = vec![loaded(job("worker", ""))];
+    let plan = ReconcilePlan::compute(&loaded, &[on_disk(job("worker", ""))], Path::new("/etc/nusa"), false);
+    assert!(plan.is_empty());
+    assert_eq!(plan.render(), "No changes");
+}
+
+#[test]
+fn test_reconcile_plan_only_removes_jobs_from_the_dir() {
+    let loaded = vec![
+        loaded(job("gone", "")),
+        (Some(PathBuf::from("/etc/nusa/multi-user/nested.toml")), job("nested", "")),
+        (Some(PathBuf::from("/opt/app/jobs/elsewhere.toml")), job("elsewhere", "")),
+        (None, job("instance@1", "")),
+    ];
+    let dir = Path::new("/etc/nusa");
+    
+    let plan = ReconcilePlan::compute(&loaded, &[], dir, false);
+    assert_eq!(plan.removed, vec!["gone"]);
+    
+    let plan = ReconcilePlan::compute(&loaded, &[], dir, true);
+    assert_eq!(plan.removed, vec!["gone", "nested"]);
+}
\ No newline at end of file
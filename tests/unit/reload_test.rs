@@ -0,0 +1,255 @@
+use nusalaunchd::job::config::JobConfig;
+use nusalaunchd::job::{JobManager, JobState, ReloadOutcome};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn write_config(path: &Path, seconds: &str, max_restarts: u32) {
+    let content = format!(
+        "label = \"sleeper\"\n[program]\npath = \"/bin/sleep\"\narguments = [\"{}\"]\n[supervision]\nkeep_alive = false\nmax_restarts = {}\n",
+        seconds, max_restarts
+    );
+    std::fs::write(path, content).unwrap();
+}
+
+async fn load(manager: &JobManager, path: &Path) {
+    let config = JobConfig::from_file(path).await.unwrap();
+    manager.load_job_from(config, Some(path.to_path_buf())).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_reload_applies_supervision_live() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("sleeper.toml");
+    write_config(&path, "30", 5);
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    load(&manager, &path).await;
+    let pid = manager.start_job_detailed("sleeper").await.unwrap().map(|started| started.pid);
+    
+    assert_eq!(manager.reload_job("sleeper", false).await.unwrap(), ReloadOutcome::Unchanged);
+    
+    write_config(&path, "30", 9);
+    assert_eq!(manager.reload_job("sleeper", false).await.unwrap(), ReloadOutcome::Applied);
+    
+    let status = manager.get_job_status("sleeper").await.unwrap();
+    assert_eq!(status.config.supervision.max_restarts, 9);
+    assert_eq!(status.pid, pid);
+    assert!(!status.needs_restart);
+    
+    manager.stop_job("sleeper").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_reload_program_change_needs_restart() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("sleeper.toml");
+    write_config(&path, "30", 5);
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    load(&manager, &path).await;
+    let pid = manager.start_job_detailed("sleeper").await.unwrap().map(|started| started.pid);
+    
+    write_config(&path, "60", 5);
+    assert_eq!(manager.reload_job("sleeper", false).await.unwrap(), ReloadOutcome::NeedsRestart);
+    
+    let status = manager.get_job_status("sleeper").await.unwrap();
+    assert_eq!(status.pid, pid);
+    assert!(status.needs_restart);
+    
+    write_config(&path, "90", 5);
+    assert_eq!(manager.reload_job("sleeper", true).await.unwrap(), ReloadOutcome::Restarted);
+    
+    let status = manager.get_job_status("sleeper").await.unwrap();
+    assert_eq!(status.state, JobState::Running);
+    assert_ne!(status.pid, pid);
+    assert!(!status.needs_restart);
+    assert_eq!(status.config.program.arguments, vec!["90"]);
+    
+    manager.stop_job("sleeper").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_reload_rejects_broken_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("sleeper.toml");
+    write_config(&path, "30", 5);
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    load(&manager, &path).await;
+    
+    std::fs::write(&path, "label = \"sleeper\"\n[program\n").unwrap();
+    assert!(manager.reload_job("sleeper", false).await.is_err());
+    
+    // The loaded config is untouched
+    let status = manager.get_job_status("sleeper").await.unwrap();
+    assert_eq!(status.config.program.arguments, vec!["30"]);
+}
+
+#[tokio::test]
+async fn test_rescan_directory_loads_reloads_and_unloads() {
+    use nusalaunchd::job::JobEvent;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let sleeper = temp_dir.path().join("sleeper.toml");
+    let other = temp_dir.path().join("other.toml");
+    write_config(&sleeper, "30", 5);
+    std::fs::write(&other, "label = \"other\"\n[program]\npath = \"/bin/true\"\n[supervision]\nkeep_alive = false\n").unwrap();
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    let mut events = manager.subscribe();
+    assert_eq!(manager.load_directory(temp_dir.path()).await, (2, 0));
+    manager.start_job("sleeper").await.unwrap();
+    
+    // One file changed, one removed, one added
+    write_config(&sleeper, "30", 7);
+    std::fs::remove_file(&other).unwrap();
+    std::fs::write(
+        temp_dir.path().join("new.toml"),
+        "label = \"new\"\n[program]\npath = \"/bin/true\"\n[supervision]\nkeep_alive = false\n",
+    ).unwrap();
+    
+    manager.rescan_directory(temp_dir.path()).await.unwrap();
+    
+    let mut labels: Vec<String> = manager.list_jobs().await.into_iter().map(|job| job.label).collect();
+    labels.sort();
+    assert_eq!(labels, vec!["new", "sleeper"]);
+    
+    let status = manager.get_job_status("sleeper").await.unwrap();
+    assert_eq!(status.config.supervision.max_restarts, 7);
+    assert_eq!(status.state, JobState::Running);
+    
+    let mut seen = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        match event {
+            JobEvent::JobReloaded(label, _) => seen.push(format!("reloaded {}", label)),
+            JobEvent::JobUnloaded(label) => seen.push(format!("unloaded {}", label)),
+            JobEvent::JobLoaded(label) if label == "new" => seen.push(format!("loaded {}", label)),
+            _ => {}
+        }
+    }
+    seen.sort();
+    assert_eq!(seen, vec!["loaded new", "reloaded sleeper", "unloaded other"]);
+    
+    manager.stop_job("sleeper").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_job_array_file_loaded_and_rescanned() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("workers.toml");
+    let job = |label: &str, max_restarts: u32| format!(
+        "[[job]]\nlabel = \"{}\"\n[job.program]\npath = \"/bin/true\"\n\
+         [job.supervision]\nkeep_alive = false\nmax_restarts = {}\n",
+        label, max_restarts
+    );
+    std::fs::write(&path, job("worker-a", 5) + &job("worker-b", 5)).unwrap();
+    
+    let (mut manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.set_auto_start(false);
+    assert_eq!(manager.load_directory(temp_dir.path()).await, (2, 0));
+    assert_eq!(manager.get_job_status("worker-b").await.unwrap().source_path.as_deref(), Some(path.as_path()));
+    
+    // worker-a changes, worker-b is dropped from the file, worker-c is added to it
+    std::fs::write(&path, job("worker-a", 7) + &job("worker-c", 5)).unwrap();
+    manager.rescan_directory(temp_dir.path()).await.unwrap();
+    
+    let mut labels: Vec<String> = manager.list_jobs().await.into_iter().map(|job| job.label).collect();
+    labels.sort();
+    assert_eq!(labels, vec!["worker-a", "worker-c"]);
+    assert_eq!(manager.get_job_status("worker-a").await.unwrap().config.supervision.max_restarts, 7);
+}
+
+#[tokio::test]
+async fn test_reload_all_reports_summary() {
+    use nusalaunchd::job::RescanSummary;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let sleeper = temp_dir.path().join("sleeper.toml");
+    write_config(&sleeper, "30", 5);
+    
+    let (mut manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.set_auto_start(false);
+    assert!(manager.reload_all().await.is_err());
+    
+    manager.set_config_dir(temp_dir.path().to_path_buf());
+    let first = manager.reload_all().await.unwrap();
+    assert_eq!(first, RescanSummary { loaded: 1, ..Default::default() });
+    
+    write_config(&sleeper, "30", 7);
+    std::fs::write(temp_dir.path().join("broken.toml"), "label = ").unwrap();
+    let second = manager.reload_all().await.unwrap();
+    assert_eq!(second, RescanSummary { reloaded: 1, failed: 1, ..Default::default() });
+    
+    std::fs::remove_file(&sleeper).unwrap();
+    assert_eq!(manager.reload_all().await.unwrap().removed, 1);
+}
+
+#[tokio::test]
+async fn test_concurrent_rescans_do_not_interleave() {
+    let temp_dir = TempDir::new().unwrap();
+    for i in 0..5 {
+        std::fs::write(
+            temp_dir.path().join(format!("job-{}.toml", i)),
+            format!("label = \"job-{}\"\n[program]\npath = \"/bin/true\"\n[supervision]\nkeep_alive = false\n", i),
+        ).unwrap();
+    }
+    
+    let (mut manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.set_auto_start(false);
+    
+    // Interleaved, both would see the files as new and one would fail to load each job
+    let (first, second) = tokio::join!(
+        manager.rescan_directory(temp_dir.path()),
+        manager.rescan_directory(temp_dir.path()),
+    );
+    let (first, second) = (first.unwrap(), second.unwrap());
+    
+    assert_eq!(first.loaded + second.loaded, 5);
+    assert_eq!(first.unchanged + second.unchanged, 5);
+    assert_eq!(first.failed + second.failed, 0);
+}
+
+#[tokio::test]
+async fn test_recursive_config_dir_starts_only_target_jobs() {
+    use nusalaunchd::job::manager::directory_target;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    for (dir, label) in [("", "shared"), ("staging", "web"), ("production", "api")] {
+        std::fs::create_dir_all(root.join(dir)).unwrap();
+        std::fs::write(root.join(dir).join(format!("{}.toml", label)), format!(
+            "label = \"{}\"\n[program]\npath = \"/bin/sleep\"\narguments = [\"30\"]\n[supervision]\nkeep_alive = true\n",
+            label
+        )).unwrap();
+    }
+    std::os::unix::fs::symlink(root, root.join("staging/loop")).unwrap();
+    
+    assert_eq!(directory_target(root, &root.join("staging/web.toml")).as_deref(), Some("staging"));
+    assert_eq!(directory_target(root, &root.join("shared.toml")), None);
+    
+    let (mut flat, _flat_rx) = JobManager::new().await.unwrap();
+    flat.set_auto_start(false);
+    assert_eq!(flat.load_directory(root).await, (1, 0));
+    
+    let (mut manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.set_auto_start(false);
+    manager.set_config_dir(root.to_path_buf());
+    manager.set_recursive_config(true);
+    manager.set_target("staging".to_string());
+    assert_eq!(manager.load_directory(root).await, (3, 0));
+    
+    manager.start_keep_alive().await.unwrap();
+    let state = |label| {
+        let manager = manager.clone();
+        async move { manager.get_job_status(label).await.unwrap().state }
+    };
+    assert_eq!(state("shared").await, JobState::Running);
+    assert_eq!(state("web").await, JobState::Running);
+    assert_eq!(state("api").await, JobState::Stopped);
+    
+    // Rescans look into subdirectories too
+    assert_eq!(manager.reload_all().await.unwrap().unchanged, 3);
+    
+    manager.kill_job("shared").await.unwrap();
+    manager.kill_job("web").await.unwrap();
+}
\ No newline at end of file
@@ -0,0 +1,96 @@
+use chrono::{Local, TimeZone};
+use nusalaunchd::job::config::{CalendarInterval, JobConfig, ScheduleConfig};
+use nusalaunchd::job::scheduler::{next_run, validate_schedule};
+
+fn calendar(minute: Option<u32>, hour: Option<u32>, day: Option<u32>, weekday: Option<u32>) -> ScheduleConfig {
+    ScheduleConfig {
+        calendar: Some(CalendarInterval { minute, hour, day, weekday }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_interval_next_run() {
+    let schedule = ScheduleConfig {
+        interval_sec: Some(90),
+        ..Default::default()
+    };
+    let now = Local.with_ymd_and_hms(2024, 3, 14, 10, 0, 0).unwrap();
+    
+    assert_eq!(next_run(&schedule, now), Some(Local.with_ymd_and_hms(2024, 3, 14, 10, 1, 30).unwrap()));
+}
+
+#[test]
+fn test_calendar_daily() {
+    // Every day at 03:30
+    let schedule = calendar(Some(30), Some(3), None, None);
+    
+    let before = Local.with_ymd_and_hms(2024, 3, 14, 1, 0, 0).unwrap();
+    assert_eq!(next_run(&schedule, before), Some(Local.with_ymd_and_hms(2024, 3, 14, 3, 30, 0).unwrap()));
+    
+    // A run exactly at the matching minute schedules the next day's
+    let at = Local.with_ymd_and_hms(2024, 3, 14, 3, 30, 0).unwrap();
+    assert_eq!(next_run(&schedule, at), Some(Local.with_ymd_and_hms(2024, 3, 15, 3, 30, 0).unwrap()));
+}
+
+#[test]
+fn test_calendar_hourly() {
+    // Minute 15 of every hour
+    let schedule = calendar(Some(15), None, None, None);
+    let now = Local.with_ymd_and_hms(2024, 3, 14, 10, 20, 0).unwrap();
+    
+    assert_eq!(next_run(&schedule, now), Some(Local.with_ymd_and_hms(2024, 3, 14, 11, 15, 0).unwrap()));
+}
+
+#[test]
+fn test_calendar_weekday_and_day() {
+    // Sundays at midnight; 2024-03-14 is a Thursday
+    let sunday = calendar(Some(0), Some(0), None, Some(0));
+    let now = Local.with_ymd_and_hms(2024, 3, 14, 12, 0, 0).unwrap();
+    assert_eq!(next_run(&sunday, now), Some(Local.with_ymd_and_hms(2024, 3, 17, 0, 0, 0).unwrap()));
+    
+    // 7 is also Sunday
+    let sunday = calendar(Some(0), Some(0), None, Some(7));
+    assert_eq!(next_run(&sunday, now), Some(Local.with_ymd_and_hms(2024, 3, 17, 0, 0, 0).unwrap()));
+    
+    // The 31st skips months without one
+    let month_end = calendar(Some(0), Some(12), Some(31), None);
+    let now = Local.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap();
+    assert_eq!(next_run(&month_end, now), Some(Local.with_ymd_and_hms(2024, 5, 31, 12, 0, 0).unwrap()));
+}
+
+#[test]
+fn test_validate_schedule() {
+    assert!(validate_schedule(&ScheduleConfig::default()).is_err());
+    assert!(validate_schedule(&ScheduleConfig { interval_sec: Some(0), ..Default::default() }).is_err());
+    assert!(validate_schedule(&ScheduleConfig { interval_sec: Some(60), ..calendar(None, None, None, None) }).is_err());
+    assert!(validate_schedule(&calendar(Some(60), None, None, None)).is_err());
+    assert!(validate_schedule(&calendar(None, Some(24), None, None)).is_err());
+    assert!(validate_schedule(&calendar(None, None, Some(0), None)).is_err());
+    assert!(validate_schedule(&calendar(Some(0), Some(4), None, Some(1))).is_ok());
+}
+
+#[tokio::test]
+async fn test_schedule_section_parses() {
+    let config: JobConfig = toml::from_str(r#"
+        label = "cleanup"
+        
+        [program]
+        path = "/usr/bin/true"
+        
+        [supervision]
+        keep_alive = false
+        
+        [schedule]
+        run_missed = true
+        
+        [schedule.calendar]
+        hour = 4
+        minute = 0
+    "#).unwrap();
+    
+    let schedule = config.schedule.as_ref().unwrap();
+    assert!(schedule.run_missed);
+    assert_eq!(schedule.calendar.unwrap().hour, Some(4));
+    assert!(config.validate().await.is_ok());
+}
\ No newline at end of file
@@ -0,0 +1,187 @@
+use nusalaunchd::job::config::{JobConfig, SocketConfig, SocketType};
+use nusalaunchd::job::sockets::{validate_sockets, ListenAddr};
+use nusalaunchd::job::{JobManager, JobState};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[test]
+fn test_parse_listen_addr() {
+    assert_eq!(ListenAddr::parse("8080"), Ok(ListenAddr::Inet("0.0.0.0:8080".parse::<SocketAddr>().unwrap())));
+    assert_eq!(ListenAddr::parse("127.0.0.1:53"), Ok(ListenAddr::Inet("127.0.0.1:53".parse::<SocketAddr>().unwrap())));
+    assert_eq!(ListenAddr::parse("[::1]:80"), Ok(ListenAddr::Inet("[::1]:80".parse::<SocketAddr>().unwrap())));
+    assert_eq!(ListenAddr::parse("/run/app.sock"), Ok(ListenAddr::Unix(PathBuf::from("/run/app.sock"))));
+    
+    assert!(ListenAddr::parse("localhost:80").is_err());
+    assert!(ListenAddr::parse("app.sock").is_err());
+    assert!(ListenAddr::parse("70000").is_err());
+}
+
+#[test]
+fn test_validate_sockets() {
+    let socket = |listen: &str, socket_type| SocketConfig {
+        listen: listen.to_string(),
+        socket_type,
+        name: None,
+        idle_timeout_sec: None,
+    };
+    
+    // The same port may be used once for TCP and once for UDP
+    assert!(validate_sockets(&[socket("53", SocketType::Stream), socket("53", SocketType::Dgram)]).is_ok());
+    
+    assert!(validate_sockets(&[socket("53", SocketType::Dgram), socket("0.0.0.0:53", SocketType::Dgram)]).is_err());
+    assert!(validate_sockets(&[socket("not an address", SocketType::Stream)]).is_err());
+    
+    // Names end up colon-separated in LISTEN_FDNAMES
+    let named = |name: &str| SocketConfig { name: Some(name.to_string()), ..socket("80", SocketType::Stream) };
+    assert!(validate_sockets(&[named("http")]).is_ok());
+    assert!(validate_sockets(&[named("a:b")]).is_err());
+    assert!(validate_sockets(&[named("")]).is_err());
+    
+    let idle = |secs| SocketConfig { idle_timeout_sec: Some(secs), ..socket("80", SocketType::Stream) };
+    assert!(validate_sockets(&[idle(30)]).is_ok());
+    assert!(validate_sockets(&[idle(0)]).is_err());
+}
+
+#[test]
+fn test_socket_config_defaults_to_stream() {
+    let config: JobConfig = toml::from_str(
+        "label = \"s\"\n[program]\npath = \"/bin/true\"\n[[sockets]]\nlisten = \"8080\"\n[[sockets]]\nlisten = \"/tmp/s.sock\"\ntype = \"dgram\"\n"
+    ).unwrap();
+    
+    assert_eq!(config.sockets.len(), 2);
+    assert_eq!(config.sockets[0].socket_type, SocketType::Stream);
+    assert_eq!(config.sockets[1].socket_type, SocketType::Dgram);
+}
+
+#[tokio::test]
+async fn test_job_started_on_first_connection() {
+    let temp_dir = TempDir::new().unwrap();
+    let socket_path = temp_dir.path().join("activate.sock");
+    let out_path = temp_dir.path().join("out");
+    
+    // Report what sd_listen_fds would check: the fd count, LISTEN_PID against our own PID, and fd 3
+    let script = format!(
+        "echo $LISTEN_FDS $([ \\\"$LISTEN_PID\\\" = $$ ] && echo pid-ok) $(readlink /proc/$$/fd/3 | cut -d: -f1) > {}",
+        out_path.display()
+    );
+    let config: JobConfig = toml::from_str(&format!(
+        "label = \"activated\"\n[program]\npath = \"/bin/sh\"\narguments = [\"-c\", \"{}\"]\n[supervision]\nkeep_alive = false\n[[sockets]]\nlisten = \"{}\"\n",
+        script,
+        socket_path.display()
+    )).unwrap();
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(config).await.unwrap();
+    
+    // Listening, but nothing started yet
+    let sockets = manager.list_sockets().await;
+    assert_eq!(sockets.len(), 1);
+    assert_eq!(sockets[0].job, "activated");
+    assert_eq!(sockets[0].activations, 0);
+    assert_eq!(manager.get_job_status("activated").await.unwrap().state, JobState::Stopped);
+    assert!(socket_path.exists());
+    
+    let _client = std::os::unix::net::UnixStream::connect(&socket_path).unwrap();
+    
+    for _ in 0..100 {
+        if std::fs::read_to_string(&out_path).map_or(false, |content| content.ends_with('\n')) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    
+    assert_eq!(std::fs::read_to_string(&out_path).unwrap(), "1 pid-ok socket\n");
+    assert_eq!(manager.list_sockets().await[0].activations, 1);
+    
+    // Unloading closes the socket and removes its file
+    manager.unload_job("activated").await.unwrap();
+    assert!(manager.list_sockets().await.is_empty());
+    assert!(!socket_path.exists());
+}
+
+#[tokio::test]
+async fn test_idle_job_stopped_and_activated_again() {
+    let temp_dir = TempDir::new().unwrap();
+    let socket_path = temp_dir.path().join("idle.sock");
+    
+    // Take the datagram that activated the job, then idle
+    let config: JobConfig = toml::from_str(&format!(
+        "label = \"idler\"\n[program]\npath = \"/bin/sh\"\narguments = [\"-c\", \"head -c 64 <&3 >/dev/null; exec sleep 30\"]\n\
+         [supervision]\nkeep_alive = false\n[[sockets]]\nlisten = \"{}\"\ntype = \"dgram\"\nidle_timeout_sec = 1\n",
+        socket_path.display()
+    )).unwrap();
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(config).await.unwrap();
+    
+    let client = std::os::unix::net::UnixDatagram::unbound().unwrap();
+    client.send_to(b"wake", &socket_path).unwrap();
+    
+    let state = || async { manager.get_job_status("idler").await.unwrap().state };
+    for _ in 0..50 {
+        if state().await == JobState::Running {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(state().await, JobState::Running);
+    
+    // Stopped, not failed, once the socket was quiet for a second
+    tokio::time::sleep(Duration::from_millis(1800)).await;
+    assert_eq!(state().await, JobState::Stopped);
+    assert_eq!(manager.list_sockets().await[0].activations, 1);
+    
+    client.send_to(b"wake", &socket_path).unwrap();
+    for _ in 0..100 {
+        if state().await == JobState::Running {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(state().await, JobState::Running);
+    assert_eq!(manager.list_sockets().await[0].activations, 2);
+    
+    manager.unload_job("idler").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_listening_socket_satisfies_requires() {
+    let temp_dir = TempDir::new().unwrap();
+    let socket_path = temp_dir.path().join("lazy.sock");
+    
+    let lazy: JobConfig = toml::from_str(&format!(
+        "label = \"lazy\"\n[program]\npath = \"/bin/sleep\"\narguments = [\"30\"]\n[supervision]\nkeep_alive = false\n[[sockets]]\nlisten = \"{}\"\n",
+        socket_path.display()
+    )).unwrap();
+    let client: JobConfig = toml::from_str(
+        "label = \"client\"\nrequires = [\"lazy\"]\n[program]\npath = \"/bin/sleep\"\narguments = [\"30\"]\n[supervision]\nkeep_alive = false\n"
+    ).unwrap();
+    
+    let (mut manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.set_auto_start(false);
+    manager.load_job(lazy).await.unwrap();
+    manager.load_job(client).await.unwrap();
+    
+    assert!(manager.start_job("client").await.is_err());
+    assert!(matches!(manager.get_job_status("client").await.unwrap().state, JobState::Blocked(_)));
+    
+    // Listening is enough: the dependent starts, the socket-activated job does not
+    manager.listen_sockets("lazy").await.unwrap();
+    let state = |label| {
+        let manager = manager.clone();
+        async move { manager.get_job_status(label).await.unwrap().state }
+    };
+    for _ in 0..50 {
+        if state("client").await == JobState::Running {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(state("client").await, JobState::Running);
+    assert_eq!(state("lazy").await, JobState::Stopped);
+    
+    manager.kill_job("client").await.unwrap();
+    manager.unload_job("lazy").await.unwrap();
+}
\ No newline at end of file
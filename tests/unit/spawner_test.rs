@@ -0,0 +1,35 @@
+use nusalaunchd::job::config::JobConfig;
+use nusalaunchd::process::ProcessSpawner;
+use std::io;
+use tempfile::NamedTempFile;
+
+#[test]
+fn test_transient_spawn_errors() {
+    // Resource pressure is retryable
+    assert!(ProcessSpawner::is_transient_spawn_error(&io::Error::from_raw_os_error(libc::EAGAIN)));
+    assert!(ProcessSpawner::is_transient_spawn_error(&io::Error::from_raw_os_error(libc::ENOMEM)));
+    
+    // A missing or non-executable binary is permanent
+    assert!(!ProcessSpawner::is_transient_spawn_error(&io::Error::from_raw_os_error(libc::ENOENT)));
+    assert!(!ProcessSpawner::is_transient_spawn_error(&io::Error::from_raw_os_error(libc::EACCES)));
+}
+
+#[tokio::test]
+async fn test_spawn_retries_default() {
+    let toml_content = r#"
+        label = "retry-test"
+        
+        [program]
+        path = "/bin/true"
+        
+        [supervision]
+        keep_alive = false
+    "#;
+    
+    let mut file = NamedTempFile::new().unwrap();
+    std::io::Write::write_all(&mut file, toml_content.as_bytes()).unwrap();
+    
+    let config = JobConfig::from_file(file.path()).await.unwrap();
+    
+    assert_eq!(config.supervision.spawn_retries, 3);
+}
\ No newline at end of file
@@ -0,0 +1,228 @@
+use nusalaunchd::cli::args::OutputFormat;
+use nusalaunchd::cli::output::{render_job_status, render_jobs};
+use nusalaunchd::cli::status::{format_bytes, format_uptime, render_history, render_plain, render_summary, render_table, JobFilter};
+use nusalaunchd::job::{DaemonSummary, JobManager, JobState, JobStatus, RestartRecord};
+use std::time::{Duration, SystemTime};
+
+fn status(label: &str, state: JobState, pid: Option<u32>, uptime_sec: Option<u64>) -> JobStatus {
+    JobStatus {
+        label: label.to_string(),
+        state,
+        pid,
+        restart_count: 2,
+        uptime_sec,
+        supervised_sec: uptime_sec,
+        started_after_boot_sec: None,
+        exit_code: None,
+        exit_signal: None,
+        config: toml::from_str(&format!("label = \"{}\"\n[program]\npath = \"/bin/true\"\n", label)).unwrap(),
+        needs_restart: false,
+        source_path: None,
+        rss_bytes: None,
+        cpu_percent: None,
+    }
+}
+
+#[test]
+fn test_format_uptime() {
+    assert_eq!(format_uptime(Duration::from_secs(45)), "45s");
+    assert_eq!(format_uptime(Duration::from_secs(750)), "12m30s");
+    assert_eq!(format_uptime(Duration::from_secs(3 * 3600 + 300)), "3h05m");
+    assert_eq!(format_uptime(Duration::from_secs(2 * 86400 + 4 * 3600)), "2d04h");
+}
+
+#[test]
+fn test_format_bytes() {
+    assert_eq!(format_bytes(512), "512B");
+    assert_eq!(format_bytes(4096), "4.0K");
+    assert_eq!(format_bytes(12_900_000), "12.3M");
+    assert_eq!(format_bytes(3 << 30), "3.0G");
+}
+
+#[test]
+fn test_table_columns_are_aligned() {
+    let jobs = vec![
+        status("web-server", JobState::Running, Some(1234), Some(90)),
+        status("db", JobState::Stopped, None, None),
+    ];
+    
+    let table = render_table(&jobs, false);
+    let lines: Vec<&str> = table.lines().collect();
+    
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("LABEL"));
+    
+    // Every column starts at the same offset on every line
+    let state_column = lines[0].find("STATE").unwrap();
+    assert_eq!(lines[1].find("running"), Some(state_column));
+    assert_eq!(lines[2].find("stopped"), Some(state_column));
+    assert!(lines[1].contains("1m30s"));
+}
+
+#[test]
+fn test_plain_one_job_per_line() {
+    let jobs = vec![
+        status("web-server", JobState::Running, Some(1234), None),
+        status("db", JobState::Stopped, None, None),
+    ];
+    
+    assert_eq!(render_plain(&jobs), "web-server running 1234\ndb stopped -\n");
+}
+
+#[test]
+fn test_detailed_table_shows_source() {
+    let mut web = status("web-server", JobState::Running, Some(1234), None);
+    web.source_path = Some("/etc/nusalaunchd/jobs/web.toml".into());
+    let jobs = vec![web, status("adhoc", JobState::Stopped, None, None)];
+    
+    let table = render_table(&jobs, true);
+    let lines: Vec<&str> = table.lines().collect();
+    
+    let source_column = lines[0].find("SOURCE").unwrap();
+    assert_eq!(lines[1].find("/etc/nusalaunchd/jobs/web.toml"), Some(source_column));
+    assert!(lines[2].ends_with('-'));
+}
+
+#[test]
+fn test_detailed_table_shows_exit_signal() {
+    // The spawner records -1 as the exit code of a signalled process
+    let mut crashed = status("web-server", JobState::Failed("crashed".into()), None, None);
+    crashed.exit_code = Some(-1);
+    crashed.exit_signal = Some(9);
+    let mut exited = status("db", JobState::Stopped, None, None);
+    exited.exit_code = Some(2);
+    
+    let table = render_table(&[crashed, exited], true);
+    let lines: Vec<&str> = table.lines().collect();
+    
+    let exit_column = lines[0].find("LAST EXIT").unwrap();
+    assert_eq!(lines[1].find("SIGKILL (9)"), Some(exit_column), "{}", table);
+    assert_eq!(lines[2].find("code 2"), Some(exit_column), "{}", table);
+}
+
+#[test]
+fn test_json_output_reports_uptime_seconds() {
+    let jobs = vec![status("web-server", JobState::Failed("exit 1".into()), None, Some(90))];
+    
+    let json = render_jobs(&jobs, &OutputFormat::Json, false).unwrap();
+    assert!(json.contains("\"label\": \"web-server\""), "{}", json);
+    assert!(json.contains("\"uptime_sec\": 90"), "{}", json);
+    
+    assert_eq!(render_jobs(&jobs, &OutputFormat::Plain, false).unwrap(), render_plain(&jobs));
+    assert_eq!(jobs[0].state.name(), "failed");
+}
+
+#[test]
+fn test_job_status_shows_config_on_request() {
+    let jobs = vec![status("web-server", JobState::Running, Some(1234), Some(90))];
+    
+    let runtime = render_job_status(&jobs, &OutputFormat::Table, false).unwrap();
+    assert_eq!(runtime, render_table(&jobs, true));
+    assert!(!runtime.contains("[program]"));
+    
+    let full = render_job_status(&jobs, &OutputFormat::Table, true).unwrap();
+    let config = nusalaunchd::cli::export::to_toml(&jobs[0].config).unwrap();
+    assert!(full.starts_with(&runtime));
+    assert!(full.contains(&format!("# web-server\n{}", config.trim_end())), "{}", full);
+    
+    // Defaults are spelled out
+    assert!(full.contains("keep_alive = "), "{}", full);
+}
+
+#[test]
+fn test_job_status_encodes_config_on_request() {
+    let jobs = vec![status("web-server", JobState::Running, Some(1234), Some(90))];
+    
+    for format in [OutputFormat::Json, OutputFormat::Yaml] {
+        let runtime = render_job_status(&jobs, &format, false).unwrap();
+        assert!(runtime.contains("web-server"), "{}", runtime);
+        assert!(!runtime.contains("config"), "{}", runtime);
+        
+        let full = render_job_status(&jobs, &format, true).unwrap();
+        assert!(full.contains("config"), "{}", full);
+        assert!(full.contains("/bin/true"), "{}", full);
+    }
+}
+
+#[test]
+fn test_job_filter() {
+    let labels = |filter: JobFilter| -> Vec<String> {
+        let jobs = vec![
+            JobStatus { started_after_boot_sec: Some(20), ..status("web", JobState::Running, Some(1234), None) },
+            JobStatus { started_after_boot_sec: Some(90_000), ..status("api", JobState::Running, Some(1300), None) },
+            status("db", JobState::Failed("exit 1".into()), None, None),
+            status("cache", JobState::Stopped, None, None),
+        ];
+        filter.apply(jobs)
+            .unwrap()
+            .into_iter()
+            .map(|job| job.label)
+            .collect()
+    };
+    
+    assert_eq!(labels(JobFilter::default()), ["api", "cache", "db", "web"]);
+    assert_eq!(labels(JobFilter { state: Some("failed".into()), ..Default::default() }), ["db"]);
+    assert_eq!(labels(JobFilter { running: true, failed: true, ..Default::default() }), ["api", "db", "web"]);
+    assert_eq!(labels(JobFilter { since_boot: Some(60), ..Default::default() }), ["web"]);
+    
+    assert!(JobFilter { state: Some("sleeping".into()), ..Default::default() }.validate().is_err());
+}
+
+#[tokio::test]
+async fn test_summary_counts_jobs_by_state() {
+    let (mut manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.set_auto_start(false);
+    for (label, path) in [("sleeper", "/bin/sleep"), ("idle", "/bin/true")] {
+        manager.load_job(toml::from_str(&format!(
+            "label = \"{}\"\n[program]\npath = \"{}\"\narguments = [\"30\"]\n[supervision]\nkeep_alive = false\n",
+            label, path
+        )).unwrap()).await.unwrap();
+    }
+    manager.start_job("sleeper").await.unwrap();
+    
+    let summary = manager.summary().await;
+    assert_eq!((summary.total, summary.running, summary.stopped, summary.failed), (2, 1, 1, 0));
+    assert_eq!(summary.restarts, 0);
+    assert!(summary.started_at <= SystemTime::now());
+    assert!(summary.uptime_sec < 5);
+    
+    manager.kill_job("sleeper").await.unwrap();
+}
+
+#[test]
+fn test_render_summary() {
+    let summary = DaemonSummary {
+        total: 5,
+        running: 2,
+        stopped: 1,
+        failed: 1,
+        backoff: 0,
+        other: 1,
+        restarts: 7,
+        started_at: SystemTime::now(),
+        uptime_sec: 750,
+    };
+    
+    let rendered = render_summary(&summary);
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines[0], "Jobs:      5 (2 running, 1 stopped, 1 failed, 0 backoff, 1 other)");
+    assert_eq!(lines[1], "Restarts:  7");
+    assert!(lines[2].starts_with("Uptime:    12m30s (since "), "{}", lines[2]);
+}
+
+#[test]
+fn test_render_history() {
+    assert_eq!(render_history(&[]), "No restarts\n");
+    
+    let at = SystemTime::now();
+    let rendered = render_history(&[
+        RestartRecord { at, exit_code: Some(1), signal: None },
+        RestartRecord { at, exit_code: None, signal: Some(11) },
+        RestartRecord { at, exit_code: None, signal: None },
+    ]);
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert!(lines[0].starts_with("TIME"), "{}", lines[0]);
+    assert!(lines[1].ends_with("code 1"), "{}", lines[1]);
+    assert!(lines[2].ends_with("SIGSEGV (11)"), "{}", lines[2]);
+    assert!(lines[3].ends_with("spawn failed"), "{}", lines[3]);
+}
\ No newline at end of file
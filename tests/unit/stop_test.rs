@@ -0,0 +1,164 @@
+mod common;
+
+use std::time::{Duration, Instant};
+use nix::sys::signal::Signal;
+use nusalaunchd::job::config::{parse_signal, JobConfig};
+use nusalaunchd::job::validator::ConfigValidator;
+use nusalaunchd::job::{JobManager, JobState};
+
+fn job(supervision: &str) -> JobConfig {
+    common::job_config(&format!(
+        "label = \"trap\"\n[program]\npath = \"/bin/sh\"\narguments = [\"-c\", \"trap '' TERM; trap 'exit 0' INT; while :; do sleep 0.1; done\"]\n[supervision]\nkeep_alive = false\n{}\n",
+        supervision
+    ))
+}
+
+#[test]
+fn test_parse_signal_names() {
+    assert_eq!(parse_signal("SIGINT"), Some(Signal::SIGINT));
+    assert_eq!(parse_signal("quit"), Some(Signal::SIGQUIT));
+    assert_eq!(parse_signal("SIGBOGUS"), None);
+    assert_eq!(parse_signal("10"), Some(Signal::SIGUSR1));
+    assert_eq!(parse_signal("0"), None);
+    
+    let config = job("");
+    assert_eq!(config.supervision.stop_signal(), Signal::SIGTERM);
+    assert_eq!(config.supervision.stop_timeout(), Duration::from_secs(10));
+}
+
+#[tokio::test]
+async fn test_validator_rejects_unknown_signal() {
+    assert!(ConfigValidator::validate(&job("kill_signal = \"SIGQUIT\""), false).await.is_ok());
+    
+    let err = ConfigValidator::validate(&job("kill_signal = \"SIGNOPE\""), false).await.unwrap_err();
+    assert!(err.to_string().contains("SIGNOPE"), "{}", err);
+}
+
+#[tokio::test]
+async fn test_stop_uses_kill_signal() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(job("kill_signal = \"SIGINT\"\nstop_timeout_sec = 30")).await.unwrap();
+    manager.start_job("trap").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    
+    // SIGTERM is ignored, so only SIGINT stops it before the timeout
+    let started = Instant::now();
+    manager.stop_job("trap").await.unwrap();
+    
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert_eq!(manager.get_job_status("trap").await.unwrap().state, JobState::Stopped);
+}
+
+#[tokio::test]
+async fn test_shutdown_uses_job_stop_timeout() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(job("stop_timeout_sec = 1")).await.unwrap();
+    manager.start_job("trap").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    
+    // SIGTERM is ignored, so the job is killed after its own timeout, not the default
+    let started = Instant::now();
+    manager.shutdown_all(None).await;
+    
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(manager.get_job_status("trap").await.unwrap().pid.is_none());
+}
+
+#[tokio::test]
+async fn test_send_signal_to_running_job() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let marker = temp_dir.path().join("usr1");
+    let config: JobConfig = toml::from_str(&format!(
+        "label = \"trap\"\n[program]\npath = \"/bin/sh\"\narguments = [\"-c\", \"trap 'touch {}' USR1; while :; do sleep 0.1; done\"]\n[supervision]\nkeep_alive = false\n",
+        marker.display()
+    )).unwrap();
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(config).await.unwrap();
+    
+    let err = manager.send_signal("trap", "USR1").await.unwrap_err();
+    assert!(err.to_string().contains("not running"), "{}", err);
+    
+    manager.start_job("trap").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    
+    let err = manager.send_signal("trap", "SIGNOPE").await.unwrap_err();
+    assert!(err.to_string().contains("SIGNOPE"), "{}", err);
+    
+    let pid = manager.send_signal("trap", "usr1").await.unwrap();
+    assert_eq!(Some(pid), manager.get_job_status("trap").await.unwrap().pid);
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(marker.exists());
+    assert_eq!(manager.get_job_status("trap").await.unwrap().state, JobState::Running);
+    
+    manager.kill_job("trap").await.unwrap();
+}
+/// Whether `pid` is gone (or only a zombie waiting for its new parent to reap it)
+fn process_gone(pid: i32) -> bool {
+    match std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
+        Ok(stat) => stat.rsplit(')').next().unwrap_or("").trim_start().starts_with('Z'),
+        Err(_) => true,
+    }
+}
+
+#[tokio::test]
+async fn test_stop_kills_process_group() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let pid_file = temp_dir.path().join("sleeper.pid");
+    let config = |extra: &str| -> JobConfig {
+        toml::from_str(&format!(
+            "label = \"forker\"\n[program]\npath = \"/bin/sh\"\narguments = [\"-c\", \"sleep 1000 & echo $! > {}; wait\"]\n[supervision]\nkeep_alive = false\n{}\n",
+            pid_file.display(), extra
+        )).unwrap()
+    };
+    assert!(config("").supervision.kill_process_group);
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(config("")).await.unwrap();
+    manager.start_job("forker").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    
+    let sleeper: i32 = std::fs::read_to_string(&pid_file).unwrap().trim().parse().unwrap();
+    assert!(!process_gone(sleeper));
+    
+    manager.stop_job("forker").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert!(process_gone(sleeper), "sleeper {} survived its job", sleeper);
+}
+
+#[tokio::test]
+async fn test_stop_without_process_group_spares_children() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let pid_file = temp_dir.path().join("sleeper.pid");
+    let config: JobConfig = toml::from_str(&format!(
+        "label = \"forker\"\n[program]\npath = \"/bin/sh\"\narguments = [\"-c\", \"sleep 1000 & echo $! > {}; wait\"]\n[supervision]\nkeep_alive = false\nkill_process_group = false\n",
+        pid_file.display()
+    )).unwrap();
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job(config).await.unwrap();
+    manager.start_job("forker").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    
+    let sleeper: i32 = std::fs::read_to_string(&pid_file).unwrap().trim().parse().unwrap();
+    manager.stop_job("forker").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    
+    assert!(!process_gone(sleeper));
+    nix::sys::signal::kill(nix::unistd::Pid::from_raw(sleeper), Signal::SIGKILL).unwrap();
+}
+
+#[test]
+fn test_defaults_without_supervision_table() {
+    let bare: JobConfig = toml::from_str("label = \"bare\"\n[program]\npath = \"/bin/true\"\n").unwrap();
+    let empty: JobConfig = toml::from_str("label = \"empty\"\n[program]\npath = \"/bin/true\"\n[supervision]\n").unwrap();
+    
+    for supervision in [&bare.supervision, &empty.supervision] {
+        assert!(supervision.kill_process_group);
+        assert!(supervision.keep_alive);
+        assert_eq!(supervision.restart_limit_burst, 5);
+        assert_eq!(supervision.spawn_retries, 3);
+        assert_eq!(supervision.max_restarts, 5);
+        assert_eq!(supervision.restart_delay_sec, 1);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,349 @@
+mod common;
+
+use nusalaunchd::job::{ChannelOptions, JobConfig, JobEvent, JobManager, JobState};
+use nusalaunchd::process::mock::MockSpawner;
+use nusalaunchd::util::error::NusaError;
+use nix::sys::signal::Signal;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+async fn mock_manager() -> (JobManager, mpsc::Receiver<JobEvent>, MockSpawner) {
+    let mut mock = None;
+    let (mut manager, event_rx) = JobManager::with_spawner(ChannelOptions::default(), |_, exit_tx| {
+        let spawner = MockSpawner::new(exit_tx);
+        mock = Some(spawner.clone());
+        Arc::new(spawner)
+    }).await.unwrap();
+    manager.set_auto_start(false);
+    
+    (manager, event_rx, mock.unwrap())
+}
+
+fn job(supervision: &str) -> JobConfig {
+    common::job_config(&format!(
+        "label = \"worker\"\n[program]\npath = \"/usr/bin/worker\"\n[supervision]\nkeep_alive = true\n{}\n",
+        supervision
+    ))
+}
+
+/// Skip events until one matches
+async fn wait_for(event_rx: &mut mpsc::Receiver<JobEvent>, matches: impl Fn(&JobEvent) -> bool) -> JobEvent {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let event = event_rx.recv().await.expect("event channel closed");
+            if matches(&event) {
+                return event;
+            }
+        }
+    }).await.expect("timed out waiting for event")
+}
+
+#[tokio::test]
+async fn test_crashes_until_max_restarts() {
+    let (manager, mut event_rx, mock) = mock_manager().await;
+    manager.load_job(job("restart_delay_sec = 0\nmax_restarts = 3")).await.unwrap();
+    manager.start_job("worker").await.unwrap();
+    
+    for restart in 1..=2 {
+        wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobStarted(..))).await;
+        assert_eq!(manager.get_job_status("worker").await.unwrap().state, JobState::Running);
+        
+        assert!(mock.exit("worker", 1));
+        match wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobRestartScheduled(..))).await {
+            JobEvent::JobRestartScheduled(_, _, count) => assert_eq!(count, restart),
+            _ => unreachable!(),
+        }
+        assert_eq!(manager.get_job_status("worker").await.unwrap().state, JobState::Backoff);
+    }
+    
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobStarted(..))).await;
+    assert!(mock.crash("worker", Signal::SIGSEGV));
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobFailed(..))).await;
+    
+    let status = manager.get_job_status("worker").await.unwrap();
+    assert_eq!(
+        status.state,
+        JobState::Failed("Exceeded max restarts (3): Process terminated by SIGSEGV (11)".into())
+    );
+    assert_eq!(status.exit_signal, Some(Signal::SIGSEGV as i32));
+    assert_eq!(mock.spawn_count("worker"), 3);
+}
+
+#[tokio::test]
+async fn test_no_restart_exit_code_stops_supervision() {
+    let (manager, mut event_rx, mock) = mock_manager().await;
+    manager.load_job(job("restart_delay_sec = 0\nno_restart_exit_codes = [78]")).await.unwrap();
+    manager.start_job("worker").await.unwrap();
+    
+    assert!(mock.exit("worker", 78));
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobExited(..))).await;
+    
+    let status = manager.get_job_status("worker").await.unwrap();
+    assert_eq!(status.state, JobState::Stopped);
+    assert_eq!(status.exit_code, Some(78));
+    assert_eq!(mock.spawn_count("worker"), 1);
+}
+
+#[tokio::test]
+async fn test_clean_exit_completes_job() {
+    let (manager, mut event_rx, mock) = mock_manager().await;
+    manager.load_job(job("restart_policy = \"on-failure\"")).await.unwrap();
+    manager.start_job("worker").await.unwrap();
+    
+    assert!(mock.exit("worker", 0));
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobExited(..))).await;
+    
+    let status = manager.get_job_status("worker").await.unwrap();
+    assert_eq!(status.state, JobState::Completed);
+    assert_eq!(status.state.to_string(), "completed");
+    assert_eq!(mock.spawn_count("worker"), 1);
+}
+
+#[tokio::test]
+async fn test_stop_signals_simulated_process() {
+    let (manager, _event_rx, mock) = mock_manager().await;
+    manager.load_job(job("")).await.unwrap();
+    
+    let started = manager.start_job_detailed("worker").await.unwrap().unwrap();
+    assert_eq!(mock.pid("worker"), Some(started.pid));
+    
+    manager.stop_job("worker").await.unwrap();
+    assert_eq!(manager.get_job_status("worker").await.unwrap().state, JobState::Stopped);
+    assert!(mock.pid("worker").is_none());
+    assert!(!mock.exit("worker", 0));
+}
+
+#[tokio::test]
+async fn test_spawn_failures_back_off_until_spawn_succeeds() {
+    let (manager, mut event_rx, mock) = mock_manager().await;
+    manager.load_job(job("restart_delay_sec = 0\nretry_on_spawn_failure = true")).await.unwrap();
+    
+    mock.fail_spawns("worker", true);
+    assert!(manager.start_job("worker").await.is_err());
+    assert_eq!(manager.get_job_status("worker").await.unwrap().state, JobState::Backoff);
+    
+    mock.fail_spawns("worker", false);
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobStarted(..))).await;
+    assert_eq!(manager.get_job_status("worker").await.unwrap().state, JobState::Running);
+    assert_eq!(mock.spawn_count("worker"), 1);
+}
+
+#[tokio::test]
+async fn test_transient_spawn_failures_are_retried() {
+    let (manager, _event_rx, mock) = mock_manager().await;
+    manager.load_job(job("spawn_retries = 3")).await.unwrap();
+    
+    mock.fail_next_spawns("worker", 2);
+    manager.start_job("worker").await.unwrap();
+    assert_eq!(manager.get_job_status("worker").await.unwrap().state, JobState::Running);
+    assert_eq!(mock.spawn_count("worker"), 1);
+}
+
+#[tokio::test]
+async fn test_transient_spawn_failures_beyond_spawn_retries_fail_the_start() {
+    let (manager, _event_rx, mock) = mock_manager().await;
+    manager.load_job(job("spawn_retries = 1")).await.unwrap();
+    
+    mock.fail_next_spawns("worker", 2);
+    assert!(manager.start_job("worker").await.is_err());
+    assert_eq!(mock.spawn_count("worker"), 0);
+}
+
+#[tokio::test]
+async fn test_unload_during_spawn_kills_the_new_process() {
+    let (manager, _event_rx, mock) = mock_manager().await;
+    manager.load_job(job("spawn_retries = 3")).await.unwrap();
+    
+    // The first retry waits 100ms; unload the job meanwhile
+    mock.fail_next_spawns("worker", 1);
+    let starting = manager.clone();
+    let start = tokio::spawn(async move { starting.start_job("worker").await });
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    manager.unload_job("worker").await.unwrap();
+    
+    assert!(matches!(start.await.unwrap(), Err(NusaError::JobNotFound(_))));
+    assert_eq!(mock.spawn_count("worker"), 1);
+    assert_eq!(mock.pid("worker"), None);
+    assert!(manager.get_job_status("worker").await.is_none());
+}
+
+#[tokio::test]
+async fn test_unload_during_failing_spawn() {
+    let (manager, _event_rx, mock) = mock_manager().await;
+    manager.load_job(job("spawn_retries = 1")).await.unwrap();
+    
+    mock.fail_next_spawns("worker", 2);
+    let starting = manager.clone();
+    let start = tokio::spawn(async move { starting.start_job("worker").await });
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    manager.unload_job("worker").await.unwrap();
+    
+    assert!(matches!(start.await.unwrap(), Err(NusaError::JobNotFound(_))));
+    assert_eq!(mock.spawn_count("worker"), 0);
+}
+
+#[tokio::test]
+async fn test_stop_during_spawn_kills_the_new_process() {
+    let (manager, _event_rx, mock) = mock_manager().await;
+    manager.load_job(job("spawn_retries = 3")).await.unwrap();
+    
+    mock.fail_next_spawns("worker", 1);
+    let starting = manager.clone();
+    let start = tokio::spawn(async move { starting.start_job("worker").await });
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    manager.stop_job("worker").await.unwrap();
+    
+    assert!(start.await.unwrap().is_err());
+    assert_eq!(mock.pid("worker"), None);
+    assert_eq!(manager.get_job_status("worker").await.unwrap().state, JobState::Stopped);
+}
+
+#[tokio::test]
+async fn test_daemon_signal_is_not_a_crash() {
+    let (manager, mut event_rx, mock) = mock_manager().await;
+    manager.load_job(job("restart_policy = \"on-crash\"\nrestart_delay_sec = 0")).await.unwrap();
+    
+    manager.start_job("worker").await.unwrap();
+    manager.stop_job("worker").await.unwrap();
+    assert_eq!(manager.get_job_status("worker").await.unwrap().state, JobState::Stopped);
+    
+    manager.start_job("worker").await.unwrap();
+    manager.send_signal("worker", "TERM").await.unwrap();
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobExited(..))).await;
+    assert_eq!(manager.get_job_status("worker").await.unwrap().state, JobState::Stopped);
+    
+    // The same signal from elsewhere is a crash
+    manager.start_job("worker").await.unwrap();
+    assert_eq!(mock.spawn_count("worker"), 3);
+    assert!(mock.crash("worker", Signal::SIGTERM));
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobRestartScheduled(..))).await;
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobStarted(..))).await;
+    assert_eq!(mock.spawn_count("worker"), 4);
+}
+
+#[tokio::test]
+async fn test_restart_history_keeps_recent_exits() {
+    let (manager, mut event_rx, mock) = mock_manager().await;
+    manager.load_job(job("restart_delay_sec = 0\nmax_restarts = 0")).await.unwrap();
+    manager.start_job("worker").await.unwrap();
+    assert_eq!(manager.get_job_history("worker").await, Some(vec![]));
+    
+    for exit_code in 1..=20 {
+        wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobStarted(..))).await;
+        assert!(mock.exit("worker", exit_code));
+    }
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobStarted(..))).await;
+    assert!(mock.crash("worker", Signal::SIGSEGV));
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobRestartScheduled(..))).await;
+    
+    let history = manager.get_job_history("worker").await.unwrap();
+    assert_eq!(history.len(), 20);
+    assert_eq!(history[0].exit_code, Some(2));
+    assert!(history.windows(2).all(|pair| pair[0].at <= pair[1].at));
+    
+    let last = history.last().unwrap();
+    assert_eq!((last.exit_code, last.signal), (None, Some(Signal::SIGSEGV as i32)));
+    assert!(manager.get_job_history("missing").await.is_none());
+}
+
+#[tokio::test]
+async fn test_summary_counts_restarts_across_restart_count_resets() {
+    let (manager, mut event_rx, mock) = mock_manager().await;
+    manager.load_job(job("restart_delay_sec = 0")).await.unwrap();
+    manager.start_job("worker").await.unwrap();
+    
+    for _ in 0..2 {
+        wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobStarted(..))).await;
+        assert!(mock.exit("worker", 1));
+    }
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobStarted(..))).await;
+    assert_eq!(manager.get_job_status("worker").await.unwrap().restart_count, 2);
+    
+    // A manual start resets the job's restart count but not the summary
+    manager.stop_job("worker").await.unwrap();
+    manager.start_job("worker").await.unwrap();
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobStarted(..))).await;
+    assert!(mock.exit("worker", 1));
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobStarted(..))).await;
+    
+    assert_eq!(manager.get_job_status("worker").await.unwrap().restart_count, 1);
+    assert_eq!(manager.summary().await.restarts, 3);
+}
+
+#[tokio::test]
+async fn test_crash_after_survived_signal_restarts() {
+    let (manager, mut event_rx, mock) = mock_manager().await;
+    mock.survive_signal(Signal::SIGHUP);
+    manager.load_job(job("restart_policy = \"on-crash\"\nrestart_delay_sec = 0")).await.unwrap();
+    manager.start_job("worker").await.unwrap();
+    
+    manager.send_signal("worker", "HUP").await.unwrap();
+    assert_eq!(manager.get_job_status("worker").await.unwrap().state, JobState::Running);
+    
+    assert!(mock.crash("worker", Signal::SIGSEGV));
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobRestartScheduled(..))).await;
+    wait_for(&mut event_rx, |event| matches!(event, JobEvent::JobStarted(..))).await;
+    assert_eq!(mock.spawn_count("worker"), 2);
+}
+
+/// Wait for the job's next start, collecting rapid clean exit warnings on the way
+async fn next_start(event_rx: &mut mpsc::Receiver<JobEvent>, warnings: &mut Vec<u32>) {
+    loop {
+        match wait_for(event_rx, |event| matches!(event, JobEvent::JobStarted(..) | JobEvent::RapidCleanExits(..))).await {
+            JobEvent::RapidCleanExits(_, count) => warnings.push(count),
+            _ => return,
+        }
+    }
+}
+
+/// Exit with each code in turn, returning the warnings raised up to the following start
+async fn exit_in_turn(
+    mock: &MockSpawner,
+    event_rx: &mut mpsc::Receiver<JobEvent>,
+    exit_codes: &[i32],
+) -> Vec<u32> {
+    let mut warnings = Vec::new();
+    for &exit_code in exit_codes {
+        assert!(mock.exit("worker", exit_code));
+        next_start(event_rx, &mut warnings).await;
+    }
+    warnings
+}
+
+#[tokio::test]
+async fn test_rapid_clean_exits_warned_once() {
+    let (manager, mut event_rx, mock) = mock_manager().await;
+    manager.load_job(job("restart_policy = \"always\"\nrestart_delay_sec = 0\nmax_restarts = 100")).await.unwrap();
+    manager.start_job("worker").await.unwrap();
+    next_start(&mut event_rx, &mut Vec::new()).await;
+    
+    // Warned on the third exit in a row, not again while the streak continues
+    assert_eq!(exit_in_turn(&mock, &mut event_rx, &[0, 0, 0, 0, 0]).await, [3]);
+}
+
+#[tokio::test]
+async fn test_failed_exit_resets_rapid_clean_exits() {
+    let (manager, mut event_rx, mock) = mock_manager().await;
+    manager.load_job(job("restart_policy = \"always\"\nrestart_delay_sec = 0\nmax_restarts = 100")).await.unwrap();
+    manager.start_job("worker").await.unwrap();
+    next_start(&mut event_rx, &mut Vec::new()).await;
+    
+    assert!(exit_in_turn(&mock, &mut event_rx, &[0, 0, 1, 0, 0]).await.is_empty());
+    assert_eq!(exit_in_turn(&mock, &mut event_rx, &[0]).await, [3]);
+}
+
+#[tokio::test]
+async fn test_slow_exit_resets_rapid_clean_exits() {
+    let (manager, mut event_rx, mock) = mock_manager().await;
+    manager.load_job(job("restart_policy = \"always\"\nrestart_delay_sec = 0\nmax_restarts = 100")).await.unwrap();
+    manager.start_job("worker").await.unwrap();
+    next_start(&mut event_rx, &mut Vec::new()).await;
+    
+    assert!(exit_in_turn(&mock, &mut event_rx, &[0, 0]).await.is_empty());
+    
+    // Past the 10s window a clean exit is no longer rapid
+    tokio::time::sleep(Duration::from_secs(11)).await;
+    assert!(exit_in_turn(&mock, &mut event_rx, &[0, 0, 0]).await.is_empty());
+    assert_eq!(exit_in_turn(&mock, &mut event_rx, &[0]).await, [3]);
+}
\ No newline at end of file
@@ -0,0 +1,26 @@
+use nusalaunchd::cli::output::{set_color, Table};
+
+fn table() -> Table {
+    let mut table = Table::new(&["LABEL", "STATE", "PID"]).with_state_column(1);
+    table.push(vec!["web".into(), "running".into(), "1234".into()]);
+    table.push(vec!["worker".into(), "failed (exit 1)".into(), "-".into()]);
+    table.push(vec!["db".into(), "stopped".into(), "-".into()]);
+    table
+}
+
+#[test]
+fn test_state_colored_only_when_enabled() {
+    let plain = table().render();
+    assert!(!plain.contains('\x1B'));
+    assert_eq!(plain.lines().nth(1), Some("web     running          1234"));
+    
+    set_color(true);
+    let colored = table().render();
+    set_color(false);
+    
+    let lines: Vec<&str> = colored.lines().collect();
+    assert_eq!(lines[0], "LABEL   STATE            PID");
+    assert_eq!(lines[1], "web     \x1B[32mrunning\x1B[0m          1234");
+    assert_eq!(lines[2], "worker  \x1B[31mfailed (exit 1)\x1B[0m  -");
+    assert_eq!(lines[3], "db      stopped          -");
+}
\ No newline at end of file
@@ -0,0 +1,55 @@
+use nusalaunchd::job::config::{split_instance, JobConfig};
+use nusalaunchd::job::{JobManager, JobState};
+
+fn template() -> JobConfig {
+    toml::from_str(
+        "label = \"worker@\"\n[program]\npath = \"/bin/sleep\"\narguments = [\"30\", \"--id=%i\"]\n\
+         [[environment]]\nkey = \"WORKER_ID\"\nvalue = \"worker-%i\"\n[supervision]\nkeep_alive = true\n"
+    ).unwrap()
+}
+
+#[test]
+fn test_split_instance() {
+    assert_eq!(split_instance("worker@3"), Some(("worker@", "3")));
+    assert_eq!(split_instance("worker@"), None);
+    assert_eq!(split_instance("worker"), None);
+    assert_eq!(split_instance("@3"), None);
+}
+
+#[tokio::test]
+async fn test_instantiate_substitutes_instance_name() {
+    let template = template();
+    assert!(template.is_template());
+    assert!(template.validate().await.is_ok());
+    
+    let instance = template.instantiate("3");
+    assert!(!instance.is_template());
+    assert_eq!(instance.label, "worker@3");
+    assert_eq!(instance.program.arguments, vec!["30", "--id=3"]);
+    assert_eq!(instance.environment[0].value, "worker-3");
+    
+    let mut bad = template.clone();
+    bad.label = "a@b@c".to_string();
+    assert!(bad.validate().await.is_err());
+}
+
+#[tokio::test]
+async fn test_start_instantiates_loaded_template() {
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    
+    // Templates are never started, even with keep_alive set
+    manager.load_job(template()).await.unwrap();
+    assert_eq!(manager.get_job_status("worker@").await.unwrap().state, JobState::Stopped);
+    let err = manager.start_job("worker@").await.unwrap_err();
+    assert!(err.to_string().contains("is a template"), "{}", err);
+    
+    manager.start_job("worker@2").await.unwrap();
+    let status = manager.get_job_status("worker@2").await.unwrap();
+    assert!(matches!(status.state, JobState::Starting | JobState::Running), "{:?}", status.state);
+    assert_eq!(status.config.program.arguments, vec!["30", "--id=2"]);
+    
+    assert!(manager.start_job("other@2").await.is_err());
+    assert_eq!(manager.instantiate("worker@", "2").await.unwrap_err().code(), "job_exists");
+    
+    manager.kill_job("worker@2").await.unwrap();
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+use nusalaunchd::process::tree::{build_tree, parse_status, read_processes, render_tree, ProcessInfo};
+use std::path::Path;
+use tempfile::TempDir;
+
+fn add_process(root: &Path, pid: u32, ppid: u32, name: &str) {
+    let dir = root.join(pid.to_string());
+    std::fs::create_dir(&dir).unwrap();
+    std::fs::write(
+        dir.join("status"),
+        format!("Name:\t{}\nUmask:\t0022\nState:\tS (sleeping)\nTgid:\t{}\nPid:\t{}\nPPid:\t{}\n", name, pid, pid, ppid),
+    ).unwrap();
+}
+
+#[test]
+fn test_parse_status() {
+    assert_eq!(
+        parse_status(42, "Name:\tnginx\nState:\tS (sleeping)\nPPid:\t1\n"),
+        Some(ProcessInfo { pid: 42, ppid: 1, name: "nginx".to_string() })
+    );
+    assert_eq!(parse_status(42, "Name:\tnginx\n"), None);
+}
+
+#[test]
+fn test_tree_from_proc_fixture() {
+    let proc_root = TempDir::new().unwrap();
+    add_process(proc_root.path(), 1, 0, "init");
+    add_process(proc_root.path(), 100, 1, "nginx");
+    add_process(proc_root.path(), 101, 100, "worker");
+    add_process(proc_root.path(), 102, 100, "worker");
+    add_process(proc_root.path(), 110, 101, "sh");
+    add_process(proc_root.path(), 200, 1, "unrelated");
+    std::fs::create_dir(proc_root.path().join("self")).unwrap();
+    
+    let processes = read_processes(proc_root.path());
+    assert_eq!(processes.len(), 6);
+    
+    let tree = build_tree(&processes, 100).unwrap();
+    assert_eq!(render_tree(&tree), "100 nginx\n  101 worker\n    110 sh\n  102 worker\n");
+    
+    assert!(build_tree(&processes, 999).is_none());
+}
+
+#[test]
+fn test_tree_of_own_process() {
+    let processes = read_processes(Path::new("/proc"));
+    let tree = build_tree(&processes, std::process::id()).unwrap();
+    assert_eq!(tree.process.pid, std::process::id());
+}
\ No newline at end of file
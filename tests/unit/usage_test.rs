@@ -0,0 +1,92 @@
+use nusalaunchd::job::JobManager;
+use nusalaunchd::process::usage::{
+    parse_cpu_ticks, parse_resident_pages, parse_start_ticks, parse_system_uptime, read_cpu_sample,
+    read_rss_bytes, read_start_ticks, running_for, ticks_per_sec, CpuSample,
+};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+#[test]
+fn test_parse_cpu_ticks() {
+    let stat = "4242 (my (odd) proc) S 1 4242 4242 0 -1 4194560 1200 0 3 0 150 25 0 0 20 0 1 0 9000 12345678 500";
+    assert_eq!(parse_cpu_ticks(stat), Some(175));
+    assert_eq!(parse_cpu_ticks("4242 (truncated) S 1 4242"), None);
+}
+
+#[test]
+fn test_parse_start_ticks() {
+    let stat = "4242 (my (odd) proc) S 1 4242 4242 0 -1 4194560 1200 0 3 0 150 25 0 0 20 0 1 0 9000 12345678 500";
+    assert_eq!(parse_start_ticks(stat), Some(9000));
+    assert_eq!(parse_system_uptime("3600.25 7000.10\n"), Some(3600.25));
+}
+
+#[test]
+fn test_parse_resident_pages() {
+    assert_eq!(parse_resident_pages("2500 734 512 12 0 300 0\n"), Some(734));
+    assert_eq!(parse_resident_pages(""), None);
+}
+
+#[test]
+fn test_read_from_proc_fixture() {
+    let proc_root = TempDir::new().unwrap();
+    let dir = proc_root.path().join("77");
+    std::fs::create_dir(&dir).unwrap();
+    std::fs::write(dir.join("stat"), "77 (app) R 1 77 77 0 -1 0 0 0 0 0 40 10 0 0 20 0 1 0 100 0 0\n").unwrap();
+    std::fs::write(dir.join("statm"), "1000 3 2 1 0 2 0\n").unwrap();
+    
+    assert_eq!(read_cpu_sample(proc_root.path(), 77).map(|sample| sample.ticks), Some(50));
+    assert_eq!(read_rss_bytes(proc_root.path(), 77).map(|bytes| bytes % 3), Some(0));
+    assert!(read_cpu_sample(proc_root.path(), 78).is_none());
+    assert_eq!(read_start_ticks(proc_root.path(), 77), Some(100));
+}
+
+#[test]
+fn test_running_for_from_boot_offsets() {
+    let proc_root = TempDir::new().unwrap();
+    std::fs::write(proc_root.path().join("uptime"), "1000.00 1800.00\n").unwrap();
+    
+    let started = 400 * ticks_per_sec();
+    assert_eq!(running_for(proc_root.path(), started), Some(Duration::from_secs(600)));
+}
+
+#[test]
+fn test_cpu_percent_between_samples() {
+    let start = Instant::now();
+    let previous = CpuSample { pid: 7, ticks: 100, taken_at: start };
+    let current = CpuSample { pid: 7, ticks: 150, taken_at: start + Duration::from_secs(2) };
+    
+    // 50 ticks at 100/s is half a second of CPU over two seconds
+    assert_eq!(current.percent_since(&previous, 100), Some(25.0));
+    assert_eq!(previous.percent_since(&previous, 100), None);
+    assert_eq!(current.percent_since(&CpuSample { pid: 8, ..previous }, 100), None);
+}
+
+#[tokio::test]
+async fn test_status_reports_usage_while_running() {
+    let (mut manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.set_auto_start(false);
+    manager.load_job(toml::from_str(
+        "label = \"sleeper\"\n[program]\npath = \"/bin/sleep\"\narguments = [\"30\"]\n[supervision]\nkeep_alive = false\n"
+    ).unwrap()).await.unwrap();
+    
+    let stopped = manager.get_job_status("sleeper").await.unwrap();
+    assert!(stopped.rss_bytes.is_none() && stopped.cpu_percent.is_none());
+    
+    manager.start_job("sleeper").await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    
+    let running = manager.get_job_status("sleeper").await.unwrap();
+    assert!(running.rss_bytes.map_or(false, |bytes| bytes > 0), "{:?}", running.rss_bytes);
+    assert!(running.cpu_percent.is_some());
+    
+    // Process and supervision start together here; the process start has tick granularity
+    let (uptime, supervised) = (running.uptime_sec.unwrap(), running.supervised_sec.unwrap());
+    assert!(uptime.abs_diff(supervised) <= 1, "{} vs {}", uptime, supervised);
+    
+    let pid = running.pid.unwrap();
+    let started = read_start_ticks(Path::new("/proc"), pid).unwrap() / ticks_per_sec();
+    assert_eq!(running.started_after_boot_sec, Some(started));
+    
+    manager.kill_job("sleeper").await.unwrap();
+}
\ No newline at end of file
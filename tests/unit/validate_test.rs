@@ -0,0 +1,152 @@
+use nusalaunchd::cli::args::OutputFormat;
+use nusalaunchd::cli::validate::{check_dir, render_dir, ValidationResult};
+use tempfile::TempDir;
+
+#[tokio::test]
+async fn test_check_dir_reports_each_file() {
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("a.toml"), "label = \"a\"\n[program]\npath = \"/bin/true\"\n").unwrap();
+    std::fs::write(temp_dir.path().join("b.toml"), "label = \"b\"\n").unwrap();
+    std::fs::write(temp_dir.path().join("notes.txt"), "not a config").unwrap();
+    
+    let results = check_dir(temp_dir.path(), false).await.unwrap();
+    
+    assert_eq!(results.len(), 2);
+    assert!(results[0].valid);
+    assert_eq!(results[0].label.as_deref(), Some("a"));
+    assert!(results[0].error.is_none());
+    assert!(!results[1].valid);
+    assert!(results[1].label.is_none());
+    assert!(results[1].error.is_some());
+    
+    let table = render_dir(&results, &OutputFormat::Table).unwrap();
+    assert!(table.contains("✓"));
+    assert!(table.ends_with("Validation complete: 1 valid, 1 invalid\n"));
+}
+
+#[tokio::test]
+async fn test_check_dir_names_dependency_cycles() {
+    let temp_dir = TempDir::new().unwrap();
+    for (label, dependencies) in [
+        ("a", "requires = [\"b\"]"),
+        ("b", "after = [\"a\"]"),
+        ("c", "requires = [\"a\"]"),
+        ("loner", "requires = [\"loner\"]"),
+    ] {
+        std::fs::write(
+            temp_dir.path().join(format!("{}.toml", label)),
+            format!("label = \"{}\"\n{}\n[program]\npath = \"/bin/true\"\n", label, dependencies),
+        ).unwrap();
+    }
+    
+    let results = check_dir(temp_dir.path(), false).await.unwrap();
+    let error = |label: &str| {
+        let result = results.iter().find(|result| result.label.as_deref() == Some(label)).unwrap();
+        result.error.clone().unwrap_or_default()
+    };
+    
+    assert!(error("a").contains("a -> b -> a"), "{}", error("a"));
+    assert_eq!(error("b"), error("a"));
+    
+    // A job naming itself is caught on its own, before any cycle search
+    let loner = results.iter().find(|result| result.path.ends_with("loner.toml")).unwrap();
+    assert!(loner.error.as_deref().unwrap().contains("cannot depend on itself"), "{:?}", loner.error);
+    
+    // Depending on a cycle is not being on it
+    assert_eq!(error("c"), "");
+    assert_eq!(results.iter().filter(|result| result.valid).count(), 1);
+}
+
+#[tokio::test]
+async fn test_check_missing_file() {
+    let result = ValidationResult::check(std::path::Path::new("/nonexistent/job.toml"), false).await;
+    
+    assert!(!result.valid);
+    assert!(result.error.unwrap().contains("not found"));
+}
+
+#[tokio::test]
+async fn test_strict_requires_executable_program() {
+    use std::os::unix::fs::PermissionsExt;
+    
+    let temp_dir = TempDir::new().unwrap();
+    let script = temp_dir.path().join("script.sh");
+    std::fs::write(&script, "#!/bin/sh\n").unwrap();
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o644)).unwrap();
+    
+    let config_path = temp_dir.path().join("job.toml");
+    std::fs::write(&config_path, format!("label = \"script\"\n[program]\npath = \"{}\"\n", script.display())).unwrap();
+    
+    // Only a warning unless strict
+    assert!(ValidationResult::check(&config_path, false).await.valid);
+    
+    let result = ValidationResult::check(&config_path, true).await;
+    assert!(!result.valid);
+    assert!(result.error.unwrap().contains("not executable"));
+    
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+    assert!(ValidationResult::check(&config_path, true).await.valid);
+    
+    std::fs::write(&config_path, "label = \"missing\"\n[program]\npath = \"/nonexistent/program\"\n").unwrap();
+    assert!(!ValidationResult::check(&config_path, true).await.valid);
+}
+
+#[tokio::test]
+async fn test_duplicate_environment_key() {
+    use nusalaunchd::job::validator::ConfigValidator;
+    use nusalaunchd::job::JobConfig;
+    
+    let config: JobConfig = toml::from_str(
+        "label = \"web\"\n[program]\npath = \"/bin/true\"\n\
+         [[environment]]\nkey = \"PATH\"\nvalue = \"/usr/bin\"\n\
+         [[environment]]\nkey = \"PATH\"\nvalue = \"/bin\"\n"
+    ).unwrap();
+    
+    let err = ConfigValidator::validate(&config, false).await.unwrap_err();
+    assert!(err.to_string().contains("Duplicate environment variable key: PATH"));
+    
+    // Loading a config checks it too
+    let err = config.validate().await.unwrap_err();
+    assert!(err.to_string().contains("Duplicate environment variable key: PATH"));
+}
+
+#[tokio::test]
+async fn test_check_dir_reports_duplicate_labels() {
+    let temp_dir = TempDir::new().unwrap();
+    let first = temp_dir.path().join("a.toml");
+    let second = temp_dir.path().join("b.toml");
+    std::fs::write(&first, "label = \"web\"\n[program]\npath = \"/bin/true\"\n").unwrap();
+    std::fs::write(&second, "label = \"web\"\n[program]\npath = \"/bin/true\"\n").unwrap();
+    std::fs::write(temp_dir.path().join("c.toml"), "label = \"db\"\n[program]\npath = \"/bin/true\"\n").unwrap();
+    
+    let results = check_dir(temp_dir.path(), false).await.unwrap();
+    
+    assert!(!results[0].valid);
+    assert!(results[0].error.as_deref().unwrap().contains(&second.display().to_string()));
+    assert!(!results[1].valid);
+    assert!(results[1].error.as_deref().unwrap().contains(&first.display().to_string()));
+    assert!(results[2].valid);
+    
+    let table = render_dir(&results, &OutputFormat::Table).unwrap();
+    assert!(table.contains("Duplicate label 'web'"));
+    assert!(table.ends_with("Validation complete: 1 valid, 2 invalid\n"));
+}
+
+#[tokio::test]
+async fn test_soft_limit_above_hard_limit() {
+    use nusalaunchd::job::validator::ConfigValidator;
+    use nusalaunchd::job::JobConfig;
+    
+    let limits = |nofile: &str| -> JobConfig {
+        toml::from_str(&format!(
+            "label = \"web\"\n[program]\npath = \"/bin/true\"\n[limits]\nnofile = {}\n",
+            nofile
+        )).unwrap()
+    };
+    
+    assert!(ConfigValidator::validate(&limits("{ soft = 1024, hard = 4096 }"), false).await.is_ok());
+    assert!(ConfigValidator::validate(&limits("{ soft = 4096, hard = 4096 }"), false).await.is_ok());
+    
+    let err = ConfigValidator::validate(&limits("{ soft = 8192, hard = 4096 }"), false).await.unwrap_err();
+    assert!(err.to_string().contains("Soft limit for nofile (8192) exceeds hard limit (4096)"), "{}", err);
+}
\ No newline at end of file
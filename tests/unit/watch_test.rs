@@ -0,0 +1,80 @@
+use nusalaunchd::daemon::watch::{apply_change, ConfigWatcher, DEBOUNCE};
+use nusalaunchd::job::{JobEvent, JobManager, ReloadOutcome};
+use std::path::Path;
+use std::time::Duration;
+use tempfile::TempDir;
+
+fn write_config(path: &Path, max_restarts: u32) {
+    let content = format!(
+        "label = \"watched\"\n[program]\npath = \"/bin/true\"\n[supervision]\nkeep_alive = false\nmax_restarts = {}\n",
+        max_restarts
+    );
+    std::fs::write(path, content).unwrap();
+}
+
+#[tokio::test]
+async fn test_apply_change_loads_reloads_and_unloads() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("watched.toml");
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    let mut events = manager.subscribe();
+    
+    write_config(&path, 3);
+    apply_change(&manager, &path).await;
+    assert_eq!(manager.job_for_source(&path).await.as_deref(), Some("watched"));
+    
+    write_config(&path, 7);
+    apply_change(&manager, &path).await;
+    assert_eq!(manager.get_job_status("watched").await.unwrap().config.supervision.max_restarts, 7);
+    
+    std::fs::remove_file(&path).unwrap();
+    apply_change(&manager, &path).await;
+    assert!(manager.get_job_status("watched").await.is_none());
+    
+    let mut seen = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        match event {
+            JobEvent::JobLoaded(label) => seen.push(format!("loaded {}", label)),
+            JobEvent::JobReloaded(label, outcome) => {
+                assert_eq!(outcome, ReloadOutcome::Applied);
+                seen.push(format!("reloaded {}", label));
+            }
+            JobEvent::JobUnloaded(label) => seen.push(format!("unloaded {}", label)),
+            _ => {}
+        }
+    }
+    assert_eq!(seen, vec!["loaded watched", "reloaded watched", "unloaded watched"]);
+}
+
+#[tokio::test]
+async fn test_watcher_debounces_rapid_writes() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("watched.toml");
+    write_config(&path, 1);
+    
+    let (manager, _event_rx) = JobManager::new().await.unwrap();
+    manager.load_job_from(
+        nusalaunchd::job::JobConfig::from_file(&path).await.unwrap(),
+        Some(path.clone()),
+    ).await.unwrap();
+    let mut events = manager.subscribe();
+    
+    let _watcher = ConfigWatcher::start(temp_dir.path(), manager.clone()).unwrap();
+    
+    // Several saves in quick succession end up as one reload of the last version
+    for max_restarts in 2..6 {
+        write_config(&path, max_restarts);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    tokio::time::sleep(DEBOUNCE * 3).await;
+    
+    let mut reloads = 0;
+    while let Ok(event) = events.try_recv() {
+        if let JobEvent::JobReloaded(..) = event {
+            reloads += 1;
+        }
+    }
+    assert_eq!(reloads, 1);
+    assert_eq!(manager.get_job_status("watched").await.unwrap().config.supervision.max_restarts, 5);
+}
\ No newline at end of file
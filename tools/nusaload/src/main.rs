@@ -1,44 +1,116 @@
 use clap::Parser;
-use std::path::PathBuf;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
 #[derive(Parser)]
 #[command(name = "nusaload")]
 #[command(about = "NusaLaunchd control tool", version)]
 struct Cli {
+    /// Socket path
+    #[arg(short = 's', long, default_value = "/run/nusalaunchd/control.sock", global = true)]
+    socket: PathBuf,
+    
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(clap::Subcommand)]
 enum Commands {
-    /// Connect to NusaLaunchd daemon
+    /// Connect to NusaLaunchd daemon and send a raw JSON command
     Connect {
-        /// Socket path
-        #[arg(short = 's', long, default_value = "/run/nusalaunchd/control.sock")]
-        socket: PathBuf,
+        /// Command to send, e.g. '{"cmd":"start","label":"web"}'
+        #[arg(default_value = r#"{"cmd":"list"}"#)]
+        command: String,
+    },
+    
+    /// Show what rescanning the daemon's config directory would add, remove, or update
+    Diff {
+        /// The daemon's config directory; the daemon refuses to compare any other
+        dir: Option<PathBuf>,
+        
+        /// Print the plan as JSON
+        #[arg(long)]
+        json: bool,
     },
     
     /// List available commands
     Help,
 }
 
-fn main() {
+fn main() -> ExitCode {
     let cli = Cli::parse();
     
-    match cli.command {
-        Commands::Connect { socket } => {
-            println!("Connecting to socket: {}", socket.display());
-            // TODO: Implement socket connection
-            println!("(Control tool implementation coming in Week 3-4)");
+    let result = match cli.command {
+        Commands::Connect { command } => {
+            serde_json::from_str::<Value>(&command)
+                .map_err(|e| format!("Invalid command JSON: {}", e))
+                .and_then(|request| send(&cli.socket, &request))
+                .map(|response| print_json(&response))
+        }
+        Commands::Diff { dir, json } => {
+            let dir = dir.map(|dir| dir.canonicalize().unwrap_or(dir));
+            send(&cli.socket, &json!({ "cmd": "diff", "path": dir }))
+                .and_then(into_data)
+                .map(|data| {
+                    if json {
+                        print_json(&data["plan"]);
+                    } else {
+                        println!("{}", data["rendered"].as_str().unwrap_or_default());
+                    }
+                })
         }
         Commands::Help => {
             println!("NusaLaunchd Control Tool (nusaload)");
             println!();
             println!("Available commands:");
-            println!("  connect    - Connect to NusaLaunchd daemon");
+            println!("  connect    - Send a JSON command to the daemon and print the response");
+            println!("  diff       - Preview changes from the daemon's config directory");
             println!("  help       - Show this help message");
             println!();
-            println!("Note: Full control tool implementation will be completed in Week 3-4");
+            println!("Commands: list, status, start, stop, kill, restart, diff, reload-config");
+            Ok(())
         }
+    };
+    
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Send one request line and read back one response line
+fn send(socket: &Path, request: &Value) -> Result<Value, String> {
+    let mut stream = UnixStream::connect(socket)
+        .map_err(|e| format!("Failed to connect to {}: {}", socket.display(), e))?;
+    
+    let mut line = request.to_string();
+    line.push('\n');
+    stream.write_all(line.as_bytes())
+        .map_err(|e| format!("Failed to send command: {}", e))?;
+    
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response)
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+    
+    serde_json::from_str(&response)
+        .map_err(|e| format!("Invalid response from daemon: {}", e))
+}
+
+/// Extract `data` from a response, turning a daemon-side failure into an error
+fn into_data(response: Value) -> Result<Value, String> {
+    if response["ok"].as_bool() == Some(true) {
+        Ok(response["data"].clone())
+    } else {
+        Err(response["error"].as_str().unwrap_or("unknown error").to_string())
     }
+}
+
+fn print_json(value: &Value) {
+    println!("{}", serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string()));
 }
\ No newline at end of file